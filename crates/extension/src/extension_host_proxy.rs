@@ -30,6 +30,7 @@ pub struct ExtensionHostProxy {
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     indexed_docs_provider_proxy: RwLock<Option<Arc<dyn ExtensionIndexedDocsProviderProxy>>>,
     debug_adapter_provider_proxy: RwLock<Option<Arc<dyn ExtensionDebugAdapterProviderProxy>>>,
+    i18n_proxy: RwLock<Option<Arc<dyn ExtensionI18nProxy>>>,
 }
 
 impl ExtensionHostProxy {
@@ -56,6 +57,7 @@ impl ExtensionHostProxy {
             context_server_proxy: RwLock::default(),
             indexed_docs_provider_proxy: RwLock::default(),
             debug_adapter_provider_proxy: RwLock::default(),
+            i18n_proxy: RwLock::default(),
         }
     }
 
@@ -100,6 +102,10 @@ impl ExtensionHostProxy {
             .write()
             .replace(Arc::new(proxy));
     }
+
+    pub fn register_i18n_proxy(&self, proxy: impl ExtensionI18nProxy) {
+        self.i18n_proxy.write().replace(Arc::new(proxy));
+    }
 }
 
 pub trait ExtensionThemeProxy: Send + Sync + 'static {
@@ -218,6 +224,86 @@ pub trait ExtensionGrammarProxy: Send + Sync + 'static {
     fn register_grammars(&self, grammars: Vec<(Arc<str>, PathBuf)>);
 }
 
+pub trait ExtensionI18nProxy: Send + Sync + 'static {
+    /// Registers UI translations provided by the given extension. Any
+    /// extension may register strings, not just language packs.
+    fn register_translations(
+        &self,
+        extension_id: Arc<str>,
+        language: String,
+        translations: Vec<(String, String)>,
+    );
+
+    /// Records the translator credits an extension declared for a language,
+    /// for display in the UI. Called after `register_translations`.
+    fn register_translators(&self, extension_id: Arc<str>, language: String, names: Vec<String>);
+
+    /// Removes every translation the given extension registered. Called when
+    /// the extension is unloaded or uninstalled.
+    fn unregister_translations(&self, extension_id: Arc<str>);
+
+    /// Returns the IETF language tag of the user's current UI language.
+    fn current_language(&self) -> String;
+
+    /// Resolves `key` in the current UI language for the given extension,
+    /// substituting `{name}` placeholders with `args` after lookup. Keys the
+    /// extension registered resolve from its own namespace.
+    fn translate(&self, extension_id: Arc<str>, key: String, args: Vec<(String, String)>)
+    -> String;
+}
+
+impl ExtensionI18nProxy for ExtensionHostProxy {
+    fn register_translations(
+        &self,
+        extension_id: Arc<str>,
+        language: String,
+        translations: Vec<(String, String)>,
+    ) {
+        let Some(proxy) = self.i18n_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_translations(extension_id, language, translations)
+    }
+
+    fn register_translators(&self, extension_id: Arc<str>, language: String, names: Vec<String>) {
+        let Some(proxy) = self.i18n_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_translators(extension_id, language, names)
+    }
+
+    fn unregister_translations(&self, extension_id: Arc<str>) {
+        let Some(proxy) = self.i18n_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_translations(extension_id)
+    }
+
+    fn current_language(&self) -> String {
+        let Some(proxy) = self.i18n_proxy.read().clone() else {
+            return "en".to_string();
+        };
+
+        proxy.current_language()
+    }
+
+    fn translate(
+        &self,
+        extension_id: Arc<str>,
+        key: String,
+        args: Vec<(String, String)>,
+    ) -> String {
+        let Some(proxy) = self.i18n_proxy.read().clone() else {
+            return key;
+        };
+
+        proxy.translate(extension_id, key, args)
+    }
+}
+
 impl ExtensionGrammarProxy for ExtensionHostProxy {
     fn register_grammars(&self, grammars: Vec<(Arc<str>, PathBuf)>) {
         let Some(proxy) = self.grammar_proxy.read().clone() else {
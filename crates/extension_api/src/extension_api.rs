@@ -28,6 +28,7 @@ pub use wit::{
         GithubRelease, GithubReleaseAsset, GithubReleaseOptions, github_release_by_tag_name,
         latest_github_release,
     },
+    zed::extension::i18n::{current_language, register_translations},
     zed::extension::nodejs::{
         node_binary_path, npm_install_package, npm_package_installed_version,
         npm_package_latest_version,
@@ -45,6 +46,44 @@ pub use wit::{
 #[doc(hidden)]
 pub use wit::Guest;
 
+/// Constructs for rendering an extension's UI text in the user's language.
+pub mod i18n {
+    pub use crate::wit::zed::extension::i18n::register_translations;
+
+    /// Returns the IETF language tag of the user's current UI language.
+    pub fn current_lang() -> String {
+        crate::wit::zed::extension::i18n::current_language()
+    }
+
+    /// Resolves a translation key in the user's current UI language, with
+    /// the same fallback behavior Zed's own UI uses. Keys this extension
+    /// registered with [`register_translations`] resolve from its own
+    /// namespace; a key nobody registered returns as itself.
+    pub fn t(key: &str) -> String {
+        crate::wit::zed::extension::i18n::translate(key)
+    }
+
+    /// Like [`t`], but substitutes `{name}` placeholders in the resolved
+    /// text with the given values after lookup.
+    pub fn t_with_args(key: &str, args: &[(String, String)]) -> String {
+        crate::wit::zed::extension::i18n::translate_with_args(key, args)
+    }
+
+    /// Lists the language tags of the translation files this extension
+    /// embeds under `resources/translations/`.
+    pub fn embedded_translation_languages() -> crate::Result<Vec<String>> {
+        crate::wit::zed::extension::i18n::embedded_translation_languages()
+    }
+
+    /// Loads this extension's embedded translation entries for the given
+    /// language, read from the extension's installed resources. Combine
+    /// with [`register_translations`] to register strings the extension
+    /// ships inside itself.
+    pub fn fetch_embedded_translations(language: &str) -> crate::Result<Vec<(String, String)>> {
+        crate::wit::zed::extension::i18n::fetch_embedded_translations(language)
+    }
+}
+
 /// Constructs for interacting with language servers over the
 /// Language Server Protocol (LSP).
 pub mod lsp {
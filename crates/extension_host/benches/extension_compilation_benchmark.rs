@@ -105,6 +105,7 @@ fn wasm_host(cx: &TestAppContext, extensions_dir: &TempTree) -> Arc<WasmHost> {
             NodeRuntime::unavailable(),
             Arc::new(ExtensionHostProxy::new()),
             work_dir,
+            extensions_dir,
             cx,
         )
     })
@@ -15,9 +15,9 @@ pub use extension::ExtensionManifest;
 use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
 use extension::{
     ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy, ExtensionEvents,
-    ExtensionGrammarProxy, ExtensionHostProxy, ExtensionIndexedDocsProviderProxy,
-    ExtensionLanguageProxy, ExtensionLanguageServerProxy, ExtensionSlashCommandProxy,
-    ExtensionSnippetProxy, ExtensionThemeProxy,
+    ExtensionGrammarProxy, ExtensionHostProxy, ExtensionI18nProxy,
+    ExtensionIndexedDocsProviderProxy, ExtensionLanguageProxy, ExtensionLanguageServerProxy,
+    ExtensionSlashCommandProxy, ExtensionSnippetProxy, ExtensionThemeProxy,
 };
 use fs::{Fs, RemoveOptions};
 use futures::{
@@ -241,7 +241,7 @@ impl ExtensionStore {
         let mut this = Self {
             proxy: extension_host_proxy.clone(),
             extension_index: Default::default(),
-            installed_dir,
+            installed_dir: installed_dir.clone(),
             index_path,
             builder: Arc::new(ExtensionBuilder::new(builder_client, build_dir)),
             outstanding_operations: Default::default(),
@@ -253,6 +253,7 @@ impl ExtensionStore {
                 node_runtime,
                 extension_host_proxy,
                 work_dir,
+                installed_dir,
                 cx,
             ),
             wasm_extensions: Vec::new(),
@@ -1121,6 +1122,7 @@ impl ExtensionStore {
             .collect::<Vec<_>>();
         let mut grammars_to_remove = Vec::new();
         for extension_id in &extensions_to_unload {
+            self.proxy.unregister_translations(extension_id.clone());
             let Some(extension) = old_index.extensions.get(extension_id) else {
                 continue;
             };
@@ -1148,11 +1150,18 @@ impl ExtensionStore {
         let mut themes_to_add = Vec::new();
         let mut icon_themes_to_add = Vec::new();
         let mut snippets_to_add = Vec::new();
+        let mut translations_to_add = Vec::new();
         for extension_id in &extensions_to_load {
             let Some(extension) = new_index.extensions.get(extension_id) else {
                 continue;
             };
 
+            // Data-only language packs carry translations without a
+            // compiled extension library.
+            let mut translations_path = self.installed_dir.clone();
+            translations_path.extend([extension_id.as_ref(), "resources", "translations"]);
+            translations_to_add.push((extension_id.clone(), translations_path));
+
             grammars_to_add.extend(extension.manifest.grammars.keys().map(|grammar_name| {
                 let mut grammar_path = self.installed_dir.clone();
                 grammar_path.extend([extension_id.as_ref(), "grammars"]);
@@ -1260,6 +1269,17 @@ impl ExtensionStore {
                                 .log_err();
                         }
                     }
+
+                    for (extension_id, translations_dir) in translations_to_add {
+                        register_i18n_extensions(
+                            proxy.as_ref(),
+                            extension_id,
+                            &translations_dir,
+                            fs.clone(),
+                        )
+                        .await
+                        .log_err();
+                    }
                 }
             })
             .await;
@@ -1692,6 +1712,117 @@ impl ExtensionStore {
     }
 }
 
+/// Registers every translation file an extension ships under
+/// `resources/translations/` (one `<language>.json` file per language).
+/// This is how data-only language packs — `extension.toml` plus JSON, no
+/// compiled library — contribute translations.
+pub async fn register_i18n_extensions(
+    proxy: &ExtensionHostProxy,
+    extension_id: Arc<str>,
+    translations_dir: &Path,
+    fs: Arc<dyn Fs>,
+) -> Result<()> {
+    for (language, files) in embedded_translation_files(fs.as_ref(), translations_dir).await? {
+        let (merged, translators) = merge_translation_files(fs.as_ref(), &files).await?;
+        proxy.register_translations(extension_id.clone(), language.clone(), merged);
+        if !translators.is_empty() {
+            proxy.register_translators(extension_id.clone(), language, translators);
+        }
+    }
+    Ok(())
+}
+
+/// Enumerates the translations embedded under an extension's
+/// `resources/translations/` directory: each language is either one
+/// `<language>.json` file or a `<language>/` directory of per-namespace
+/// files merged on load. Returned sorted by language tag.
+pub async fn embedded_translation_files(
+    fs: &dyn Fs,
+    translations_dir: &Path,
+) -> Result<Vec<(String, Vec<PathBuf>)>> {
+    let mut languages = Vec::new();
+    if !fs.is_dir(translations_dir).await {
+        return Ok(languages);
+    }
+    let mut entries = fs.read_dir(translations_dir).await?;
+    while let Some(entry) = entries.next().await {
+        let path = entry?;
+        let (language, mut files) = if fs.is_dir(&path).await {
+            let Some(language) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let mut files = Vec::new();
+            let mut parts = fs.read_dir(&path).await?;
+            while let Some(part) = parts.next().await {
+                let part = part?;
+                if part.extension() == Some("json".as_ref()) {
+                    files.push(part);
+                }
+            }
+            (language.to_string(), files)
+        } else {
+            if path.extension() != Some("json".as_ref()) {
+                continue;
+            }
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            (language.to_string(), vec![path])
+        };
+        files.sort();
+        languages.push((language, files));
+    }
+    languages.sort();
+    Ok(languages)
+}
+
+/// Merges one language's translation files into a flat entry list, failing
+/// when a key is defined in two files. Also returns the translator credits
+/// collected from the files' reserved `translators` entries.
+pub async fn merge_translation_files(
+    fs: &dyn Fs,
+    files: &[PathBuf],
+) -> Result<(Vec<(String, String)>, Vec<String>)> {
+    let mut merged: Vec<(String, String)> = Vec::new();
+    let mut providers: HashMap<String, PathBuf> = HashMap::default();
+    let mut translators: Vec<String> = Vec::new();
+    for file in files {
+        let contents = fs.load(file).await?;
+        // Non-string values are tolerated and skipped: translation files
+        // carry a numeric `schema_version` entry alongside the strings.
+        let mut entries: serde_json::Map<String, serde_json::Value> =
+            serde_json_lenient::from_str(&contents)
+                .with_context(|| format!("failed to parse translation file {}", file.display()))?;
+        // The reserved `translators` entry credits the people behind the
+        // file; it is surfaced in the UI rather than registered as a
+        // string.
+        if let Some(serde_json::Value::Array(names)) = entries.remove("translators") {
+            for name in names {
+                if let serde_json::Value::String(name) = name {
+                    if !translators.contains(&name) {
+                        translators.push(name);
+                    }
+                }
+            }
+        }
+        for (key, value) in entries {
+            let serde_json::Value::String(value) = value else {
+                continue;
+            };
+            if let Some(other) = providers.get(&key) {
+                bail!(
+                    "key {key} is defined in both {} and {}",
+                    other.display(),
+                    file.display()
+                );
+            }
+            providers.insert(key.clone(), file.clone());
+            merged.push((key, value));
+        }
+    }
+    Ok((merged, translators))
+}
+
 fn load_plugin_queries(root_path: &Path) -> LanguageQueries {
     let mut result = LanguageQueries::default();
     if let Some(entries) = std::fs::read_dir(root_path).log_err() {
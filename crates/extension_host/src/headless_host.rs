@@ -50,6 +50,7 @@ impl HeadlessExtensionStore {
                 node_runtime,
                 extension_host_proxy.clone(),
                 extension_dir.join("work"),
+                extension_dir.clone(),
                 cx,
             ),
             extension_dir,
@@ -47,6 +47,10 @@ pub struct WasmHost {
     pub(crate) proxy: Arc<ExtensionHostProxy>,
     fs: Arc<dyn Fs>,
     pub work_dir: PathBuf,
+    /// Where installed extensions live; embedded resources like translation
+    /// files are read from here rather than the process working directory,
+    /// which is wrong when sandboxed.
+    pub installed_dir: PathBuf,
     _main_thread_message_task: Task<()>,
     main_thread_message_tx: mpsc::UnboundedSender<MainThreadCall>,
 }
@@ -440,6 +444,7 @@ impl WasmHost {
         node_runtime: NodeRuntime,
         proxy: Arc<ExtensionHostProxy>,
         work_dir: PathBuf,
+        installed_dir: PathBuf,
         cx: &mut App,
     ) -> Arc<Self> {
         let (tx, mut rx) = mpsc::unbounded::<MainThreadCall>();
@@ -452,6 +457,7 @@ impl WasmHost {
             engine: wasm_engine(),
             fs,
             work_dir,
+            installed_dir,
             http_client,
             node_runtime,
             proxy,
@@ -666,6 +672,12 @@ impl WasmState {
     fn work_dir(&self) -> PathBuf {
         self.host.work_dir.join(self.manifest.id.as_ref())
     }
+
+    fn translations_dir(&self) -> PathBuf {
+        let mut dir = self.host.installed_dir.clone();
+        dir.extend([self.manifest.id.as_ref(), "resources", "translations"]);
+        dir
+    }
 }
 
 impl wasi::WasiView for WasmState {
@@ -14,7 +14,8 @@ use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use async_trait::async_trait;
 use extension::{
-    ExtensionLanguageServerProxy, KeyValueStoreDelegate, ProjectDelegate, WorktreeDelegate,
+    ExtensionI18nProxy, ExtensionLanguageServerProxy, KeyValueStoreDelegate, ProjectDelegate,
+    WorktreeDelegate,
 };
 use futures::{AsyncReadExt, lock::Mutex};
 use futures::{FutureExt as _, io::BufReader};
@@ -616,6 +617,69 @@ impl nodejs::Host for WasmState {
 #[async_trait]
 impl lsp::Host for WasmState {}
 
+impl i18n::Host for WasmState {
+    async fn register_translations(
+        &mut self,
+        language: String,
+        translations: Vec<(String, String)>,
+    ) -> wasmtime::Result<Result<(), String>> {
+        self.host.proxy.register_translations(
+            self.manifest.id.clone(),
+            language,
+            translations,
+        );
+        Ok(Ok(()))
+    }
+
+    async fn current_language(&mut self) -> wasmtime::Result<String> {
+        Ok(ExtensionI18nProxy::current_language(self.host.proxy.as_ref()))
+    }
+
+    async fn translate(&mut self, key: String) -> wasmtime::Result<String> {
+        Ok(self
+            .host
+            .proxy
+            .translate(self.manifest.id.clone(), key, Vec::new()))
+    }
+
+    async fn translate_with_args(
+        &mut self,
+        key: String,
+        args: Vec<(String, String)>,
+    ) -> wasmtime::Result<String> {
+        Ok(self.host.proxy.translate(self.manifest.id.clone(), key, args))
+    }
+
+    async fn embedded_translation_languages(
+        &mut self,
+    ) -> wasmtime::Result<Result<Vec<String>, String>> {
+        let translations_dir = self.translations_dir();
+        crate::embedded_translation_files(self.host.fs.as_ref(), &translations_dir)
+            .await
+            .map(|languages| languages.into_iter().map(|(language, _)| language).collect())
+            .to_wasmtime_result()
+    }
+
+    async fn fetch_embedded_translations(
+        &mut self,
+        language: String,
+    ) -> wasmtime::Result<Result<Vec<(String, String)>, String>> {
+        let translations_dir = self.translations_dir();
+        maybe!(async {
+            let languages =
+                crate::embedded_translation_files(self.host.fs.as_ref(), &translations_dir).await?;
+            let Some((_, files)) = languages.into_iter().find(|(tag, _)| *tag == language) else {
+                return Ok(Vec::new());
+            };
+            let (entries, _translators) =
+                crate::merge_translation_files(self.host.fs.as_ref(), &files).await?;
+            Ok(entries)
+        })
+        .await
+        .to_wasmtime_result()
+    }
+}
+
 impl From<::http_client::github::GithubRelease> for github::GithubRelease {
     fn from(value: ::http_client::github::GithubRelease) -> Self {
         Self {
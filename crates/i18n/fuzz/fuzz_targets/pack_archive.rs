@@ -0,0 +1,23 @@
+//! Fuzzes the archive install path: format detection, zip and tar
+//! extraction, checksum and schema verification. Run with
+//! `cargo fuzz run pack_archive` from `crates/i18n/fuzz`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::sync::Arc;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(install_dir) = tempfile::tempdir() else {
+        return;
+    };
+    let importer = i18n::I18nImporter::new(
+        Arc::new(http_client::BlockedHttpClient),
+        install_dir.path().to_path_buf(),
+        Vec::new(),
+    );
+    // Extraction stages into a fresh temporary directory, so a malicious
+    // archive that slipped past path sanitization would surface as writes
+    // outside it rather than corrupting real state.
+    let _ = smol::block_on(importer.install(data, None, "fuzz-pack"));
+});
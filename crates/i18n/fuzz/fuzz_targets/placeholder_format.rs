@@ -0,0 +1,18 @@
+//! Fuzzes `{placeholder}` substitution with adversarial templates, names,
+//! and values, since templates come from untrusted packs. Run with
+//! `cargo fuzz run placeholder_format` from `crates/i18n/fuzz`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (String, Vec<(String, String)>)| {
+    let (template, args) = input;
+    let mut fake = i18n::FakeTranslations::new("en");
+    fake.insert("en", "i18n.fuzz.target.key", &template);
+    let mut translated = i18n::TranslatedString::new("i18n.fuzz.target.key");
+    for (name, value) in args {
+        translated = translated.with_arg(name, value);
+    }
+    let _ = translated.resolve_with(&fake);
+});
@@ -0,0 +1,18 @@
+//! Fuzzes translation file parsing and validation, the first code that
+//! touches the contents of a downloaded language pack. Run with
+//! `cargo fuzz run translation_file` from `crates/i18n/fuzz`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(contents) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(file) = i18n::TranslationFile::parse("zz-fuzz", contents) {
+        // Validation walks every entry's key, placeholders, and platform
+        // variants, so it exercises the parsers behind each check.
+        let _ = i18n::validator::I18NValidator::new().validate(&file);
+    }
+});
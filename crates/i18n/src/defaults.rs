@@ -0,0 +1,294 @@
+//! The English reference strings for every translatable key.
+//!
+//! This table is the source of truth for which keys exist: validators treat a
+//! key as "real" if and only if it appears here, and template generation for
+//! new language packs is driven from it.
+
+use collections::HashMap;
+use std::sync::OnceLock;
+
+/// Every translatable key paired with its English default, sorted by key.
+/// [`default_text`] binary-searches this table, so the order is load-bearing
+/// (and checked by a test).
+pub static DEFAULT_TEXTS: &[(&str, &str)] = &[
+    ("i18n.a11y.close_button", "Close"),
+    ("i18n.a11y.language_picker", "Interface language picker"),
+    ("i18n.a11y.menu_bar", "Application menu bar"),
+    (
+        "i18n.command.copy_missing_keys_report",
+        "Copy Missing Keys Report",
+    ),
+    ("i18n.command.open_override_file", "Open Override File"),
+    ("i18n.command.reload_translations", "Reload Translations"),
+    (
+        "i18n.command.report_missing_translation",
+        "Report Missing Translation",
+    ),
+    ("i18n.command.switch_language", "Switch Language"),
+    ("i18n.dialog.cancel", "Cancel"),
+    ("i18n.dialog.dont_save", "Don't Save"),
+    ("i18n.dialog.ok", "OK"),
+    ("i18n.dialog.overwrite", "Overwrite"),
+    ("i18n.dialog.save", "Save"),
+    ("i18n.dialog.unsaved_changes", "This file contains unsaved changes."),
+    ("i18n.menu.edit.copy", "Copy"),
+    ("i18n.menu.edit.cut", "Cut"),
+    ("i18n.menu.edit.find", "Find…"),
+    ("i18n.menu.edit.find_in_project", "Find In Project…"),
+    ("i18n.menu.edit.paste", "Paste"),
+    ("i18n.menu.edit.redo", "Redo"),
+    ("i18n.menu.edit.title", "Edit"),
+    ("i18n.menu.edit.undo", "Undo"),
+    ("i18n.menu.file.close_editor", "Close Editor"),
+    ("i18n.menu.file.close_window", "Close Window"),
+    ("i18n.menu.file.new", "New"),
+    ("i18n.menu.file.new_window", "New Window"),
+    ("i18n.menu.file.open", "Open…"),
+    ("i18n.menu.file.open_recent", "Open Recent"),
+    ("i18n.menu.file.save", "Save"),
+    ("i18n.menu.file.save_all", "Save All"),
+    ("i18n.menu.file.save_as", "Save As…"),
+    ("i18n.menu.file.title", "File"),
+    ("i18n.menu.go.back", "Back"),
+    ("i18n.menu.go.forward", "Forward"),
+    ("i18n.menu.go.go_to_definition", "Go to Definition"),
+    ("i18n.menu.go.go_to_file", "Go to File…"),
+    ("i18n.menu.go.go_to_line_column", "Go to Line/Column…"),
+    ("i18n.menu.go.go_to_symbol_in_project", "Go to Symbol in Project…"),
+    ("i18n.menu.go.title", "Go"),
+    ("i18n.menu.help.documentation", "Documentation"),
+    ("i18n.menu.help.keyboard_shortcuts", "Keyboard Shortcuts"),
+    ("i18n.menu.help.report_issue", "Report Issue"),
+    ("i18n.menu.help.title", "Help"),
+    ("i18n.menu.selection.select_all", "Select All"),
+    ("i18n.menu.selection.title", "Selection"),
+    ("i18n.menu.view.close_all_docks", "Close All Docks"),
+    ("i18n.menu.view.project_panel", "Project Panel"),
+    ("i18n.menu.view.terminal_panel", "Terminal Panel"),
+    ("i18n.menu.view.title", "View"),
+    ("i18n.menu.view.zoom_in", "Zoom In"),
+    ("i18n.menu.view.zoom_out", "Zoom Out"),
+    ("i18n.menu.window.minimize", "Minimize"),
+    ("i18n.menu.window.title", "Window"),
+    ("i18n.menu.window.zoom", "Zoom"),
+    ("i18n.menu.zed.about", "About Zed"),
+    ("i18n.menu.zed.check_for_updates", "Check for Updates"),
+    ("i18n.menu.zed.extensions", "Extensions"),
+    ("i18n.menu.zed.open_settings", "Open Settings"),
+    ("i18n.menu.zed.quit", "Quit"),
+    ("i18n.menu.zed.select_language", "Select Language…"),
+    ("i18n.menu.zed.title", "Zed"),
+    ("i18n.status.cursor_position", "Ln {line}, Col {column}"),
+    ("i18n.status.language_changed", "Language changed to {language}"),
+    ("i18n.status.language_pack_installed", "Language pack {name} installed"),
+    ("i18n.text.ellipsis", "…"),
+    (
+        "i18n.welcome.no_project",
+        "Open a folder to get started, or read the [docs](https://zed.dev/docs) to learn the basics.",
+    ),
+    (
+        "i18n.welcome.tip_command_palette",
+        "Every command lives in the command palette; open it with `cmd-shift-p`.",
+    ),
+];
+
+/// Translator-facing context for keys whose English text alone is ambiguous
+/// or carries constraints (placeholders, length limits). Sparse: most keys
+/// need none.
+pub static KEY_CONTEXTS: &[(&str, &str)] = &[
+    (
+        "i18n.a11y.close_button",
+        "Screen-reader label for a dialog's close control; spoken, never shown",
+    ),
+    (
+        "i18n.a11y.language_picker",
+        "Screen-reader description of the language picker modal; spoken, never shown",
+    ),
+    (
+        "i18n.a11y.menu_bar",
+        "Screen-reader label for the application menu bar; spoken, never shown",
+    ),
+    (
+        "i18n.dialog.save",
+        "Dialog button; the verb \"save\", an action the user confirms",
+    ),
+    (
+        "i18n.menu.edit.title",
+        "Menu bar title; the noun \"Edit\" naming the menu, not the verb",
+    ),
+    (
+        "i18n.menu.file.new",
+        "Menu item; creates a new file, so \"new\" refers to a file",
+    ),
+    (
+        "i18n.menu.file.title",
+        "Menu bar title; the noun \"File\" naming the menu, not the verb",
+    ),
+    (
+        "i18n.menu.view.title",
+        "Menu bar title; the noun \"View\" naming the menu, not the verb",
+    ),
+    (
+        "i18n.menu.window.zoom",
+        "Menu item; zooms the window to fill the screen, not editor zoom",
+    ),
+    (
+        "i18n.status.cursor_position",
+        "Status bar segment; {line} and {column} are 1-based numbers; keep it short",
+    ),
+    (
+        "i18n.status.language_changed",
+        "Status message; {language} is replaced with a language name",
+    ),
+    (
+        "i18n.status.language_pack_installed",
+        "Status message; {name} is replaced with the pack's display name",
+    ),
+    (
+        "i18n.text.ellipsis",
+        "Appended where the UI shortens text; keep it to one or two characters",
+    ),
+    (
+        "i18n.welcome.no_project",
+        "Markdown; translate the link text but keep its target and structure",
+    ),
+    (
+        "i18n.welcome.tip_command_palette",
+        "Markdown; the backtick-quoted keystroke must stay exactly as written",
+    ),
+];
+
+/// Terms that are commonly left identical to English on purpose (brand names,
+/// abbreviations), so an untranslated-value warning for them would be noise.
+pub static SHARED_TERMS: &[&str] = &["Zed", "OK", "URL", "JSON"];
+
+/// Keys whose numeric placeholders always render with plain ASCII digits
+/// and no grouping, even when the active pack declares locale digit shaping
+/// (see [`crate::pack::NumberFormat`]): technical values like line numbers
+/// that users copy into terminals and other tools.
+pub static ASCII_NUMBER_KEYS: &[&str] = &["i18n.status.cursor_position"];
+
+/// Keys whose values are Markdown rather than plain text: longer UI copy
+/// like welcome pages, empty states, and onboarding tips. The validator
+/// checks that translations of these keys preserve the default's Markdown
+/// structure (link targets, code spans, list markers), and the UI renders
+/// them through `markdown::TranslatedMarkdown` instead of a plain label.
+pub static MARKDOWN_KEYS: &[&str] = &[
+    "i18n.welcome.no_project",
+    "i18n.welcome.tip_command_palette",
+];
+
+/// Old key names kept for a deprecation window after a rename, mapped to
+/// their current names. Language packs built against the old schema keep
+/// working through these; `zed-i18n rename-key` appends entries here.
+pub static KEY_ALIASES: &[(&str, &str)] = &[
+    ("i18n.menu.other.settings", "i18n.menu.zed.open_settings"),
+];
+
+/// A map view of [`DEFAULT_TEXTS`] for tooling that wants map semantics.
+/// The fallback path in the manager goes through [`default_text`] instead,
+/// which doesn't touch this lazily built table.
+pub fn default_texts() -> &'static HashMap<&'static str, &'static str> {
+    static MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    MAP.get_or_init(|| DEFAULT_TEXTS.iter().copied().collect())
+}
+
+/// Returns the English default for `key`, if it is a known key. A binary
+/// search over the sorted static table: no lazy initialization, no hashing,
+/// and no heap on the hot fallback path.
+pub fn default_text(key: &str) -> Option<&'static str> {
+    let index = DEFAULT_TEXTS
+        .binary_search_by(|(candidate, _)| candidate.cmp(&key))
+        .ok()?;
+    Some(DEFAULT_TEXTS.get(index)?.1)
+}
+
+/// Resolves a possibly-deprecated key to its current name, following alias
+/// chains in case a key was renamed more than once.
+pub fn canonical_key(key: &str) -> &str {
+    static MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    let aliases = MAP.get_or_init(|| KEY_ALIASES.iter().copied().collect());
+    let mut key = key;
+    // The table is finite and acyclic, but guard against a bad edit
+    // introducing a cycle.
+    for _ in 0..KEY_ALIASES.len() {
+        match aliases.get(key) {
+            Some(current) => key = current,
+            None => break,
+        }
+    }
+    key
+}
+
+/// Returns the translator context for `key`, if any was recorded.
+pub fn key_context(key: &str) -> Option<&'static str> {
+    static MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    MAP.get_or_init(|| KEY_CONTEXTS.iter().copied().collect())
+        .get(key)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_table_is_sorted_and_searchable() {
+        for pair in DEFAULT_TEXTS.windows(2) {
+            assert!(
+                pair[0].0 < pair[1].0,
+                "DEFAULT_TEXTS is out of order at {}",
+                pair[1].0
+            );
+        }
+        for (key, text) in DEFAULT_TEXTS {
+            assert_eq!(default_text(key), Some(*text));
+        }
+        assert_eq!(default_text("i18n.bogus.key"), None);
+    }
+
+    #[test]
+    fn aliases_point_at_real_keys_without_shadowing() {
+        for (old, _) in KEY_ALIASES {
+            assert!(
+                default_text(old).is_none(),
+                "alias {old} shadows a reference key"
+            );
+            assert!(
+                default_text(canonical_key(old)).is_some(),
+                "alias {old} resolves to an unknown key"
+            );
+        }
+        assert_eq!(canonical_key("i18n.menu.file.save"), "i18n.menu.file.save");
+    }
+
+    #[test]
+    fn contexts_only_describe_real_keys() {
+        for (key, context) in KEY_CONTEXTS {
+            assert!(
+                default_text(key).is_some(),
+                "context {context:?} describes unknown key {key}"
+            );
+        }
+    }
+
+    #[test]
+    fn ascii_number_opt_outs_are_real_keys() {
+        for key in ASCII_NUMBER_KEYS {
+            assert!(
+                default_text(key).is_some(),
+                "ASCII_NUMBER_KEYS lists unknown key {key}"
+            );
+        }
+    }
+
+    #[test]
+    fn markdown_keys_are_real_keys() {
+        for key in MARKDOWN_KEYS {
+            assert!(
+                default_text(key).is_some(),
+                "MARKDOWN_KEYS lists unknown key {key}"
+            );
+        }
+    }
+}
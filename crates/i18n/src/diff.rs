@@ -0,0 +1,141 @@
+//! Key-level diffs between two versions of a translation file, for review
+//! tooling and bots commenting on language-pack pull requests.
+
+use serde::{Deserialize, Serialize};
+
+/// The classified difference between two translation maps.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationDiff {
+    /// Keys only the new version has, with their values.
+    pub added: Vec<(String, String)>,
+    /// Keys only the old version had, with their former values.
+    pub removed: Vec<(String, String)>,
+    /// Keys present in both with different values: `(key, old, new)`.
+    pub changed: Vec<(String, String, String)>,
+    /// True when the two versions hold identical entries and only the key
+    /// order differs — a formatting-only change reviewers can wave through.
+    pub reordered_only: bool,
+}
+
+impl TranslationDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && !self.reordered_only
+    }
+
+    /// Renders the diff as Markdown suitable for a pull request comment.
+    pub fn to_markdown(&self) -> String {
+        if self.is_empty() {
+            return "No translation changes.".to_string();
+        }
+        if self.reordered_only {
+            return "Keys were reordered; no values changed.".to_string();
+        }
+        let mut output = String::new();
+        if !self.added.is_empty() {
+            output.push_str("### Added\n\n");
+            for (key, value) in &self.added {
+                output.push_str(&format!("- `{key}`: {value:?}\n"));
+            }
+            output.push('\n');
+        }
+        if !self.removed.is_empty() {
+            output.push_str("### Removed\n\n");
+            for (key, value) in &self.removed {
+                output.push_str(&format!("- `{key}` (was {value:?})\n"));
+            }
+            output.push('\n');
+        }
+        if !self.changed.is_empty() {
+            output.push_str("### Changed\n\n");
+            for (key, old, new) in &self.changed {
+                output.push_str(&format!("- `{key}`: {old:?} → {new:?}\n"));
+            }
+            output.push('\n');
+        }
+        output.trim_end().to_string()
+    }
+}
+
+/// Compares two translation maps key by key. Non-string values are compared
+/// through their JSON rendering.
+pub fn diff_translations(
+    old: &serde_json::Map<String, serde_json::Value>,
+    new: &serde_json::Map<String, serde_json::Value>,
+) -> TranslationDiff {
+    let render = |value: &serde_json::Value| match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+
+    let mut diff = TranslationDiff::default();
+    for (key, new_value) in new {
+        match old.get(key) {
+            None => diff.added.push((key.clone(), render(new_value))),
+            Some(old_value) if old_value != new_value => {
+                diff.changed
+                    .push((key.clone(), render(old_value), render(new_value)));
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, old_value) in old {
+        if !new.contains_key(key) {
+            diff.removed.push((key.clone(), render(old_value)));
+        }
+    }
+
+    diff.reordered_only = diff.added.is_empty()
+        && diff.removed.is_empty()
+        && diff.changed.is_empty()
+        && !old.keys().eq(new.keys());
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn map(pairs: &[(&str, &str)]) -> serde_json::Map<String, serde_json::Value> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::String(value.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let old = map(&[("i18n.a.a.a", "1"), ("i18n.b.b.b", "2"), ("i18n.c.c.c", "3")]);
+        let new = map(&[("i18n.a.a.a", "1"), ("i18n.b.b.b", "two"), ("i18n.d.d.d", "4")]);
+
+        let diff = diff_translations(&old, &new);
+        assert_eq!(diff.added, vec![("i18n.d.d.d".to_string(), "4".to_string())]);
+        assert_eq!(diff.removed, vec![("i18n.c.c.c".to_string(), "3".to_string())]);
+        assert_eq!(
+            diff.changed,
+            vec![("i18n.b.b.b".to_string(), "2".to_string(), "two".to_string())]
+        );
+        assert!(!diff.reordered_only);
+
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("### Added"));
+        assert!(markdown.contains("- `i18n.b.b.b`: \"2\" → \"two\""));
+    }
+
+    #[test]
+    fn detects_pure_reordering() {
+        let old = map(&[("i18n.a.a.a", "1"), ("i18n.b.b.b", "2")]);
+        let new = map(&[("i18n.b.b.b", "2"), ("i18n.a.a.a", "1")]);
+
+        let diff = diff_translations(&old, &new);
+        assert!(diff.reordered_only);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.to_markdown(), "Keys were reordered; no values changed.");
+
+        let identical = diff_translations(&old, &old);
+        assert!(identical.is_empty());
+    }
+}
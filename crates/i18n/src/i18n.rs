@@ -0,0 +1,1096 @@
+//! Runtime translation support for Zed's user interface.
+//!
+//! Translations are keyed by dotted identifiers of the form
+//! `i18n.<area>.<path>` (for example `i18n.menu.file.save`). The English
+//! reference strings live in [`defaults`]; language packs provide flat JSON
+//! files mapping the same keys to translated values.
+
+pub mod defaults;
+pub mod diff;
+pub mod i18n_settings;
+pub mod importer;
+pub mod keys;
+pub mod lang_codes;
+pub mod manager;
+pub mod pack;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_utils;
+pub mod text;
+pub mod validator;
+
+pub use defaults::default_texts;
+pub use i18n_settings::I18nSettings;
+pub use importer::I18nImporter;
+pub use manager::{I18nManager, TranslatedString, Translations};
+pub use text::truncate;
+
+#[cfg(any(test, feature = "test-support"))]
+pub use manager::FakeTranslations;
+
+use anyhow::{Context as _, Result};
+use futures::{FutureExt as _, channel::oneshot, future::Shared};
+use gpui::{App, actions};
+use settings::{Settings as _, SettingsStore};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+actions!(
+    i18n,
+    [
+        CopyCacheStats,
+        CopyMissingKeysReport,
+        OpenOverrideFile,
+        ReloadTranslations,
+        ReportMissingTranslation,
+        SwitchLanguage,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    I18nSettings::register(cx);
+    I18nManager::global().set_key_overlay(I18nSettings::get_global(cx).show_translation_keys);
+
+    if let Err(error) =
+        I18nManager::global().load_user_overrides(paths::user_translation_overrides_file())
+    {
+        log::warn!("failed to load user translation overrides: {error:#}");
+    }
+
+    preload_startup_language(cx);
+    cx.observe_global::<SettingsStore>(apply_language_settings)
+        .detach();
+
+    cx.on_action(|_: &ReloadTranslations, cx| {
+        if let Err(error) =
+            I18nManager::global().load_user_overrides(paths::user_translation_overrides_file())
+        {
+            log::warn!("failed to reload user translation overrides: {error:#}");
+        }
+        cx.refresh_windows();
+    });
+
+    // The i18n_selector crate registers the picker modal for this action on
+    // the workspace; this global handler is the fallback when no workspace
+    // is in the dispatch path, cycling through the registered languages.
+    cx.on_action(|_: &SwitchLanguage, cx| {
+        let manager = I18nManager::global();
+        let languages = manager.available_languages();
+        let current = manager.current_language();
+        let next = match languages.iter().position(|language| *language == current) {
+            Some(index) => languages.get((index + 1) % languages.len()),
+            None => languages.first(),
+        };
+        if let Some(next) = next.cloned() {
+            switch_language(&next, cx);
+        }
+    });
+
+    cx.on_action(|_: &CopyCacheStats, cx| {
+        match serde_json::to_string_pretty(&I18nManager::global().cache_stats()) {
+            Ok(report) => cx.write_to_clipboard(gpui::ClipboardItem::new_string(report)),
+            Err(error) => log::warn!("failed to serialize cache stats: {error:#}"),
+        }
+    });
+
+    cx.on_action(|_: &CopyMissingKeysReport, cx| {
+        match serde_json::to_string_pretty(&I18nManager::global().missing_keys()) {
+            Ok(report) => cx.write_to_clipboard(gpui::ClipboardItem::new_string(report)),
+            Err(error) => log::warn!("failed to serialize missing keys report: {error:#}"),
+        }
+    });
+
+    cx.on_action(|_: &ReportMissingTranslation, cx| {
+        match missing_translation_report_url(paths::language_packs_dir()) {
+            Ok(url) => cx.open_url(url.as_str()),
+            Err(error) => log::warn!("failed to build missing translation report: {error:#}"),
+        }
+    });
+
+    cx.on_action(|_: &OpenOverrideFile, cx| {
+        let path = paths::user_translation_overrides_file();
+        if !path.exists() {
+            if let Err(error) = std::fs::write(path, "{\n}\n") {
+                log::warn!(
+                    "failed to create user translation overrides file {}: {error:#}",
+                    path.display()
+                );
+                return;
+            }
+        }
+        cx.open_with_system(path);
+    });
+}
+
+/// Resolves the configured (or system-negotiated) UI language into the
+/// manager, loading the installed pack for it on first use. Called at
+/// startup and whenever the settings store changes, so hand-editing the
+/// `i18n` section of settings.json switches the language without a restart.
+fn apply_language_settings(cx: &mut App) {
+    let manager = I18nManager::global();
+    let settings = I18nSettings::get_global(cx);
+    apply_format_preferences(settings);
+    manager.set_raw_keys(settings.show_keys);
+    manager.set_cache_capacity(settings.translation_cache_capacity);
+    apply_dev_pack(settings.dev_pack_path.clone(), cx);
+    let target = resolve_target_language(settings, manager);
+    manager.set_fallback_languages(effective_fallback_languages(settings, &target));
+    if manager.current_language() == target {
+        return;
+    }
+    switch_language(&target, cx);
+}
+
+/// The language the settings ask for: the pinned `ui_language`, or the best
+/// system-locale match against the registered and installed languages when
+/// auto-detection is on.
+fn resolve_target_language(settings: &I18nSettings, manager: &I18nManager) -> String {
+    match settings.ui_language.clone() {
+        Some(language) => language,
+        None if settings.auto_detect_system_i18n_lang => {
+            let locales = lang_codes::system_locales();
+            let mut candidates = manager.available_languages();
+            for language in installed_pack_languages() {
+                if !candidates.contains(&language) {
+                    candidates.push(language);
+                }
+            }
+            match lang_codes::negotiate(locales.iter().map(String::as_str), &candidates) {
+                Some(language) => language.clone(),
+                None => manager::DEFAULT_LANGUAGE.to_string(),
+            }
+        }
+        None => manager::DEFAULT_LANGUAGE.to_string(),
+    }
+}
+
+/// The configured fallback chain, or the one derived from the target
+/// language when the setting is empty.
+fn effective_fallback_languages(settings: &I18nSettings, target: &str) -> Vec<String> {
+    if settings.fallback_i18n_langs.is_empty() {
+        lang_codes::fallback_chain(target)
+    } else {
+        settings.fallback_i18n_langs.clone()
+    }
+}
+
+static PRELOAD_COMPLETE: OnceLock<Shared<oneshot::Receiver<()>>> = OnceLock::new();
+
+/// Resolves once the startup preload has published the active language's
+/// strings — immediately when preloading already finished, the startup
+/// language is English, or preloading never ran (tests). First-paint code
+/// awaits this so the initial frame doesn't flash English before switching.
+pub fn preload_complete() -> impl std::future::Future<Output = ()> {
+    let receiver = PRELOAD_COMPLETE.get().cloned();
+    async move {
+        if let Some(receiver) = receiver {
+            // A dropped sender just means the preload was abandoned; the
+            // frame should render what's registered rather than wait.
+            receiver.await.ok();
+        }
+    }
+}
+
+/// Resolves the startup language like [`apply_language_settings`], but loads
+/// its pack (and the nearest fallback's) on the background executor instead
+/// of blocking startup on file reads and JSON parsing. The manager is
+/// lock-guarded and safe to publish into from off the main thread; only the
+/// redraw needs the foreground.
+fn preload_startup_language(cx: &mut App) {
+    let manager = I18nManager::global();
+    let settings = I18nSettings::get_global(cx);
+    apply_format_preferences(settings);
+    manager.set_raw_keys(settings.show_keys);
+    manager.set_cache_capacity(settings.translation_cache_capacity);
+    apply_dev_pack(settings.dev_pack_path.clone(), cx);
+    let target = resolve_target_language(settings, manager);
+    let fallbacks = effective_fallback_languages(settings, &target);
+    let top_fallback = fallbacks.first().cloned();
+    manager.set_fallback_languages(fallbacks);
+
+    let (ready_tx, ready_rx) = oneshot::channel();
+    PRELOAD_COMPLETE.set(ready_rx.shared()).ok();
+    if target == manager::DEFAULT_LANGUAGE {
+        ready_tx.send(()).ok();
+        return;
+    }
+    cx.spawn(async move |cx| {
+        cx.background_spawn(async move {
+            let manager = I18nManager::global();
+            if let Some(fallback) = top_fallback.filter(|fallback| *fallback != target) {
+                register_parent_chain(manager, &fallback);
+                match installed_pack_source(&fallback) {
+                    Ok(Some((source_id, entries, translators))) => {
+                        manager.register_translations(&source_id, &fallback, entries);
+                        manager.set_translators(&source_id, &fallback, translators);
+                    }
+                    Ok(None) => {}
+                    Err(error) => log::warn!(
+                        "failed to preload the fallback language pack for {fallback}: {error:#}"
+                    ),
+                }
+            }
+            register_parent_chain(manager, &target);
+            let source = match installed_pack_source(&target) {
+                Ok(source) => source,
+                Err(error) => {
+                    log::warn!("failed to load the language pack for {target}: {error:#}");
+                    None
+                }
+            };
+            // A settings edit during the preload already switched; don't
+            // clobber the newer choice with the startup one.
+            if manager.current_language() == manager::DEFAULT_LANGUAGE {
+                manager.switch_language(&target, source);
+                telemetry::event!(
+                    "UI Language Changed",
+                    language = target.to_string(),
+                    covered_keys = manager.translated_reference_key_count(&target),
+                    total_keys = defaults::DEFAULT_TEXTS.len(),
+                );
+            }
+        })
+        .await;
+        cx.update(|cx| cx.refresh_windows()).ok();
+        ready_tx.send(()).ok();
+    })
+    .detach();
+}
+
+/// Publishes the per-language formatting overrides to the formatting
+/// layer, so date and time rendering can diverge from the UI language.
+fn apply_format_preferences(settings: &I18nSettings) {
+    use i18n_settings::{DateOrder, FirstDayOfWeek, HourClock};
+
+    time_format::set_format_preferences(time_format::FormatPreferences {
+        date_order: settings.date_format.map(|order| match order {
+            DateOrder::DayMonthYear => time_format::DateOrder::DayMonthYear,
+            DateOrder::MonthDayYear => time_format::DateOrder::MonthDayYear,
+            DateOrder::YearMonthDay => time_format::DateOrder::YearMonthDay,
+        }),
+        twelve_hour_clock: settings
+            .hour_clock
+            .map(|clock| clock == HourClock::Hour12),
+        first_day_of_week: settings.first_day_of_week.map(|day| match day {
+            FirstDayOfWeek::Monday => time_format::Weekday::Monday,
+            FirstDayOfWeek::Tuesday => time_format::Weekday::Tuesday,
+            FirstDayOfWeek::Wednesday => time_format::Weekday::Wednesday,
+            FirstDayOfWeek::Thursday => time_format::Weekday::Thursday,
+            FirstDayOfWeek::Friday => time_format::Weekday::Friday,
+            FirstDayOfWeek::Saturday => time_format::Weekday::Saturday,
+            FirstDayOfWeek::Sunday => time_format::Weekday::Sunday,
+        }),
+    });
+}
+
+/// The dev pack directory currently being watched, and a generation counter
+/// that retires the poll loop for a path that's no longer configured.
+static DEV_PACK_PATH: parking_lot::Mutex<Option<PathBuf>> = parking_lot::Mutex::new(None);
+static DEV_PACK_GENERATION: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+/// Applies the `dev_pack_path` setting: loads the pack source directory at
+/// highest priority and reloads it whenever its files change, so pack
+/// authors can iterate against a running Zed without packaging, importing,
+/// or reinstalling.
+fn apply_dev_pack(path: Option<PathBuf>, cx: &mut App) {
+    use std::sync::atomic::Ordering;
+
+    let mut current = DEV_PACK_PATH.lock();
+    if *current == path {
+        return;
+    }
+    *current = path.clone();
+    drop(current);
+    let generation = DEV_PACK_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let Some(path) = path else {
+        I18nManager::global().clear_dev_translations();
+        cx.refresh_windows();
+        return;
+    };
+    cx.spawn(async move |cx| {
+        // Polling instead of a native watcher: this is a development-only
+        // path, and a half-second delay after a save is fine for the cost
+        // of no watcher plumbing in this crate.
+        let mut fingerprint = Vec::new();
+        loop {
+            if DEV_PACK_GENERATION.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            let changed = {
+                let dir = path.clone();
+                let previous = std::mem::take(&mut fingerprint);
+                let (changed, next) = cx
+                    .background_spawn(async move {
+                        let next = dev_pack_fingerprint(&dir);
+                        if next == previous {
+                            return (false, next);
+                        }
+                        if let Err(error) = load_dev_pack(&dir) {
+                            // A transient parse failure mid-save keeps the
+                            // previous strings; the next change retries.
+                            log::warn!(
+                                "failed to load dev language pack from {}: {error:#}",
+                                dir.display()
+                            );
+                        }
+                        (true, next)
+                    })
+                    .await;
+                fingerprint = next;
+                changed
+            };
+            if changed && cx.update(|cx| cx.refresh_windows()).is_err() {
+                return;
+            }
+            cx.background_executor()
+                .timer(std::time::Duration::from_millis(500))
+                .await;
+        }
+    })
+    .detach();
+}
+
+/// The size and modification time of every file that affects the dev
+/// pack's contents; the poll loop reloads when two snapshots differ.
+fn dev_pack_fingerprint(dir: &Path) -> Vec<(PathBuf, u64, std::time::SystemTime)> {
+    let mut files = Vec::new();
+    let mut paths = vec![
+        dir.join(pack::METADATA_FILE_NAME),
+        dir.join("translation.json"),
+    ];
+    if let Ok(entries) = std::fs::read_dir(dir.join(pack::SPLIT_TRANSLATIONS_DIR_NAME)) {
+        paths.extend(entries.flatten().map(|entry| entry.path()));
+    }
+    paths.sort();
+    for path in paths {
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata
+            .modified()
+            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+        files.push((path, metadata.len(), modified));
+    }
+    files
+}
+
+/// Loads the pack source directory like an installed pack — metadata plus
+/// either `translation.json` or the split `translations/` directory — and
+/// publishes its strings at dev priority.
+fn load_dev_pack(dir: &Path) -> Result<()> {
+    let metadata = pack::PackMetadata::load(dir)?;
+    metadata.check_schema_compatibility()?;
+    let font_hints = metadata.font_hints();
+    let docs_links = metadata.docs_links();
+    let language = metadata.language;
+    let translation_path = dir.join("translation.json");
+    let file = if translation_path.exists() {
+        TranslationFile::load(&language, &translation_path)?
+    } else {
+        TranslationFile::load_merged(&language, &dir.join(pack::SPLIT_TRANSLATIONS_DIR_NAME))?
+    };
+    let manager = I18nManager::global();
+    manager.set_language_parent(&language, metadata.extends);
+    publish_number_format(manager, &language, metadata.number_format);
+    manager.set_font_hints(&language, font_hints);
+    manager.set_docs_links(&language, docs_links);
+    manager.set_dev_translations(
+        &language,
+        file.entries
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string()))),
+    );
+    Ok(())
+}
+
+/// Switches the UI language: loads the installed pack if one provides it,
+/// publishes it together with the language change in one atomic step, and
+/// redraws every window. All language switches (settings edits, the palette
+/// action) funnel through here.
+fn switch_language(target: &str, cx: &mut App) {
+    let manager = I18nManager::global();
+    register_parent_chain(manager, target);
+    let source = match installed_pack_source(target) {
+        Ok(source) => source,
+        Err(error) => {
+            log::warn!("failed to load the language pack for {target}: {error:#}");
+            None
+        }
+    };
+    manager.switch_language(target, source);
+    // Subject to the user's telemetry settings like every other metric;
+    // coverage tells maintainers which languages need investment.
+    telemetry::event!(
+        "UI Language Changed",
+        language = target.to_string(),
+        covered_keys = manager.translated_reference_key_count(target),
+        total_keys = defaults::DEFAULT_TEXTS.len(),
+    );
+    cx.refresh_windows();
+}
+
+/// The packs installed on disk, as (directory name, metadata) pairs. The
+/// directory name doubles as the pack's extension id for store lookups.
+pub fn installed_packs() -> Vec<(String, pack::PackMetadata)> {
+    let mut packs = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(paths::language_packs_dir()) {
+        for entry in entries.flatten() {
+            if let Ok(metadata) = pack::PackMetadata::load(&entry.path()) {
+                packs.push((entry.file_name().to_string_lossy().into_owned(), metadata));
+            }
+        }
+    }
+    packs
+}
+
+/// Publishes the parent chain a sparse pack declares through `extends` in
+/// its metadata: the parent edges are recorded in the manager and each
+/// ancestor's installed pack is registered as a source, so the sparse pack
+/// only needs the keys that differ.
+fn register_parent_chain(manager: &I18nManager, target: &str) {
+    let mut seen = vec![target.to_string()];
+    let mut language = target.to_string();
+    loop {
+        let metadata = installed_pack_metadata(&language);
+        publish_number_format(
+            manager,
+            &language,
+            metadata
+                .as_ref()
+                .and_then(|metadata| metadata.number_format.clone()),
+        );
+        manager.set_font_hints(
+            &language,
+            metadata.as_ref().and_then(|metadata| metadata.font_hints()),
+        );
+        manager.set_docs_links(
+            &language,
+            metadata.as_ref().and_then(|metadata| metadata.docs_links()),
+        );
+        let parent = metadata.and_then(|metadata| metadata.extends);
+        manager.set_language_parent(&language, parent.clone());
+        let Some(parent) = parent else {
+            return;
+        };
+        if seen.contains(&parent) {
+            log::warn!("language pack parent chain for {target} contains a cycle at {parent}");
+            return;
+        }
+        match installed_pack_source(&parent) {
+            Ok(Some((source_id, entries, translators))) => {
+                manager.register_translations(&source_id, &parent, entries);
+                manager.set_translators(&source_id, &parent, translators);
+            }
+            Ok(None) => {}
+            Err(error) => {
+                log::warn!("failed to load the parent language pack {parent}: {error:#}");
+            }
+        }
+        seen.push(parent.clone());
+        language = parent;
+    }
+}
+
+/// The metadata of the installed pack providing `language`, if any.
+fn installed_pack_metadata(language: &str) -> Option<pack::PackMetadata> {
+    installed_packs()
+        .into_iter()
+        .find(|(_, metadata)| metadata.language == language)
+        .map(|(_, metadata)| metadata)
+}
+
+/// Publishes (or clears) the number formatting a pack declares for its
+/// language, dropping a declaration that fails its own checks.
+fn publish_number_format(
+    manager: &I18nManager,
+    language: &str,
+    format: Option<pack::NumberFormat>,
+) {
+    let format = format.filter(|format| match format.check() {
+        Ok(()) => true,
+        Err(error) => {
+            log::warn!("ignoring the number format the {language} pack declares: {error:#}");
+            false
+        }
+    });
+    manager.set_number_format(language, format);
+}
+
+/// The canonical English documentation base. URLs under it are eligible for
+/// routing through the active pack's [`pack::DocsLinks`]; everything else —
+/// and every page the pack declares no routing for — opens as written.
+pub const DOCS_BASE_URL: &str = "https://zed.dev/docs";
+
+/// Routes a documentation URL through the active language's declared docs
+/// links, so `Help → Documentation` and inline "Learn more" links land on
+/// translated docs when the pack provides them. A per-page override wins,
+/// then the pack's `docs_base_url` with the same page path and fragment;
+/// with neither, the URL comes back unchanged.
+pub fn localize_docs_url(url: &str) -> String {
+    let localized = docs_page(url)
+        .and_then(|page| I18nManager::global().docs_links()?.resolve(page));
+    localized.unwrap_or_else(|| url.to_string())
+}
+
+/// Replaces the top-level `description`s in the generated `settings.json`
+/// schema with the active language's `i18n.settings_schema.*` translations,
+/// so hover documentation and completion inside the settings file follow
+/// the UI language. Settings the pack doesn't cover keep the English text
+/// from their doc comments.
+pub fn localize_settings_schema(schema: &mut serde_json::Value) {
+    let manager = I18nManager::global();
+    localize_schema_properties(manager, schema);
+}
+
+/// The top-level properties `SettingsStore::json_schema` duplicates the
+/// whole key set under, one per release stage.
+const RELEASE_STAGE_KEYS: &[&str] = &["dev", "nightly", "stable", "preview"];
+
+fn localize_schema_properties(manager: &I18nManager, schema: &mut serde_json::Value) {
+    let Some(properties) = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.as_object_mut())
+    else {
+        return;
+    };
+    for (key, subschema) in properties.iter_mut() {
+        if RELEASE_STAGE_KEYS.contains(&key.as_str()) {
+            localize_schema_properties(manager, subschema);
+            continue;
+        }
+        let translation =
+            manager.translated_text(&format!("{}{key}", keys::SETTINGS_SCHEMA_PREFIX));
+        if let Some(translation) = translation
+            && let Some(subschema) = subschema.as_object_mut()
+        {
+            subschema.insert(
+                "description".to_string(),
+                translation.to_string().into(),
+            );
+        }
+    }
+}
+
+/// The page path of `url` relative to [`DOCS_BASE_URL`]: `""` for the docs
+/// landing page, fragments included. `None` for URLs outside the docs.
+fn docs_page(url: &str) -> Option<&str> {
+    let rest = url.strip_prefix(DOCS_BASE_URL)?;
+    if rest.is_empty() || rest.starts_with('#') {
+        Some(rest)
+    } else {
+        rest.strip_prefix('/')
+    }
+}
+
+/// The languages the packs installed on disk provide, loaded or not.
+fn installed_pack_languages() -> Vec<String> {
+    installed_packs()
+        .into_iter()
+        .map(|(_, metadata)| metadata.language)
+        .collect()
+}
+
+/// Reloads the installed pack for the current language and redraws. Called
+/// after a pack update lands on disk, so the new strings apply without a
+/// restart.
+pub fn reload_current_language(cx: &mut App) {
+    let manager = I18nManager::global();
+    let language = manager.current_language();
+    register_parent_chain(manager, &language);
+    match installed_pack_source(&language) {
+        Ok(Some(source)) => {
+            manager.switch_language(&language, Some(source));
+            cx.refresh_windows();
+        }
+        Ok(None) => {}
+        Err(error) => {
+            log::warn!("failed to reload the language pack for {language}: {error:#}");
+        }
+    }
+}
+
+/// Loads the installed pack providing `language` from disk, returning its
+/// source id (the pack's directory name), string entries, and translator
+/// credits, ready to hand to [`I18nManager::switch_language`]. `None` when
+/// no installed pack provides that language.
+fn installed_pack_source(
+    language: &str,
+) -> Result<Option<(String, Vec<(String, String)>, Vec<String>)>> {
+    let Ok(entries) = std::fs::read_dir(paths::language_packs_dir()) else {
+        return Ok(None);
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let Ok(metadata) = pack::PackMetadata::load(&dir) else {
+            continue;
+        };
+        if metadata.language != language {
+            continue;
+        }
+        let translation_path = dir.join("translation.json");
+        let file = if translation_path.exists() {
+            TranslationFile::load(language, &translation_path)?
+        } else {
+            TranslationFile::load_merged(language, &dir.join(pack::SPLIT_TRANSLATIONS_DIR_NAME))?
+        };
+        let source_id = entry.file_name().to_string_lossy().into_owned();
+        let strings = file
+            .entries
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+            .collect();
+        let mut translators = metadata.translators;
+        for translator in file.translators {
+            if !translators.contains(&translator) {
+                translators.push(translator);
+            }
+        }
+        return Ok(Some((source_id, strings, translators)));
+    }
+    Ok(None)
+}
+
+/// Decides whether to offer installing a language pack: the settings allow
+/// it, the user hasn't pinned a UI language, and the system's top usable
+/// language preference isn't covered by any registered source. Returns the
+/// normalized language to offer.
+pub fn suggested_language(
+    settings: &I18nSettings,
+    system_locales: &[String],
+    available_languages: &[String],
+) -> Option<String> {
+    if !settings.auto_detect_system_i18n_lang
+        || !settings.suggest_language_packs
+        || settings.ui_language.is_some()
+    {
+        return None;
+    }
+    for locale in system_locales {
+        let Some(language) = lang_codes::normalize_locale(locale) else {
+            continue;
+        };
+        if lang_codes::negotiate([language.as_str()], available_languages).is_some() {
+            // Their most preferred language already renders; English
+            // preferences end up here since the default language is always
+            // available.
+            return None;
+        }
+        return Some(language);
+    }
+    None
+}
+
+/// The repository missing-translation reports go to when the active pack's
+/// metadata doesn't name one.
+const DEFAULT_ISSUE_REPOSITORY: &str = "zed-industries/zed";
+
+/// Gathers the current language, the installed pack for it (if any), and the
+/// session's missing keys into a prefilled GitHub issue URL, targeting the
+/// repository the pack's metadata configures.
+fn missing_translation_report_url(packs_dir: &Path) -> Result<http_client::Url> {
+    let manager = I18nManager::global();
+    let language = manager.current_language();
+    let mut pack = None;
+    if let Ok(entries) = std::fs::read_dir(packs_dir) {
+        for entry in entries.flatten() {
+            if let Ok(metadata) = pack::PackMetadata::load(&entry.path()) {
+                if metadata.language == language {
+                    pack = Some(metadata);
+                    break;
+                }
+            }
+        }
+    }
+    let missing: Vec<String> = manager
+        .missing_keys()
+        .get(&language)
+        .map(|keys| keys.iter().cloned().collect())
+        .unwrap_or_default();
+    report_issue_url(&language, pack.as_ref(), &missing)
+}
+
+fn report_issue_url(
+    language: &str,
+    pack: Option<&pack::PackMetadata>,
+    missing_keys: &[String],
+) -> Result<http_client::Url> {
+    let repository = pack
+        .and_then(|pack| pack.issue_repository.as_deref())
+        .unwrap_or(DEFAULT_ISSUE_REPOSITORY);
+    let mut body = format!("UI language: {language}\n");
+    match pack {
+        Some(pack) => body.push_str(&format!(
+            "Language pack: {} {} (schema {})\n",
+            pack.name, pack.version, pack.schema_version
+        )),
+        None => body.push_str("Language pack: none installed\n"),
+    }
+    body.push_str("\nKeys that had no translation this session:\n");
+    if missing_keys.is_empty() {
+        body.push_str("(none recorded)\n");
+    }
+    // Browsers and GitHub both cap URL length, so long sessions report a
+    // sample rather than the full log.
+    const MAX_REPORTED_KEYS: usize = 50;
+    for key in missing_keys.iter().take(MAX_REPORTED_KEYS) {
+        body.push_str(&format!("- `{key}`\n"));
+    }
+    if missing_keys.len() > MAX_REPORTED_KEYS {
+        body.push_str(&format!(
+            "…and {} more\n",
+            missing_keys.len() - MAX_REPORTED_KEYS
+        ));
+    }
+    let url = http_client::Url::parse_with_params(
+        &format!("https://github.com/{repository}/issues/new"),
+        &[
+            ("title", format!("Missing translations for {language}")),
+            ("body", body),
+        ],
+    )
+    .context("failed to build issue URL")?;
+    Ok(url)
+}
+
+/// A parsed translation file for a single language.
+///
+/// Entries preserve the order they appeared in on disk, which lets tooling
+/// rewrite files without churning unrelated lines.
+#[derive(Debug, Clone)]
+pub struct TranslationFile {
+    /// The IETF language tag this file provides translations for, e.g.
+    /// `zh-CN`.
+    pub language: String,
+    /// The schema version the file declared on disk (1 when it declared
+    /// none). The entries themselves are migrated to the current schema
+    /// during parsing.
+    pub schema_version: u32,
+    /// The translator credits the file declared in its reserved
+    /// [`pack::TRANSLATORS_KEY`] entry, if any.
+    pub translators: Vec<String>,
+    pub entries: serde_json::Map<String, serde_json::Value>,
+}
+
+impl TranslationFile {
+    /// Parses a translation file. Comments and trailing commas are accepted,
+    /// since templates and reorganized files carry `//` category comments.
+    /// Files written against an older key schema are migrated best-effort;
+    /// files from a newer schema are rejected.
+    pub fn parse(language: impl Into<String>, contents: &str) -> Result<Self> {
+        let mut entries: serde_json::Map<String, serde_json::Value> =
+            serde_json_lenient::from_str(contents).context("failed to parse translation file")?;
+        let schema_version = match entries.remove(pack::SCHEMA_VERSION_KEY) {
+            Some(value) => u32::try_from(
+                value
+                    .as_u64()
+                    .context("schema_version must be an integer")?,
+            )?,
+            None => 1,
+        };
+        anyhow::ensure!(
+            schema_version <= pack::CURRENT_SCHEMA_VERSION,
+            "translation file declares schema version {schema_version}, but this build only \
+             supports up to {}",
+            pack::CURRENT_SCHEMA_VERSION,
+        );
+        let translators = match entries.remove(pack::TRANSLATORS_KEY) {
+            Some(value) => value
+                .as_array()
+                .context("translators must be an array of strings")?
+                .iter()
+                .map(|name| {
+                    Ok(name
+                        .as_str()
+                        .context("translators must be an array of strings")?
+                        .to_string())
+                })
+                .collect::<Result<Vec<String>>>()?,
+            None => Vec::new(),
+        };
+        pack::migrate_entries(&mut entries, schema_version);
+        Ok(Self {
+            language: language.into(),
+            schema_version,
+            translators,
+            entries,
+        })
+    }
+
+    pub fn load(language: impl Into<String>, path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("failed to read translation file {}", path.display()))?;
+        let contents = validator::check_encoding(&bytes)
+            .with_context(|| format!("translation file {} is not plain UTF-8", path.display()))?;
+        Self::parse(language, contents)
+    }
+
+    /// Loads a split translation set: every `.json` file in `dir`, merged
+    /// into one file. Each part is parsed (and schema-migrated) on its own;
+    /// a key provided by more than one part is an error rather than a silent
+    /// last-writer-wins. The merged file's declared schema version is the
+    /// oldest among the parts.
+    pub fn load_merged(language: impl Into<String>, dir: &Path) -> Result<Self> {
+        let language = language.into();
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)
+            .with_context(|| format!("failed to read {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        anyhow::ensure!(
+            !paths.is_empty(),
+            "no translation files in {}",
+            dir.display()
+        );
+
+        let mut merged = Self {
+            language: language.clone(),
+            schema_version: pack::CURRENT_SCHEMA_VERSION,
+            translators: Vec::new(),
+            entries: serde_json::Map::new(),
+        };
+        let mut providers: std::collections::HashMap<String, PathBuf> =
+            std::collections::HashMap::new();
+        for path in paths {
+            let part = Self::load(language.clone(), &path)?;
+            merged.schema_version = merged.schema_version.min(part.schema_version);
+            for translator in part.translators {
+                if !merged.translators.contains(&translator) {
+                    merged.translators.push(translator);
+                }
+            }
+            for (key, value) in part.entries {
+                if let Some(other) = providers.get(&key) {
+                    anyhow::bail!(
+                        "key {key} is defined in both {} and {}",
+                        other.display(),
+                        path.display()
+                    );
+                }
+                providers.insert(key.clone(), path.clone());
+                merged.entries.insert(key, value);
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Returns the translation for `key`, if present and a string.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).and_then(|value| value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_packs_are_suggested_only_for_uncovered_system_languages() {
+        let settings = I18nSettings {
+            ui_language: None,
+            fallback_i18n_langs: Vec::new(),
+            auto_detect_system_i18n_lang: true,
+            suggest_language_packs: true,
+            recently_used_languages: Vec::new(),
+            dev_pack_path: None,
+            trusted_language_pack_keys: Vec::new(),
+            date_format: None,
+            hour_clock: None,
+            first_day_of_week: None,
+            translation_cache_capacity: 1000,
+            show_translation_keys: false,
+            show_keys: false,
+        };
+        let english_only = vec!["en".to_string()];
+
+        let locales = vec!["zh-CN".to_string()];
+        assert_eq!(
+            suggested_language(&settings, &locales, &english_only),
+            Some("zh-CN".to_string())
+        );
+        // A later English preference doesn't mask an uncovered first choice…
+        let locales = vec!["fr".to_string(), "en".to_string()];
+        assert_eq!(
+            suggested_language(&settings, &locales, &english_only),
+            Some("fr".to_string())
+        );
+        // …but a covered first choice ends the search.
+        let locales = vec!["en-US".to_string(), "fr".to_string()];
+        assert_eq!(suggested_language(&settings, &locales, &english_only), None);
+
+        let locales = vec!["zh-CN".to_string()];
+        let with_pack = vec!["en".to_string(), "zh-CN".to_string()];
+        assert_eq!(suggested_language(&settings, &locales, &with_pack), None);
+
+        let pinned = I18nSettings {
+            ui_language: Some("ja".to_string()),
+            ..settings.clone()
+        };
+        assert_eq!(suggested_language(&pinned, &locales, &english_only), None);
+        let never_ask = I18nSettings {
+            suggest_language_packs: false,
+            ..settings.clone()
+        };
+        assert_eq!(
+            suggested_language(&never_ask, &locales, &english_only),
+            None
+        );
+    }
+
+    #[test]
+    fn settings_schema_descriptions_follow_the_language() {
+        crate::test_utils::with_locale(
+            "zz-schema-test",
+            &[("i18n.settings_schema.theme", "界面主题。")],
+            || {
+                let mut schema = serde_json::json!({
+                    "properties": {
+                        "theme": { "description": "The theme." },
+                        "vim_mode": { "description": "Whether vim mode is enabled." },
+                        "stable": {
+                            "properties": {
+                                "theme": { "description": "The theme." },
+                            },
+                        },
+                    },
+                });
+                localize_settings_schema(&mut schema);
+                assert_eq!(schema["properties"]["theme"]["description"], "界面主题。");
+                // Untranslated settings keep their English doc comments.
+                assert_eq!(
+                    schema["properties"]["vim_mode"]["description"],
+                    "Whether vim mode is enabled."
+                );
+                // The release-stage copies of the key set localize too.
+                assert_eq!(
+                    schema["properties"]["stable"]["properties"]["theme"]["description"],
+                    "界面主题。"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn docs_urls_route_through_the_active_packs_links() {
+        let _guard = crate::test_utils::GLOBAL_MANAGER_LOCK.lock();
+        let manager = I18nManager::global();
+        let previous_language = manager.current_language();
+        let mut overrides = std::collections::BTreeMap::new();
+        overrides.insert(
+            "vim".to_string(),
+            "https://example.com/zh/vim-mode".to_string(),
+        );
+        manager.set_docs_links(
+            "zz-docs-route-test",
+            Some(pack::DocsLinks {
+                base_url: Some("https://zed.dev/zh/docs".to_string()),
+                overrides,
+            }),
+        );
+        manager.set_current_language("zz-docs-route-test");
+
+        assert_eq!(
+            localize_docs_url("https://zed.dev/docs/"),
+            "https://zed.dev/zh/docs"
+        );
+        assert_eq!(
+            localize_docs_url("https://zed.dev/docs/repl#changing-kernels"),
+            "https://zed.dev/zh/docs/repl#changing-kernels"
+        );
+        assert_eq!(
+            localize_docs_url("https://zed.dev/docs/vim"),
+            "https://example.com/zh/vim-mode"
+        );
+        // Non-docs URLs pass through untouched.
+        assert_eq!(
+            localize_docs_url("https://zed.dev/jobs"),
+            "https://zed.dev/jobs"
+        );
+
+        manager.set_docs_links("zz-docs-route-test", None);
+        assert_eq!(
+            localize_docs_url("https://zed.dev/docs/vim"),
+            "https://zed.dev/docs/vim"
+        );
+        manager.set_current_language(&previous_language);
+    }
+
+    #[test]
+    fn report_urls_target_the_packs_repository_and_carry_the_missing_keys() {
+        let metadata = pack::PackMetadata {
+            name: "简体中文".to_string(),
+            language: "zh-CN".to_string(),
+            version: "1.2.0".to_string(),
+            schema_version: pack::CURRENT_SCHEMA_VERSION,
+            translators: Vec::new(),
+            issue_repository: Some("someone/zh-pack".to_string()),
+            extends: None,
+            number_format: None,
+            preferred_fonts: Vec::new(),
+            requires_cjk_fallback: false,
+            docs_base_url: None,
+            docs_overrides: Default::default(),
+        };
+        let url = report_issue_url(
+            "zh-CN",
+            Some(&metadata),
+            &["i18n.menu.file.save".to_string()],
+        )
+        .unwrap();
+        assert!(
+            url.as_str()
+                .starts_with("https://github.com/someone/zh-pack/issues/new?")
+        );
+        let body = url
+            .query_pairs()
+            .find(|(name, _)| name == "body")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+        assert!(body.contains("UI language: zh-CN"));
+        assert!(body.contains("简体中文 1.2.0"));
+        assert!(body.contains("- `i18n.menu.file.save`"));
+
+        let url = report_issue_url("zh-CN", None, &[]).unwrap();
+        assert!(
+            url.as_str().starts_with(&format!(
+                "https://github.com/{DEFAULT_ISSUE_REPOSITORY}/issues/new?"
+            ))
+        );
+    }
+
+    #[test]
+    fn merges_split_files_and_rejects_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("menu.json"),
+            r#"{"schema_version": 2, "i18n.menu.file.save": "保存"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("dialog.json"),
+            r#"{"schema_version": 2, "i18n.dialog.ok": "确定"}"#,
+        )
+        .unwrap();
+
+        let merged = TranslationFile::load_merged("zh-CN", dir.path()).unwrap();
+        assert_eq!(merged.get("i18n.menu.file.save"), Some("保存"));
+        assert_eq!(merged.get("i18n.dialog.ok"), Some("确定"));
+        assert_eq!(merged.schema_version, 2);
+
+        std::fs::write(
+            dir.path().join("extra.json"),
+            r#"{"i18n.menu.file.save": "另存"}"#,
+        )
+        .unwrap();
+        let error = TranslationFile::load_merged("zh-CN", dir.path()).unwrap_err();
+        assert!(error.to_string().contains("i18n.menu.file.save"));
+        assert!(error.to_string().contains("is defined in both"));
+    }
+}
@@ -0,0 +1,258 @@
+use anyhow::Result;
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, JsonSchema)]
+pub struct I18nSettings {
+    /// The IETF language tag to use for the UI, e.g. `"zh-CN"`.
+    ///
+    /// When unset, the language is negotiated from the system's locale
+    /// preferences. This can also be set in a project's local settings to
+    /// override the UI language for that project only.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub ui_language: Option<String>,
+    /// The languages to try, in order, when a string has no translation in
+    /// the UI language, before falling back to the built-in English text.
+    ///
+    /// When empty, the chain is derived from the UI language by dropping
+    /// subtags, e.g. `"zh-Hant-TW"` falls back through `"zh-Hant"` and
+    /// `"zh"`. A single string is accepted for compatibility with the old
+    /// `fallback_i18n_lang` setting.
+    ///
+    /// Default: []
+    #[serde(
+        default,
+        alias = "fallback_i18n_lang",
+        deserialize_with = "one_or_many"
+    )]
+    pub fallback_i18n_langs: Vec<String>,
+    /// Whether to match the system's preferred languages against the
+    /// installed packs, and suggest installing a pack when the system
+    /// prefers a language none of them provides.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub auto_detect_system_i18n_lang: bool,
+    /// Whether the language pack suggestion may be shown. Choosing "Never
+    /// Ask Again" on the suggestion sets this to false.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub suggest_language_packs: bool,
+    /// The languages most recently chosen in the UI language picker, newest
+    /// first. Maintained by the picker, which lists them in its "Recent"
+    /// section.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub recently_used_languages: Vec<String>,
+    /// A language pack source directory to load at highest priority,
+    /// reloaded automatically when its files change — so pack authors can
+    /// iterate against a running Zed without packaging or reinstalling. The
+    /// directory uses the installed-pack layout: a `metadata.json` next to
+    /// either `translation.json` or a `translations/` directory.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub dev_pack_path: Option<std::path::PathBuf>,
+    /// PEM-encoded RSA public keys that language pack downloads must be
+    /// signed by.
+    ///
+    /// When empty, packs are still required to carry a valid embedded
+    /// checksum manifest, but no signature is required.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub trusted_language_pack_keys: Vec<String>,
+    /// Overrides the order of day, month, and year in short numeric dates,
+    /// for users who want an English UI with local date conventions (or
+    /// vice versa). When unset, the order follows the system locale.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub date_format: Option<DateOrder>,
+    /// Overrides whether times are shown on a 12-hour or 24-hour clock.
+    /// When unset, the clock follows the system locale.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub hour_clock: Option<HourClock>,
+    /// Overrides the first day of the week in calendar-like UI. When unset,
+    /// it follows the system locale's region.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub first_day_of_week: Option<FirstDayOfWeek>,
+    /// How many resolved strings the translation cache keeps. Higher values
+    /// trade memory for fewer repeated lookups; zero disables the cache.
+    /// The `i18n: copy cache stats` command reports hit, miss, and eviction
+    /// counts for tuning this with data.
+    ///
+    /// Default: 1000
+    #[serde(default = "default_translation_cache_capacity")]
+    pub translation_cache_capacity: usize,
+    /// Whether to suffix every translated string with the `i18n.*` key it
+    /// resolved from, so on-screen text can be mapped back to its key. Also
+    /// toggled at runtime with the `zed: toggle i18n key overlay` command.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub show_translation_keys: bool,
+    /// Whether to render every translated string as its `i18n.*` key instead
+    /// of its text, so QA screenshots identify every string on screen. For
+    /// keeping the text and only annotating it with the key, see
+    /// `show_translation_keys`.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub show_keys: bool,
+}
+
+impl Settings for I18nSettings {
+    const KEY: Option<&'static str> = Some("i18n");
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _cx: &mut App) -> Result<Self> {
+        // Project settings participate so a project can pin its own UI
+        // language.
+        SettingsSources::<Self::FileContent>::json_merge_with(
+            [sources.default]
+                .into_iter()
+                .chain(sources.user)
+                .chain(sources.server)
+                .chain(sources.project.iter().copied()),
+        )
+    }
+
+    fn import_from_vscode(vscode: &settings::VsCodeSettings, current: &mut Self::FileContent) {
+        // VS Code keeps its display language in argv.json, which the import
+        // flow doesn't read; "locale" in settings.json is the older location
+        // and the one settings exports carry.
+        if let Some(language) = vscode
+            .read_string("locale")
+            .and_then(crate::lang_codes::normalize_locale)
+        {
+            current.ui_language = Some(language);
+            // An explicitly configured locale is a pinned choice, not a
+            // detected one.
+            current.auto_detect_system_i18n_lang = false;
+        }
+    }
+}
+
+/// The order of the day, month, and year fields in short numeric dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DateOrder {
+    /// `10/04/1990`
+    DayMonthYear,
+    /// `04/10/1990`
+    MonthDayYear,
+    /// `1990-04-10`
+    YearMonthDay,
+}
+
+/// Whether times are shown on a 12-hour or 24-hour clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum HourClock {
+    #[serde(rename = "12-hour")]
+    Hour12,
+    #[serde(rename = "24-hour")]
+    Hour24,
+}
+
+/// The first day of the week in calendar-like UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FirstDayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_translation_cache_capacity() -> usize {
+    crate::manager::DEFAULT_TRANSLATION_CACHE_CAPACITY
+}
+
+/// Accepts either a single language tag or a list of them, so settings
+/// written against the old `fallback_i18n_lang: Option<String>` shape keep
+/// deserializing.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(language)) => vec![language],
+        Some(OneOrMany::Many(languages)) => languages,
+        None => Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::VsCodeSettings;
+
+    #[test]
+    fn the_old_singular_fallback_key_still_deserializes() {
+        let settings: I18nSettings =
+            serde_json_lenient::from_str(r#"{ "fallback_i18n_lang": "zh-Hans" }"#).unwrap();
+        assert_eq!(settings.fallback_i18n_langs, ["zh-Hans"]);
+
+        let settings: I18nSettings =
+            serde_json_lenient::from_str(r#"{ "fallback_i18n_langs": ["zh-Hans", "zh"] }"#)
+                .unwrap();
+        assert_eq!(settings.fallback_i18n_langs, ["zh-Hans", "zh"]);
+
+        let settings: I18nSettings = serde_json_lenient::from_str("{}").unwrap();
+        assert!(settings.fallback_i18n_langs.is_empty());
+    }
+
+    #[test]
+    fn vscode_locale_imports_as_a_pinned_ui_language() {
+        let vscode = VsCodeSettings::from_str(r#"{ "locale": "zh_cn" }"#).unwrap();
+        let mut settings = I18nSettings {
+            ui_language: None,
+            fallback_i18n_langs: Vec::new(),
+            auto_detect_system_i18n_lang: true,
+            suggest_language_packs: true,
+            recently_used_languages: Vec::new(),
+            dev_pack_path: None,
+            trusted_language_pack_keys: Vec::new(),
+            date_format: None,
+            hour_clock: None,
+            first_day_of_week: None,
+            translation_cache_capacity: 1000,
+            show_translation_keys: false,
+            show_keys: false,
+        };
+        I18nSettings::import_from_vscode(&vscode, &mut settings);
+        assert_eq!(settings.ui_language.as_deref(), Some("zh-CN"));
+        assert!(!settings.auto_detect_system_i18n_lang);
+
+        let vscode = VsCodeSettings::from_str("{}").unwrap();
+        let mut untouched = settings.clone();
+        I18nSettings::import_from_vscode(&vscode, &mut untouched);
+        assert_eq!(untouched.ui_language.as_deref(), Some("zh-CN"));
+    }
+}
@@ -0,0 +1,1036 @@
+//! Downloading and installing language packs.
+//!
+//! A language pack is a zip archive containing translation JSON files plus a
+//! `metadata.json` describing the pack. Packs are installed into a
+//! subdirectory of the extensions directory so that the extension system can
+//! pick them up.
+//!
+//! Packs downloaded from arbitrary URLs are never installed unverified: the
+//! archive must embed a `checksums.sha256` manifest covering every file, and
+//! if any trusted signing keys are configured, the download must additionally
+//! carry a detached signature made by one of them.
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use futures::AsyncReadExt as _;
+use http_client::HttpClient;
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::pkcs8::DecodePublicKey as _;
+use rsa::sha2::Sha256 as RsaSha256;
+use rsa::signature::Verifier as _;
+use rsa::RsaPublicKey;
+use sha2::{Digest as _, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The name of the checksum manifest every pack archive must contain.
+pub const CHECKSUM_MANIFEST_NAME: &str = "checksums.sha256";
+
+/// Progress reported while an import runs, in phase order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportProgress {
+    Downloading {
+        received_bytes: usize,
+        /// From the Content-Length header, when the server sends one.
+        total_bytes: Option<u64>,
+    },
+    Verifying,
+    Extracting,
+    Installing,
+}
+
+/// A cloneable handle that cancels an in-flight import.
+///
+/// Cancellation is checked between phases and between download chunks; a
+/// cancelled import fails with an error and leaves the install directory
+/// untouched.
+#[derive(Clone, Default)]
+pub struct ImportCancellationHandle(Arc<std::sync::atomic::AtomicBool>);
+
+impl ImportCancellationHandle {
+    pub fn cancel(&self) {
+        self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    fn check(&self) -> Result<()> {
+        anyhow::ensure!(
+            !self.0.load(std::sync::atomic::Ordering::SeqCst),
+            "language pack import was cancelled"
+        );
+        Ok(())
+    }
+}
+
+/// Downloads, verifies, and installs language packs.
+pub struct I18nImporter {
+    http_client: Arc<dyn HttpClient>,
+    /// The directory packs are installed into, normally
+    /// [`paths::extensions_dir`].
+    install_dir: PathBuf,
+    /// PEM-encoded RSA public keys from the
+    /// `i18n.trusted_language_pack_keys` setting. When non-empty, packs must
+    /// be signed by one of these keys.
+    trusted_keys: Vec<String>,
+    progress: Option<Arc<dyn Fn(ImportProgress) + Send + Sync>>,
+    cancellation: ImportCancellationHandle,
+    /// Where downloaded archives are kept for offline reinstalls, when set.
+    cache_dir: Option<PathBuf>,
+}
+
+impl I18nImporter {
+    pub fn new(
+        http_client: Arc<dyn HttpClient>,
+        install_dir: PathBuf,
+        trusted_keys: Vec<String>,
+    ) -> Self {
+        Self {
+            http_client,
+            install_dir,
+            trusted_keys,
+            progress: None,
+            cancellation: ImportCancellationHandle::default(),
+            cache_dir: None,
+        }
+    }
+
+    /// Keeps a copy of every downloaded archive in `cache_dir`, and falls
+    /// back to that copy when the same URL can't be reached again — so a
+    /// pack that was installed once can be reinstalled offline.
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Registers a callback invoked as the import moves through its phases.
+    pub fn with_progress(mut self, callback: impl Fn(ImportProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns a handle that cancels this importer's in-flight work.
+    pub fn cancellation_handle(&self) -> ImportCancellationHandle {
+        self.cancellation.clone()
+    }
+
+    fn report(&self, progress: ImportProgress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+
+    /// Downloads the pack at `url` and installs it as `pack_name`.
+    ///
+    /// Besides direct archive URLs, `url` may reference a GitHub repository —
+    /// `github:owner/repo`, `github:owner/repo@tag`,
+    /// `https://github.com/owner/repo`, or a release-tag page URL — in which
+    /// case the archive asset of the referenced release (the latest release,
+    /// if no tag is given) is downloaded.
+    ///
+    /// When trusted keys are configured, a detached signature is fetched from
+    /// `<url>.sig` and verified against them before anything is written to
+    /// the install directory.
+    pub async fn import_from_url(&self, url: &str, pack_name: &str) -> Result<PathBuf> {
+        let url = match parse_github_repo(url) {
+            Some((repo, tag)) => self.resolve_github_release(&repo, tag.as_deref()).await?,
+            None => url.to_string(),
+        };
+        let url = url.as_str();
+        let archive = self.download(url).await?;
+        let signature = if self.trusted_keys.is_empty() {
+            None
+        } else {
+            let signature_url = format!("{url}.sig");
+            Some(self.download(&signature_url).await.with_context(|| {
+                format!("failed to download detached signature from {signature_url}")
+            })?)
+        };
+        self.install(&archive, signature.as_deref(), pack_name).await
+    }
+
+    /// Imports a pack published to Zed's extension store, referenced by its
+    /// extension id (e.g. `i18n-ja`), and installs it under that id.
+    ///
+    /// The id is resolved to the store's download URL for the extension's
+    /// latest version through `client`, whose base URL decides which store
+    /// (production, staging, a local server) is asked. The download then
+    /// goes through exactly the same verification and install pipeline as
+    /// [`Self::import_from_url`], so the selector UI and the CLI share one
+    /// code path.
+    pub async fn import_from_marketplace(
+        &self,
+        client: &http_client::HttpClientWithUrl,
+        extension_id: &str,
+    ) -> Result<PathBuf> {
+        let url = marketplace_download_url(client, extension_id)?;
+        self.import_from_url(url.as_str(), extension_id).await
+    }
+
+    /// Checks each installed pack — an extension id paired with its
+    /// installed version — against the extension store and returns the ones
+    /// with a newer published version, so the app can offer a one-click
+    /// update. New Zed releases add keys that stale packs don't carry.
+    pub async fn check_for_updates(
+        &self,
+        client: &http_client::HttpClientWithUrl,
+        installed: &[(String, String)],
+    ) -> Result<Vec<PackUpdate>> {
+        let mut updates = Vec::new();
+        for (extension_id, installed_version) in installed {
+            let Some(latest_version) = self
+                .latest_marketplace_version(client, extension_id)
+                .await?
+            else {
+                continue;
+            };
+            if version_is_newer(&latest_version, installed_version) {
+                updates.push(PackUpdate {
+                    extension_id: extension_id.clone(),
+                    installed_version: installed_version.clone(),
+                    latest_version,
+                });
+            }
+        }
+        Ok(updates)
+    }
+
+    /// The newest version of `extension_id` published to the extension
+    /// store, or `None` when the store lists no versions for it.
+    pub async fn latest_marketplace_version(
+        &self,
+        client: &http_client::HttpClientWithUrl,
+        extension_id: &str,
+    ) -> Result<Option<String>> {
+        let url = marketplace_versions_url(client, extension_id)?;
+        let body = self.fetch(url.as_str()).await?;
+        latest_version_in_response(&body)
+    }
+
+    /// Imports a pack from a local path: either an archive file or a plain
+    /// directory laid out like an extracted pack.
+    ///
+    /// Local imports skip signature verification — they are used for packs
+    /// the user already has on disk — but the checksum manifest is still
+    /// required and verified.
+    pub async fn import_from_path(&self, path: &Path, pack_name: &str) -> Result<PathBuf> {
+        let metadata = smol::fs::metadata(path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if metadata.is_dir() {
+            self.cancellation.check()?;
+            self.report(ImportProgress::Verifying);
+            verify_checksum_manifest(path).await?;
+            check_schema(path)?;
+            check_translation_encoding(path).await?;
+            self.cancellation.check()?;
+            self.report(ImportProgress::Installing);
+            self.install_staged(path, pack_name).await
+        } else {
+            let archive = smol::fs::read(path).await?;
+            self.cancellation.check()?;
+            self.report(ImportProgress::Verifying);
+            self.extract_and_install(&archive, pack_name).await
+        }
+    }
+
+    /// Verifies and installs an already-downloaded pack archive. Both zip
+    /// and gzipped tar archives are accepted; the format is detected from
+    /// the archive's magic bytes.
+    pub async fn install(
+        &self,
+        archive: &[u8],
+        signature: Option<&[u8]>,
+        pack_name: &str,
+    ) -> Result<PathBuf> {
+        self.cancellation.check()?;
+        self.report(ImportProgress::Verifying);
+        self.verify_signature(archive, signature)?;
+        self.extract_and_install(archive, pack_name).await
+    }
+
+    /// Extracts an archive into staging, verifies its checksum manifest and
+    /// contents, and installs it. The signature policy is the caller's:
+    /// downloads come through [`Self::install`] and its key check, local
+    /// imports through [`Self::import_from_path`] without one.
+    async fn extract_and_install(&self, archive: &[u8], pack_name: &str) -> Result<PathBuf> {
+        let staging = tempfile::tempdir_in(paths::temp_dir())
+            .context("failed to create staging directory")?;
+        self.cancellation.check()?;
+        self.report(ImportProgress::Extracting);
+        match detect_format(archive)? {
+            ArchiveFormat::Zip => extract_zip(staging.path(), archive).await?,
+            ArchiveFormat::TarGz => extract_tar_gz(staging.path(), archive).await?,
+        }
+        verify_checksum_manifest(staging.path()).await?;
+        check_schema(staging.path())?;
+        check_translation_encoding(staging.path()).await?;
+
+        self.cancellation.check()?;
+        self.report(ImportProgress::Installing);
+        self.install_staged(staging.path(), pack_name).await
+    }
+
+    /// Copies a fully verified pack into the install directory.
+    ///
+    /// The install is transactional: any previously installed copy is moved
+    /// aside first and restored if the copy fails partway, so an interrupted
+    /// import can never leave the user with a half-written pack.
+    async fn install_staged(&self, staged: &Path, pack_name: &str) -> Result<PathBuf> {
+        let destination = self.install_dir.join(pack_name);
+        if let Some(parent) = destination.parent() {
+            smol::fs::create_dir_all(parent).await?;
+        }
+        let backup = self.install_dir.join(format!("{pack_name}.backup"));
+        if smol::fs::metadata(&backup).await.is_ok() {
+            smol::fs::remove_dir_all(&backup)
+                .await
+                .context("failed to remove stale pack backup")?;
+        }
+        let had_previous = smol::fs::metadata(&destination).await.is_ok();
+        if had_previous {
+            smol::fs::rename(&destination, &backup)
+                .await
+                .context("failed to move previously installed pack aside")?;
+        }
+
+        match copy_dir(staged, &destination).await {
+            Ok(()) => {
+                if had_previous {
+                    smol::fs::remove_dir_all(&backup).await.ok();
+                }
+                Ok(destination)
+            }
+            Err(error) => {
+                smol::fs::remove_dir_all(&destination).await.ok();
+                if had_previous {
+                    if let Err(restore_error) = smol::fs::rename(&backup, &destination).await {
+                        log::error!(
+                            "failed to restore previous {pack_name} language pack: {restore_error:#}"
+                        );
+                    }
+                }
+                Err(error.context("language pack install failed and was rolled back"))
+            }
+        }
+    }
+
+    /// Resolves a GitHub repository reference to the download URL of its
+    /// release's pack archive asset.
+    async fn resolve_github_release(&self, repo: &str, tag: Option<&str>) -> Result<String> {
+        let release = match tag {
+            Some(tag) => {
+                http_client::github::get_release_by_tag_name(repo, tag, self.http_client.clone())
+                    .await?
+            }
+            None => {
+                http_client::github::latest_github_release(
+                    repo,
+                    true,
+                    false,
+                    self.http_client.clone(),
+                )
+                .await?
+            }
+        };
+        let asset = select_pack_asset(&release.assets).with_context(|| {
+            format!(
+                "release {} of {repo} has no .zip or .tar.gz language pack asset",
+                release.tag_name
+            )
+        })?;
+        Ok(asset.browser_download_url.clone())
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>> {
+        let cached_path = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(hex::encode(Sha256::digest(url.as_bytes()))));
+        match self.fetch(url).await {
+            Ok(body) => {
+                if let Some(cached_path) = &cached_path {
+                    if let Err(error) = write_cache_entry(cached_path, &body).await {
+                        log::warn!("failed to cache language pack download: {error:#}");
+                    }
+                }
+                Ok(body)
+            }
+            Err(error) => {
+                if let Some(cached_path) = &cached_path {
+                    if let Ok(body) = smol::fs::read(cached_path).await {
+                        log::info!(
+                            "downloading {url} failed ({error:#}); using the cached copy"
+                        );
+                        return Ok(body);
+                    }
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+        let mut response = self
+            .http_client
+            .get(url, Default::default(), true)
+            .await
+            .with_context(|| format!("failed to download {url}"))?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "download of {url} failed with status {}",
+            response.status()
+        );
+        let total_bytes = response
+            .headers()
+            .get("content-length")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        let mut body = Vec::new();
+        let mut chunk = [0u8; 16 * 1024];
+        loop {
+            self.cancellation.check()?;
+            let read = response.body_mut().read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..read]);
+            self.report(ImportProgress::Downloading {
+                received_bytes: body.len(),
+                total_bytes,
+            });
+        }
+        Ok(body)
+    }
+
+    /// Checks the detached signature over the archive bytes against the
+    /// trusted keys. A missing or bad signature is an error whenever trusted
+    /// keys are configured.
+    fn verify_signature(&self, archive: &[u8], signature: Option<&[u8]>) -> Result<()> {
+        if self.trusted_keys.is_empty() {
+            return Ok(());
+        }
+        let signature = signature.ok_or_else(|| {
+            anyhow!(
+                "this language pack is unsigned, but trusted_language_pack_keys is set; \
+                 refusing to install it"
+            )
+        })?;
+        let signature = Signature::try_from(signature).context("malformed pack signature")?;
+        for pem in &self.trusted_keys {
+            let Ok(public_key) = RsaPublicKey::from_public_key_pem(pem) else {
+                log::warn!("ignoring malformed key in trusted_language_pack_keys");
+                continue;
+            };
+            let verifying_key = VerifyingKey::<RsaSha256>::new(public_key);
+            if verifying_key.verify(archive, &signature).is_ok() {
+                return Ok(());
+            }
+        }
+        bail!("language pack signature does not match any trusted key; refusing to install it")
+    }
+}
+
+/// Checks every file in the extracted pack against the embedded
+/// `checksums.sha256` manifest. The manifest uses the `sha256sum` format:
+/// one `<hex digest>  <relative path>` entry per line.
+async fn verify_checksum_manifest(pack_dir: &Path) -> Result<()> {
+    let manifest_path = pack_dir.join(CHECKSUM_MANIFEST_NAME);
+    let manifest = smol::fs::read_to_string(&manifest_path)
+        .await
+        .context("language pack has no checksums.sha256 manifest; refusing to install it")?;
+
+    let mut verified_files = 0;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (expected, relative_path) = line
+            .split_once("  ")
+            .with_context(|| format!("malformed checksum manifest line: {line:?}"))?;
+        let contents = smol::fs::read(pack_dir.join(relative_path))
+            .await
+            .with_context(|| format!("pack file {relative_path} is listed in the checksum manifest but missing"))?;
+        let actual = hex::encode(Sha256::digest(&contents));
+        anyhow::ensure!(
+            actual == expected.to_ascii_lowercase(),
+            "checksum mismatch for {relative_path}: the pack is corrupt or has been tampered with"
+        );
+        verified_files += 1;
+    }
+    anyhow::ensure!(
+        verified_files > 0,
+        "the checksum manifest doesn't cover any files"
+    );
+
+    // Every file must be covered, so that nothing can ride along unverified.
+    let listed: collections::HashSet<PathBuf> = manifest
+        .lines()
+        .filter_map(|line| line.trim().split_once("  "))
+        .map(|(_, path)| PathBuf::from(path))
+        .collect();
+    for entry in walkdir::WalkDir::new(pack_dir) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(pack_dir)?;
+        if relative != Path::new(CHECKSUM_MANIFEST_NAME) && !listed.contains(relative) {
+            bail!(
+                "pack file {} is not covered by the checksum manifest",
+                relative.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Maps a zip entry name to a path under `destination`, rejecting entries
+/// that would escape it.
+///
+/// Absolute paths and Windows drive or UNC prefixes are stripped rather than
+/// honored, `.` components are dropped, and any `..` component is an error:
+/// archives are attacker-supplied, and a crafted entry name must never be
+/// able to write outside the extraction directory.
+fn sanitize_entry_path(destination: &Path, entry_name: &str) -> Result<PathBuf> {
+    use std::path::Component;
+
+    let entry_name = entry_name.replace('\\', "/");
+    let mut path = destination.to_path_buf();
+    let mut components = 0;
+    for component in Path::new(&entry_name).components() {
+        match component {
+            Component::Normal(part) => {
+                path.push(part);
+                components += 1;
+            }
+            Component::ParentDir => {
+                bail!("zip entry {entry_name:?} contains a `..` path component")
+            }
+            Component::RootDir | Component::Prefix(_) | Component::CurDir => {}
+        }
+    }
+    anyhow::ensure!(components > 0, "zip entry {entry_name:?} has an empty path");
+    Ok(path)
+}
+
+/// Writes a cache entry atomically so that an interrupted write can't leave
+/// a truncated archive behind to be picked up offline later.
+async fn write_cache_entry(path: &Path, body: &[u8]) -> Result<()> {
+    let Some(parent) = path.parent() else {
+        bail!("cache path has no parent directory");
+    };
+    smol::fs::create_dir_all(parent).await?;
+    let temp = path.with_extension("partial");
+    smol::fs::write(&temp, body).await?;
+    smol::fs::rename(&temp, path).await?;
+    Ok(())
+}
+
+/// Parses a GitHub repository reference, returning `(owner/repo, tag)`.
+///
+/// Direct file URLs — including release asset downloads under
+/// `github.com/<owner>/<repo>/releases/download/…` — are not repository
+/// references and return `None`.
+fn parse_github_repo(url: &str) -> Option<(String, Option<String>)> {
+    if let Some(rest) = url.strip_prefix("github:") {
+        let (repo, tag) = match rest.split_once('@') {
+            Some((repo, tag)) => (repo, Some(tag.to_string())),
+            None => (rest, None),
+        };
+        if repo.split('/').filter(|part| !part.is_empty()).count() == 2 {
+            return Some((repo.to_string(), tag));
+        }
+        return None;
+    }
+
+    let rest = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("http://github.com/"))?;
+    let rest = rest.trim_end_matches('/');
+    let segments: Vec<&str> = rest.split('/').collect();
+    match segments.as_slice() {
+        [owner, repo] => Some((format!("{owner}/{repo}"), None)),
+        [owner, repo, "releases", "tag", tag] => {
+            Some((format!("{owner}/{repo}"), Some((*tag).to_string())))
+        }
+        _ => None,
+    }
+}
+
+/// Picks the release asset to install, preferring zips over tarballs.
+fn select_pack_asset(
+    assets: &[http_client::github::GithubReleaseAsset],
+) -> Option<&http_client::github::GithubReleaseAsset> {
+    assets
+        .iter()
+        .find(|asset| asset.name.ends_with(".zip"))
+        .or_else(|| {
+            assets
+                .iter()
+                .find(|asset| asset.name.ends_with(".tar.gz") || asset.name.ends_with(".tgz"))
+        })
+}
+
+/// A newer published version of an installed language pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackUpdate {
+    pub extension_id: String,
+    pub installed_version: String,
+    pub latest_version: String,
+}
+
+fn check_extension_id(extension_id: &str) -> Result<()> {
+    anyhow::ensure!(
+        !extension_id.is_empty()
+            && extension_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "{extension_id:?} is not a valid extension id"
+    );
+    Ok(())
+}
+
+/// The extension store's download endpoint for an extension's latest
+/// version.
+fn marketplace_download_url(
+    client: &http_client::HttpClientWithUrl,
+    extension_id: &str,
+) -> Result<http_client::Url> {
+    check_extension_id(extension_id)?;
+    client.build_zed_api_url(&format!("/extensions/{extension_id}/download"), &[])
+}
+
+/// The extension store's endpoint listing every published version of an
+/// extension; the same endpoint the in-app extension browser uses.
+fn marketplace_versions_url(
+    client: &http_client::HttpClientWithUrl,
+    extension_id: &str,
+) -> Result<http_client::Url> {
+    check_extension_id(extension_id)?;
+    client.build_zed_api_url(&format!("/extensions/{extension_id}"), &[])
+}
+
+/// Parses the store's version-listing response and returns the newest
+/// version it contains.
+fn latest_version_in_response(body: &[u8]) -> Result<Option<String>> {
+    #[derive(serde::Deserialize)]
+    struct Response {
+        data: Vec<Entry>,
+    }
+    // The store flattens each version's manifest into the entry; the
+    // version field is all that's needed here.
+    #[derive(serde::Deserialize)]
+    struct Entry {
+        version: String,
+    }
+    let response: Response =
+        serde_json::from_slice(body).context("failed to parse extension store response")?;
+    Ok(response
+        .data
+        .into_iter()
+        .map(|entry| entry.version)
+        .reduce(|best, candidate| {
+            if version_is_newer(&candidate, &best) {
+                candidate
+            } else {
+                best
+            }
+        }))
+}
+
+/// Compares dotted version strings numerically, component by component, so
+/// `0.10.0` counts as newer than `0.9.1`. A candidate that doesn't start
+/// with a number is never "newer".
+fn version_is_newer(candidate: &str, installed: &str) -> bool {
+    fn components(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split('.')
+            .map_while(|part| part.parse().ok())
+            .collect()
+    }
+    let candidate = components(candidate);
+    if candidate.is_empty() {
+        return false;
+    }
+    candidate > components(installed)
+}
+
+/// Rejects packs whose declared translation schema version this build can't
+/// read, before anything reaches the install directory.
+fn check_schema(pack_dir: &Path) -> Result<()> {
+    crate::pack::PackMetadata::load(pack_dir)?.check_schema_compatibility()
+}
+
+/// Rejects packs whose translation file isn't plain UTF-8 text, before
+/// anything reaches the install directory. Packs without a translation file
+/// pass; that's reported by validation, not import.
+async fn check_translation_encoding(pack_dir: &Path) -> Result<()> {
+    let path = pack_dir.join("translation.json");
+    let bytes = match smol::fs::read(&path).await {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to read {}", path.display()));
+        }
+    };
+    crate::validator::check_encoding(&bytes)
+        .with_context(|| format!("{} is not plain UTF-8", path.display()))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+fn detect_format(archive: &[u8]) -> Result<ArchiveFormat> {
+    match archive {
+        [b'P', b'K', 0x03 | 0x05 | 0x07, ..] => Ok(ArchiveFormat::Zip),
+        [0x1f, 0x8b, ..] => Ok(ArchiveFormat::TarGz),
+        _ => bail!("language pack archive is neither a zip nor a gzipped tar"),
+    }
+}
+
+async fn extract_tar_gz(destination: &Path, archive: &[u8]) -> Result<()> {
+    use async_compression::futures::bufread::GzipDecoder;
+    use futures::StreamExt as _;
+
+    let decompressed = GzipDecoder::new(futures::io::BufReader::new(archive));
+    let tar = async_tar::Archive::new(decompressed);
+    let mut entries = tar.entries()?;
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        // `unpack_in` refuses entries that would escape the destination, the
+        // same guarantee `sanitize_entry_path` gives the zip path.
+        entry.unpack_in(destination).await?;
+    }
+    Ok(())
+}
+
+async fn extract_zip(destination: &Path, archive: &[u8]) -> Result<()> {
+    use async_zip::base::read::stream::ZipFileReader;
+
+    let mut reader = ZipFileReader::new(futures::io::BufReader::new(archive));
+    while let Some(mut item) = reader.next_with_entry().await? {
+        let entry_reader = item.reader_mut();
+        let entry = entry_reader.entry();
+        let filename = entry.filename().as_str().context("non-UTF-8 zip entry name")?;
+        let path = sanitize_entry_path(destination, filename)?;
+        if entry.dir()? {
+            smol::fs::create_dir_all(&path).await?;
+        } else {
+            if let Some(parent) = path.parent() {
+                smol::fs::create_dir_all(parent).await?;
+            }
+            let mut file = smol::fs::File::create(&path).await?;
+            futures::io::copy(entry_reader, &mut file).await?;
+        }
+        reader = item.skip().await?;
+    }
+    Ok(())
+}
+
+async fn copy_dir(source: &Path, destination: &Path) -> Result<()> {
+    smol::fs::create_dir_all(destination).await?;
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let target = destination.join(relative);
+        if entry.file_type().is_dir() {
+            smol::fs::create_dir_all(&target).await?;
+        } else {
+            smol::fs::copy(entry.path(), &target).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn importer_with_keys(trusted_keys: Vec<String>) -> I18nImporter {
+        I18nImporter::new(
+            Arc::new(http_client::BlockedHttpClient),
+            PathBuf::from("/tmp/does-not-matter"),
+            trusted_keys,
+        )
+    }
+
+    #[test]
+    fn unsigned_packs_are_rejected_when_trusted_keys_are_configured() {
+        let importer = importer_with_keys(vec!["not a real key".to_string()]);
+        let error = importer
+            .verify_signature(b"archive bytes", None)
+            .unwrap_err();
+        assert!(error.to_string().contains("unsigned"));
+    }
+
+    #[test]
+    fn local_archive_imports_skip_the_signature_check() {
+        let importer = importer_with_keys(vec!["not a real key".to_string()]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pack.tar.gz");
+        std::fs::write(&path, b"archive bytes").unwrap();
+        // The import fails on the bogus bytes, not on the missing signature
+        // — local imports are exempt like the directory form.
+        let error = smol::block_on(importer.import_from_path(&path, "zh-CN")).unwrap_err();
+        assert!(error.to_string().contains("neither a zip nor a gzipped tar"));
+    }
+
+    #[test]
+    fn signature_verification_is_skipped_without_trusted_keys() {
+        let importer = importer_with_keys(Vec::new());
+        importer.verify_signature(b"archive bytes", None).unwrap();
+    }
+
+    #[test]
+    fn checksum_manifest_is_required_and_verified() {
+        smol::block_on(async {
+            let dir = tempfile::tempdir().unwrap();
+            smol::fs::write(dir.path().join("zh-CN.json"), b"{}")
+                .await
+                .unwrap();
+
+            let error = verify_checksum_manifest(dir.path()).await.unwrap_err();
+            assert!(error.to_string().contains("no checksums.sha256"));
+
+            let digest = hex::encode(Sha256::digest(b"{}"));
+            smol::fs::write(
+                dir.path().join(CHECKSUM_MANIFEST_NAME),
+                format!("{digest}  zh-CN.json\n"),
+            )
+            .await
+            .unwrap();
+            verify_checksum_manifest(dir.path()).await.unwrap();
+
+            smol::fs::write(dir.path().join("zh-CN.json"), b"{tampered}")
+                .await
+                .unwrap();
+            let error = verify_checksum_manifest(dir.path()).await.unwrap_err();
+            assert!(error.to_string().contains("checksum mismatch"));
+        });
+    }
+
+    #[test]
+    fn non_utf8_translation_files_are_rejected() {
+        smol::block_on(async {
+            let dir = tempfile::tempdir().unwrap();
+            check_translation_encoding(dir.path()).await.unwrap();
+
+            smol::fs::write(dir.path().join("translation.json"), [b'{', 0xFF, 0xFE])
+                .await
+                .unwrap();
+            let error = check_translation_encoding(dir.path()).await.unwrap_err();
+            assert!(format!("{error:#}").contains("byte offset 1"));
+
+            smol::fs::write(dir.path().join("translation.json"), b"\xEF\xBB\xBF{}")
+                .await
+                .unwrap();
+            let error = check_translation_encoding(dir.path()).await.unwrap_err();
+            assert!(format!("{error:#}").contains("byte order mark"));
+        });
+    }
+
+    #[test]
+    fn downloads_fall_back_to_the_cache_when_offline() {
+        smol::block_on(async {
+            let cache = tempfile::tempdir().unwrap();
+            let importer =
+                importer_with_keys(Vec::new()).with_cache_dir(cache.path().to_path_buf());
+            let url = "https://example.com/pack.zip";
+            assert!(importer.download(url).await.is_err());
+
+            let key = hex::encode(Sha256::digest(url.as_bytes()));
+            smol::fs::write(cache.path().join(key), b"cached archive")
+                .await
+                .unwrap();
+            assert_eq!(importer.download(url).await.unwrap(), b"cached archive");
+        });
+    }
+
+    #[test]
+    fn marketplace_ids_resolve_to_the_store_download_endpoint() {
+        let client = http_client::HttpClientWithUrl::new(
+            Arc::new(http_client::BlockedHttpClient),
+            "https://zed.dev",
+            None,
+        );
+        assert_eq!(
+            marketplace_download_url(&client, "i18n-ja").unwrap().as_str(),
+            "https://api.zed.dev/extensions/i18n-ja/download"
+        );
+        assert!(marketplace_download_url(&client, "").is_err());
+        assert!(marketplace_download_url(&client, "../escape").is_err());
+    }
+
+    #[test]
+    fn newer_store_versions_are_detected() {
+        assert!(version_is_newer("1.2.0", "1.1.9"));
+        assert!(version_is_newer("0.10.0", "0.9.1"));
+        assert!(version_is_newer("v1.1.0", "1.0.9"));
+        assert!(!version_is_newer("1.2.0", "1.2.0"));
+        assert!(!version_is_newer("1.1.9", "1.2.0"));
+        assert!(!version_is_newer("garbage", "1.0.0"));
+
+        let body = br#"{"data": [
+            {"id": "i18n-ja", "name": "Japanese", "version": "0.9.1", "download_count": 3},
+            {"id": "i18n-ja", "name": "Japanese", "version": "0.10.0", "download_count": 1}
+        ]}"#;
+        assert_eq!(
+            latest_version_in_response(body).unwrap(),
+            Some("0.10.0".to_string())
+        );
+        assert_eq!(latest_version_in_response(br#"{"data": []}"#).unwrap(), None);
+        assert!(latest_version_in_response(b"not json").is_err());
+    }
+
+    #[test]
+    fn github_repo_references_are_recognized() {
+        assert_eq!(
+            parse_github_repo("github:owner/repo"),
+            Some(("owner/repo".to_string(), None))
+        );
+        assert_eq!(
+            parse_github_repo("github:owner/repo@v1.2"),
+            Some(("owner/repo".to_string(), Some("v1.2".to_string())))
+        );
+        assert_eq!(
+            parse_github_repo("https://github.com/owner/repo"),
+            Some(("owner/repo".to_string(), None))
+        );
+        assert_eq!(
+            parse_github_repo("https://github.com/owner/repo/releases/tag/v1.2"),
+            Some(("owner/repo".to_string(), Some("v1.2".to_string())))
+        );
+        assert_eq!(
+            parse_github_repo("https://github.com/owner/repo/releases/download/v1.2/pack.zip"),
+            None
+        );
+        assert_eq!(parse_github_repo("https://example.com/pack.zip"), None);
+        assert_eq!(parse_github_repo("github:not-a-repo"), None);
+    }
+
+    #[test]
+    fn install_replaces_a_previous_pack() {
+        smol::block_on(async {
+            let install_dir = tempfile::tempdir().unwrap();
+            let importer = I18nImporter::new(
+                Arc::new(http_client::BlockedHttpClient),
+                install_dir.path().to_path_buf(),
+                Vec::new(),
+            );
+            let previous = install_dir.path().join("zh-CN");
+            smol::fs::create_dir_all(&previous).await.unwrap();
+            smol::fs::write(previous.join("zh-CN.json"), b"old").await.unwrap();
+
+            let staged = tempfile::tempdir().unwrap();
+            smol::fs::write(staged.path().join("zh-CN.json"), b"new")
+                .await
+                .unwrap();
+
+            importer.install_staged(staged.path(), "zh-CN").await.unwrap();
+            let contents = smol::fs::read(previous.join("zh-CN.json")).await.unwrap();
+            assert_eq!(contents, b"new");
+            assert!(
+                smol::fs::metadata(install_dir.path().join("zh-CN.backup"))
+                    .await
+                    .is_err()
+            );
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_install_restores_the_previous_pack() {
+        smol::block_on(async {
+            let install_dir = tempfile::tempdir().unwrap();
+            let importer = I18nImporter::new(
+                Arc::new(http_client::BlockedHttpClient),
+                install_dir.path().to_path_buf(),
+                Vec::new(),
+            );
+            let previous = install_dir.path().join("zh-CN");
+            smol::fs::create_dir_all(&previous).await.unwrap();
+            smol::fs::write(previous.join("zh-CN.json"), b"old").await.unwrap();
+
+            // A dangling symlink makes the copy fail partway through.
+            let staged = tempfile::tempdir().unwrap();
+            smol::fs::write(staged.path().join("zh-CN.json"), b"new")
+                .await
+                .unwrap();
+            std::os::unix::fs::symlink("does-not-exist", staged.path().join("broken"))
+                .unwrap();
+
+            let error = importer
+                .install_staged(staged.path(), "zh-CN")
+                .await
+                .unwrap_err();
+            assert!(error.to_string().contains("rolled back"));
+            let contents = smol::fs::read(previous.join("zh-CN.json")).await.unwrap();
+            assert_eq!(contents, b"old");
+        });
+    }
+
+    #[test]
+    fn cancellation_aborts_before_anything_is_written() {
+        let importer = importer_with_keys(Vec::new());
+        importer.cancellation_handle().cancel();
+        let error = smol::block_on(importer.install(b"archive bytes", None, "zh-CN"))
+            .unwrap_err();
+        assert!(error.to_string().contains("cancelled"));
+    }
+
+    #[test]
+    fn zip_entry_paths_are_sanitized() {
+        let destination = Path::new("/tmp/pack");
+        assert_eq!(
+            sanitize_entry_path(destination, "zh-CN.json").unwrap(),
+            destination.join("zh-CN.json")
+        );
+        assert_eq!(
+            sanitize_entry_path(destination, "./nested/zh-CN.json").unwrap(),
+            destination.join("nested/zh-CN.json")
+        );
+        // Absolute paths and backslash separators are treated as relative to
+        // the destination rather than honored.
+        assert_eq!(
+            sanitize_entry_path(destination, "/etc/passwd").unwrap(),
+            destination.join("etc/passwd")
+        );
+        assert_eq!(
+            sanitize_entry_path(destination, "nested\\zh-CN.json").unwrap(),
+            destination.join("nested/zh-CN.json")
+        );
+        assert!(sanitize_entry_path(destination, "../outside.json").is_err());
+        assert!(sanitize_entry_path(destination, "nested/../../outside.json").is_err());
+        assert!(sanitize_entry_path(destination, "/").is_err());
+        assert!(sanitize_entry_path(destination, "").is_err());
+    }
+
+    #[test]
+    fn files_outside_the_manifest_are_rejected() {
+        smol::block_on(async {
+            let dir = tempfile::tempdir().unwrap();
+            let digest = hex::encode(Sha256::digest(b"{}"));
+            smol::fs::write(dir.path().join("zh-CN.json"), b"{}")
+                .await
+                .unwrap();
+            smol::fs::write(dir.path().join("extra.bin"), b"surprise")
+                .await
+                .unwrap();
+            smol::fs::write(
+                dir.path().join(CHECKSUM_MANIFEST_NAME),
+                format!("{digest}  zh-CN.json\n"),
+            )
+            .await
+            .unwrap();
+            let error = verify_checksum_manifest(dir.path()).await.unwrap_err();
+            assert!(error.to_string().contains("not covered"));
+        });
+    }
+}
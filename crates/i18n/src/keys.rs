@@ -0,0 +1,464 @@
+//! The translation key naming scheme and helpers for producing conforming
+//! keys.
+//!
+//! Keys have the form `i18n.<area>.<path>` where every dot-separated segment
+//! is lowercase snake case (`[a-z0-9_]`). Scanners that derive keys from UI
+//! strings must pass the derived segment through [`normalize_segment`].
+
+/// Why a key violates the naming scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyConventionViolation {
+    /// The key doesn't start with the `i18n.` prefix.
+    MissingPrefix,
+    /// The key has fewer than three segments (`i18n.<area>.<path>`).
+    TooFewSegments,
+    /// The key contains an empty segment, i.e. double dots or a leading or
+    /// trailing dot.
+    EmptySegment,
+    /// A segment contains characters outside `[a-z0-9_]`.
+    InvalidSegment,
+}
+
+/// The platform names a key may carry as an `@platform` suffix to override
+/// its text on one platform only, e.g. `i18n.menu.file.open@macos`.
+pub const PLATFORM_SUFFIXES: &[&str] = &["linux", "macos", "windows"];
+
+/// The suffix the manager probes for on this build, matching the values in
+/// [`PLATFORM_SUFFIXES`].
+pub const CURRENT_PLATFORM: &str = if cfg!(target_os = "macos") {
+    "macos"
+} else if cfg!(target_os = "windows") {
+    "windows"
+} else {
+    "linux"
+};
+
+/// Splits a trailing `@platform` suffix off `key`. Suffixes outside
+/// [`PLATFORM_SUFFIXES`] are left attached so they fall through to the usual
+/// naming checks.
+pub fn split_platform(key: &str) -> (&str, Option<&str>) {
+    if let Some((base, platform)) = key.rsplit_once('@') {
+        if PLATFORM_SUFFIXES.contains(&platform) {
+            return (base, Some(platform));
+        }
+    }
+    (key, None)
+}
+
+/// The namespace for screen-reader labels and descriptions,
+/// `i18n.a11y.<path>`. Accessibility text lives apart from the visible
+/// strings it describes, so a pack can word spoken text differently from
+/// what is drawn on screen.
+pub const ACCESSIBILITY_PREFIX: &str = "i18n.a11y.";
+
+/// Whether `key` names accessibility text, after stripping any platform
+/// suffix.
+pub fn is_accessibility_key(key: &str) -> bool {
+    let (base, _) = split_platform(key);
+    base.starts_with(ACCESSIBILITY_PREFIX)
+}
+
+/// The namespace for translated `settings.json` schema descriptions: the
+/// hover documentation for the top-level setting `theme` comes from
+/// `i18n.settings_schema.theme`. Unlike the rest of the key space this
+/// namespace is open-ended — the English text lives in the settings'
+/// own doc comments rather than the reference table, and packs translate
+/// whichever settings they cover.
+pub const SETTINGS_SCHEMA_PREFIX: &str = "i18n.settings_schema.";
+
+/// Whether `key` names a settings schema description.
+pub fn is_settings_schema_key(key: &str) -> bool {
+    let (base, _) = split_platform(key);
+    base.starts_with(SETTINGS_SCHEMA_PREFIX)
+}
+
+/// Checks `key` against the naming scheme, returning the first violation.
+/// A valid `@platform` suffix is stripped first, so platform variants of
+/// conforming keys conform too.
+pub fn check_key(key: &str) -> Result<(), KeyConventionViolation> {
+    let (key, _) = split_platform(key);
+    let mut segments = key.split('.');
+    if segments.next() != Some("i18n") {
+        return Err(KeyConventionViolation::MissingPrefix);
+    }
+    let mut count = 1;
+    for segment in segments {
+        count += 1;
+        if segment.is_empty() {
+            return Err(KeyConventionViolation::EmptySegment);
+        }
+        if !segment
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(KeyConventionViolation::InvalidSegment);
+        }
+    }
+    if count < 3 {
+        return Err(KeyConventionViolation::TooFewSegments);
+    }
+    Ok(())
+}
+
+/// Converts a UI string into a conforming key segment: lowercased, with runs
+/// of non-alphanumeric characters collapsed into single underscores.
+///
+/// `"Go to Line/Column…"` becomes `go_to_line_column`.
+pub fn normalize_segment(text: &str) -> String {
+    let mut segment = String::with_capacity(text.len());
+    let mut pending_separator = false;
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_separator && !segment.is_empty() {
+                segment.push('_');
+            }
+            pending_separator = false;
+            segment.push(c.to_ascii_lowercase());
+        } else {
+            pending_separator = true;
+        }
+    }
+    segment
+}
+
+/// The broad area of the UI a translation key belongs to, derived from the
+/// key's `<area>` segment. Used by tooling to break statistics down into
+/// chunks a translator can work through one at a time.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TranslationCategory {
+    Accessibility,
+    Dialog,
+    Menu,
+    Editor,
+    /// A dock panel, identified by the key's third segment (e.g. `project`
+    /// for `i18n.panel.project.title`).
+    Panel(String),
+    Extension,
+    Status,
+    Other(String),
+}
+
+impl TranslationCategory {
+    pub fn for_key(key: &str) -> Self {
+        let mut segments = key.split('.');
+        let area = match (segments.next(), segments.next()) {
+            (Some("i18n"), Some(area)) => area,
+            _ => return Self::Other(String::new()),
+        };
+        match area {
+            "a11y" => Self::Accessibility,
+            "dialog" => Self::Dialog,
+            "menu" => Self::Menu,
+            "editor" => Self::Editor,
+            "panel" => Self::Panel(segments.next().unwrap_or_default().to_string()),
+            "ext" => Self::Extension,
+            "status" => Self::Status,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for TranslationCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Accessibility => write!(f, "accessibility"),
+            Self::Dialog => write!(f, "dialog"),
+            Self::Menu => write!(f, "menu"),
+            Self::Editor => write!(f, "editor"),
+            Self::Panel(panel) => write!(f, "{panel} panel"),
+            Self::Extension => write!(f, "extension"),
+            Self::Status => write!(f, "status"),
+            Self::Other(area) => write!(f, "{area}"),
+        }
+    }
+}
+
+/// Rewrites a key registered by an extension into that extension's own
+/// namespace, `i18n.ext.<extension_id>.<path>`.
+///
+/// Keys that exist in the reference set are left alone — that's how language
+/// packs translate core UI strings — so an extension can never collide with
+/// another extension's strings, but can still contribute translations for
+/// the core set.
+pub fn extension_namespaced_key(extension_id: &str, key: &str) -> String {
+    if crate::defaults::default_text(key).is_some() {
+        return key.to_string();
+    }
+    let namespace = format!("i18n.ext.{}.", normalize_segment(extension_id));
+    if key.starts_with(&namespace) {
+        return key.to_string();
+    }
+    let path = key.strip_prefix("i18n.").unwrap_or(key);
+    format!("{namespace}{path}")
+}
+
+/// A translation key resolved against the reference table at compile time.
+///
+/// Produced by [`i18n_key!`](crate::i18n_key), which forces the resolution
+/// into a const context: the key's position in
+/// [`crate::defaults::DEFAULT_TEXTS`] is found once at build time, so the
+/// English default resolves by direct indexing with no hashing or
+/// allocation on the lookup path.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct I18nKey {
+    text: &'static str,
+    /// Index into `DEFAULT_TEXTS`, or `usize::MAX` for keys outside the
+    /// reference set (extension keys, for example).
+    index: usize,
+}
+
+impl I18nKey {
+    /// Prefer [`i18n_key!`](crate::i18n_key), which guarantees the table
+    /// scan happens at compile time.
+    pub const fn new(text: &'static str) -> Self {
+        Self {
+            text,
+            index: reference_index(text),
+        }
+    }
+
+    pub const fn text(self) -> &'static str {
+        self.text
+    }
+
+    /// The English reference string, when this is a reference key.
+    pub fn default_text(self) -> Option<&'static str> {
+        Some(crate::defaults::DEFAULT_TEXTS.get(self.index)?.1)
+    }
+}
+
+const fn reference_index(text: &str) -> usize {
+    let mut index = 0;
+    while index < crate::defaults::DEFAULT_TEXTS.len() {
+        if const_str_eq(crate::defaults::DEFAULT_TEXTS[index].0, text) {
+            return index;
+        }
+        index += 1;
+    }
+    usize::MAX
+}
+
+// `==` on `str` isn't const-stable, so compare bytes by hand.
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Builds an [`I18nKey`] from a string literal, resolving it against the
+/// reference table at compile time.
+#[macro_export]
+macro_rules! i18n_key {
+    ($key:expr) => {{
+        const KEY: $crate::keys::I18nKey = $crate::keys::I18nKey::new($key);
+        KEY
+    }};
+}
+
+/// Translates `key`, with the English default written at the call site
+/// instead of `defaults.rs`, for strings that haven't been folded into the
+/// reference table yet. `zed-i18n extract-defaults` collects these pairs,
+/// flags conflicting defaults for the same key, and reports what's missing
+/// from the table; once a key lands there, the reference text wins over the
+/// call site.
+#[macro_export]
+macro_rules! t_default {
+    ($key:expr, $default:expr) => {
+        $crate::I18nManager::global().get_text_or($crate::i18n_key!($key), $default)
+    };
+}
+
+/// Resolves the screen-reader label registered under `i18n.a11y.<path>`,
+/// taking just the path so call sites read as labels rather than keys.
+/// `zed-i18n scan` expands these call sites to their full keys, since the
+/// `concat!` below means the key never appears in the source as a literal.
+#[macro_export]
+macro_rules! a11y_label {
+    ($path:literal) => {
+        $crate::I18nManager::global()
+            .get_text_keyed($crate::i18n_key!(concat!("i18n.a11y.", $path)))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interned_keys_resolve_their_defaults_by_index() {
+        let key = crate::i18n_key!("i18n.menu.file.save");
+        assert_eq!(key.text(), "i18n.menu.file.save");
+        assert_eq!(key.default_text(), Some("Save"));
+
+        let unknown = crate::i18n_key!("i18n.ext.some_ext.hello");
+        assert_eq!(unknown.default_text(), None);
+
+        for (index, (text, default)) in crate::defaults::DEFAULT_TEXTS.iter().copied().enumerate() {
+            let key = I18nKey::new(text);
+            assert_eq!(key.index, index);
+            assert_eq!(key.default_text(), Some(default));
+        }
+    }
+
+    #[test]
+    fn all_reference_keys_conform() {
+        for (key, _) in crate::defaults::DEFAULT_TEXTS {
+            assert_eq!(check_key(key), Ok(()), "bad reference key: {key}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert_eq!(
+            check_key("menu.file.save"),
+            Err(KeyConventionViolation::MissingPrefix)
+        );
+        assert_eq!(check_key("i18n.menu"), Err(KeyConventionViolation::TooFewSegments));
+        assert_eq!(
+            check_key("i18n.menu..save"),
+            Err(KeyConventionViolation::EmptySegment)
+        );
+        assert_eq!(
+            check_key("i18n.menu.file."),
+            Err(KeyConventionViolation::EmptySegment)
+        );
+        assert_eq!(
+            check_key("i18n.menu.go.go_to_line/column"),
+            Err(KeyConventionViolation::InvalidSegment)
+        );
+        assert_eq!(
+            check_key("i18n.menu.File.save"),
+            Err(KeyConventionViolation::InvalidSegment)
+        );
+    }
+
+    #[test]
+    fn platform_suffixes_split_off_known_platforms_only() {
+        assert_eq!(
+            split_platform("i18n.menu.file.open@macos"),
+            ("i18n.menu.file.open", Some("macos"))
+        );
+        assert_eq!(split_platform("i18n.menu.file.open"), ("i18n.menu.file.open", None));
+        assert_eq!(
+            split_platform("i18n.menu.file.open@amiga"),
+            ("i18n.menu.file.open@amiga", None)
+        );
+
+        assert_eq!(check_key("i18n.menu.file.open@linux"), Ok(()));
+        assert_eq!(
+            check_key("i18n.menu.file.open@amiga"),
+            Err(KeyConventionViolation::InvalidSegment)
+        );
+        assert!(PLATFORM_SUFFIXES.contains(&CURRENT_PLATFORM));
+    }
+
+    #[test]
+    fn extension_keys_are_namespaced() {
+        // Core keys pass through so packs can translate them.
+        assert_eq!(
+            extension_namespaced_key("my-pack", "i18n.menu.file.save"),
+            "i18n.menu.file.save"
+        );
+        // Unknown keys land in the extension's namespace.
+        assert_eq!(
+            extension_namespaced_key("my-tool", "i18n.panel.title"),
+            "i18n.ext.my_tool.panel.title"
+        );
+        assert_eq!(
+            extension_namespaced_key("my-tool", "panel.title"),
+            "i18n.ext.my_tool.panel.title"
+        );
+        // Already-namespaced keys aren't namespaced twice.
+        assert_eq!(
+            extension_namespaced_key("my-tool", "i18n.ext.my_tool.panel.title"),
+            "i18n.ext.my_tool.panel.title"
+        );
+    }
+
+    #[test]
+    fn accessibility_keys_are_recognized_with_and_without_platform_suffixes() {
+        assert!(is_accessibility_key("i18n.a11y.close_button"));
+        assert!(is_accessibility_key("i18n.a11y.close_button@macos"));
+        assert!(!is_accessibility_key("i18n.menu.file.save"));
+        assert!(!is_accessibility_key("i18n.a11y"));
+    }
+
+    #[test]
+    fn categorizes_keys_by_area() {
+        assert_eq!(
+            TranslationCategory::for_key("i18n.menu.file.save"),
+            TranslationCategory::Menu
+        );
+        assert_eq!(
+            TranslationCategory::for_key("i18n.a11y.close_button"),
+            TranslationCategory::Accessibility
+        );
+        assert_eq!(
+            TranslationCategory::for_key("i18n.panel.project.title"),
+            TranslationCategory::Panel("project".to_string())
+        );
+        assert_eq!(
+            TranslationCategory::for_key("i18n.ext.my_tool.panel.title"),
+            TranslationCategory::Extension
+        );
+        assert_eq!(
+            TranslationCategory::for_key("i18n.welcome.title"),
+            TranslationCategory::Other("welcome".to_string())
+        );
+    }
+
+    #[test]
+    fn normalizes_ui_strings_into_segments() {
+        assert_eq!(normalize_segment("Go to Line/Column…"), "go_to_line_column");
+        assert_eq!(normalize_segment("Save As…"), "save_as");
+        assert_eq!(normalize_segment("  Don't Save "), "don_t_save");
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn normalized_segments_conform_and_are_stable(text in "\\PC{0,40}") {
+            let normalized = normalize_segment(&text);
+            proptest::prop_assert!(
+                normalized
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+                "non-conforming segment {normalized:?}"
+            );
+            // Separator runs collapse, and separators at the ends vanish.
+            proptest::prop_assert!(!normalized.contains("__"));
+            proptest::prop_assert!(!normalized.starts_with('_'));
+            proptest::prop_assert!(!normalized.ends_with('_'));
+            // A conforming segment is a fixed point, so re-deriving a key
+            // from its own segment can't change it.
+            proptest::prop_assert_eq!(normalize_segment(&normalized), normalized.clone());
+        }
+    }
+
+    /// Guards the generated key scheme: if a [`normalize_segment`] refactor
+    /// makes two sibling menu labels collide, keys derived from those
+    /// labels would silently merge.
+    #[test]
+    fn menu_labels_normalize_without_sibling_collisions() {
+        let mut seen: std::collections::HashMap<(&str, String), &str> =
+            std::collections::HashMap::new();
+        for (key, default) in crate::defaults::DEFAULT_TEXTS {
+            if !key.starts_with("i18n.menu.") {
+                continue;
+            }
+            let (parent, _) = key.rsplit_once('.').unwrap_or(("", key));
+            let segment = normalize_segment(default);
+            if let Some(other) = seen.insert((parent, segment.clone()), key) {
+                panic!("{other} and {key} both normalize to {parent}.{segment}");
+            }
+        }
+    }
+}
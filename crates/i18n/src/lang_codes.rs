@@ -0,0 +1,515 @@
+//! Language tag normalization and system locale detection.
+
+use anyhow::Context as _;
+
+/// Normalizes a raw locale identifier into an IETF-style language tag:
+/// `zh_CN.UTF-8` becomes `zh-CN`, `en_US@euro` becomes `en-US`. Returns
+/// `None` for the POSIX placeholder locales (`C`, `POSIX`) and empty input.
+pub fn normalize_locale(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    // Strip the encoding and modifier suffixes used by POSIX locales.
+    let raw = raw.split(['.', '@']).next().unwrap_or(raw);
+    if raw.is_empty() || raw.eq_ignore_ascii_case("c") || raw.eq_ignore_ascii_case("posix") {
+        return None;
+    }
+    let mut segments = Vec::new();
+    for (index, segment) in raw.split(['-', '_']).enumerate() {
+        if segment.is_empty() || !segment.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        let segment = if index == 0 {
+            segment.to_ascii_lowercase()
+        } else if segment.len() == 4 {
+            // Script subtags are title case, e.g. `Hans`.
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                }
+                None => return None,
+            }
+        } else {
+            segment.to_ascii_uppercase()
+        };
+        segments.push(segment);
+    }
+    Some(segments.join("-"))
+}
+
+/// Returns the user's preferred languages in preference order, normalized
+/// and deduplicated.
+///
+/// On all platforms the POSIX environment (`LC_ALL`, `LC_MESSAGES`,
+/// `LANGUAGE`, `LANG`) is consulted; on macOS and Windows the OS-level
+/// preference list is read as well, since GUI launches there typically have
+/// an empty environment.
+pub fn system_locales() -> Vec<String> {
+    let mut locales = Vec::new();
+    let mut push = |raw: &str| {
+        if let Some(normalized) = normalize_locale(raw) {
+            if !locales.contains(&normalized) {
+                locales.push(normalized);
+            }
+        }
+    };
+
+    for var in ["LC_ALL", "LC_MESSAGES"] {
+        if let Ok(value) = std::env::var(var) {
+            push(&value);
+        }
+    }
+    // LANGUAGE holds a colon-separated priority list.
+    if let Ok(value) = std::env::var("LANGUAGE") {
+        for entry in value.split(':') {
+            push(entry);
+        }
+    }
+    if let Ok(value) = std::env::var("LANG") {
+        push(&value);
+    }
+
+    for locale in native_locales() {
+        push(&locale);
+    }
+
+    locales
+}
+
+/// Language tags with established pack communities, as (tag, native name,
+/// English name) — shown when prompting for a language and used to default
+/// a new pack's human-readable name. Not a gate: packs for tags outside
+/// this list are fine.
+pub const KNOWN_LANGUAGES: &[(&str, &str, &str)] = &[
+    ("en", "English", "English"),
+    ("en-GB", "English (UK)", "English (UK)"),
+    ("zh", "中文", "Chinese"),
+    ("zh-CN", "简体中文", "Chinese (Simplified)"),
+    ("zh-TW", "繁體中文", "Chinese (Traditional)"),
+    ("ja", "日本語", "Japanese"),
+    ("ko", "한국어", "Korean"),
+    ("de", "Deutsch", "German"),
+    ("fr", "Français", "French"),
+    ("es", "Español", "Spanish"),
+    ("pt-BR", "Português (Brasil)", "Portuguese (Brazil)"),
+    ("pt", "Português", "Portuguese"),
+    ("it", "Italiano", "Italian"),
+    ("ru", "Русский", "Russian"),
+    ("pl", "Polski", "Polish"),
+    ("nl", "Nederlands", "Dutch"),
+    ("tr", "Türkçe", "Turkish"),
+    ("cs", "Čeština", "Czech"),
+    ("uk", "Українська", "Ukrainian"),
+    ("vi", "Tiếng Việt", "Vietnamese"),
+    ("th", "ไทย", "Thai"),
+    ("ar", "العربية", "Arabic"),
+    ("he", "עברית", "Hebrew"),
+    ("hi", "हिन्दी", "Hindi"),
+];
+
+/// The native name for a language tag, when it's in [`KNOWN_LANGUAGES`].
+pub fn native_name(language: &str) -> Option<&'static str> {
+    KNOWN_LANGUAGES
+        .iter()
+        .find(|(tag, _, _)| *tag == language)
+        .map(|(_, name, _)| *name)
+}
+
+/// The English name for a language tag. Falls back from the full tag to its
+/// primary language subtag, so `zh-Hans-SG` still reads as Chinese.
+pub fn english_name(language: &str) -> Option<&'static str> {
+    let exact = KNOWN_LANGUAGES
+        .iter()
+        .find(|(tag, _, _)| *tag == language)
+        .map(|(_, _, name)| *name);
+    exact.or_else(|| {
+        let primary = language.split('-').next()?;
+        KNOWN_LANGUAGES
+            .iter()
+            .find(|(tag, _, _)| *tag == primary)
+            .map(|(_, _, name)| *name)
+    })
+}
+
+/// Checks that `raw` is a plausible IETF language tag and returns its
+/// normalized spelling. Stricter than [`normalize_locale`]: the primary
+/// subtag must be a two- or three-letter language code, so inputs like
+/// `english` or arbitrary words are rejected rather than becoming the name
+/// of a pack nobody can negotiate to.
+pub fn check_language_tag(raw: &str) -> anyhow::Result<String> {
+    let normalized = normalize_locale(raw)
+        .with_context(|| format!("{raw:?} is not a language tag; expected something like zh-CN"))?;
+    let primary = normalized.split('-').next().unwrap_or(&normalized);
+    anyhow::ensure!(
+        (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic()),
+        "{raw:?} does not start with a 2-3 letter language code; expected something like zh-CN"
+    );
+    Ok(normalized)
+}
+
+/// The fallback chain implied by a language tag: successive truncations
+/// from the right, excluding the tag itself. `zh-Hant-TW` yields
+/// `["zh-Hant", "zh"]`; a bare primary subtag yields nothing. Used when the
+/// user hasn't configured `fallback_i18n_langs` explicitly.
+pub fn fallback_chain(language: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut prefix = language;
+    while let Some(split_at) = prefix.rfind('-') {
+        prefix = &prefix[..split_at];
+        chain.push(prefix.to_string());
+    }
+    chain
+}
+
+/// Returns the marketplace extension id that provides a language's pack,
+/// e.g. `i18n-zh-cn` for `zh-CN`.
+pub fn extension_id_for_language(language: &str) -> String {
+    format!("i18n-{}", language.to_ascii_lowercase())
+}
+
+/// A parsed BCP-47-style tag. Only the subtags that matter for matching are
+/// kept; variants and extensions are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageTag {
+    /// Lowercase primary language subtag, e.g. `zh`.
+    pub language: String,
+    /// Title-case script subtag, e.g. `Hans`, when the tag spells one.
+    pub script: Option<String>,
+    /// Uppercase region subtag, e.g. `CN`, or an UN M49 area code.
+    pub region: Option<String>,
+}
+
+impl LanguageTag {
+    pub fn parse(tag: &str) -> Option<Self> {
+        let normalized = normalize_locale(tag)?;
+        let mut segments = normalized.split('-');
+        let language = segments.next()?.to_string();
+        let mut script = None;
+        let mut region = None;
+        for segment in segments {
+            if segment.len() == 4 && segment.chars().all(|c| c.is_ascii_alphabetic()) {
+                script.get_or_insert_with(|| segment.to_string());
+            } else if (segment.len() == 2 && segment.chars().all(|c| c.is_ascii_alphabetic()))
+                || (segment.len() == 3 && segment.chars().all(|c| c.is_ascii_digit()))
+            {
+                region.get_or_insert_with(|| segment.to_string());
+            }
+        }
+        Some(Self {
+            language,
+            script,
+            region,
+        })
+    }
+
+    /// The script actually written, inferred from the language and region
+    /// when the tag doesn't spell one: Chinese is Traditional in Taiwan,
+    /// Hong Kong, and Macau and Simplified elsewhere (the mainland,
+    /// Singapore, Malaysia); Serbian defaults to Cyrillic, which is what
+    /// makes `sr-Latn` a distinct match target.
+    pub fn inferred_script(&self) -> Option<String> {
+        if self.script.is_some() {
+            return self.script.clone();
+        }
+        match self.language.as_str() {
+            "zh" => Some(
+                match self.region.as_deref() {
+                    Some("TW" | "HK" | "MO") => "Hant",
+                    _ => "Hans",
+                }
+                .to_string(),
+            ),
+            "sr" => Some("Cyrl".to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Picks the best available language for a user's ordered preference list.
+///
+/// Tags are parsed rather than substring-matched: a candidate serves a
+/// preference only when the primary languages match and the scripts each
+/// side actually writes (spelled or inferred through
+/// [`LanguageTag::inferred_script`]) don't disagree — so `zh-Hant-HK` finds
+/// `zh-TW` rather than `zh-CN`, and `sr-Latn` never silently gets Cyrillic
+/// Serbian. Among compatible candidates, a spelled-out script match beats
+/// an inferred one and a region match breaks ties; earlier entries win
+/// remaining ties. The returned value is the available language's own
+/// spelling.
+pub fn negotiate<'a>(
+    preferred: impl IntoIterator<Item = &'a str>,
+    available: &'a [String],
+) -> Option<&'a String> {
+    let candidates: Vec<(LanguageTag, &String)> = available
+        .iter()
+        .filter_map(|language| Some((LanguageTag::parse(language)?, language)))
+        .collect();
+
+    for preference in preferred {
+        let Some(want) = LanguageTag::parse(preference) else {
+            continue;
+        };
+        let mut best: Option<(u32, &String)> = None;
+        for (tag, language) in &candidates {
+            let Some(score) = match_score(&want, tag) else {
+                continue;
+            };
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, language));
+            }
+        }
+        if let Some((_, language)) = best {
+            return Some(language);
+        }
+    }
+    None
+}
+
+/// How well `candidate` serves a user preferring `want`: `None` when the
+/// languages differ or the written scripts disagree, otherwise higher for
+/// closer matches.
+fn match_score(want: &LanguageTag, candidate: &LanguageTag) -> Option<u32> {
+    if want.language != candidate.language {
+        return None;
+    }
+    let script_score = match (want.inferred_script(), candidate.inferred_script()) {
+        (Some(a), Some(b)) if a == b => 2,
+        (Some(_), Some(_)) => return None,
+        (None, None) => 2,
+        // One side's script is unknown: compatible, but a weaker match.
+        _ => 1,
+    };
+    let region_score = match (&want.region, &candidate.region) {
+        (Some(a), Some(b)) if a == b => 1,
+        (None, None) => 1,
+        _ => 0,
+    };
+    Some(script_score * 2 + region_score)
+}
+
+#[cfg(target_os = "macos")]
+fn native_locales() -> Vec<String> {
+    // `AppleLanguages` is the ordered preference list from System Settings.
+    let Ok(output) = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleLanguages"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_apple_languages(&stdout)
+}
+
+/// Parses the plist-style output of `defaults read -g AppleLanguages`:
+/// a parenthesized list of quoted language tags.
+#[cfg(any(test, target_os = "macos"))]
+fn parse_apple_languages(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim().trim_end_matches(',');
+            line.strip_prefix('"')?.strip_suffix('"').map(String::from)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn native_locales() -> Vec<String> {
+    use windows::Win32::Globalization::{GetUserDefaultLocaleName, LOCALE_NAME_MAX_LENGTH};
+
+    let mut buffer = [0u16; LOCALE_NAME_MAX_LENGTH as usize];
+    let length = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+    if length <= 1 {
+        return Vec::new();
+    }
+    match String::from_utf16(&buffer[..length as usize - 1]) {
+        Ok(locale) => vec![locale],
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn native_locales() -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_posix_and_bcp47_spellings() {
+        assert_eq!(normalize_locale("zh_CN.UTF-8"), Some("zh-CN".to_string()));
+        assert_eq!(normalize_locale("en_US@euro"), Some("en-US".to_string()));
+        assert_eq!(normalize_locale("zh-hans-cn"), Some("zh-Hans-CN".to_string()));
+        assert_eq!(normalize_locale("PT_br"), Some("pt-BR".to_string()));
+        assert_eq!(normalize_locale("C"), None);
+        assert_eq!(normalize_locale("POSIX"), None);
+        assert_eq!(normalize_locale(""), None);
+        assert_eq!(normalize_locale("not a locale"), None);
+    }
+
+    #[test]
+    fn fallback_chains_drop_subtags_from_the_right() {
+        assert_eq!(fallback_chain("zh-Hant-TW"), ["zh-Hant", "zh"]);
+        assert_eq!(fallback_chain("pt-BR"), ["pt"]);
+        assert!(fallback_chain("en").is_empty());
+    }
+
+    #[test]
+    fn checks_plausibility_beyond_normalization() {
+        assert_eq!(check_language_tag("zh_CN.UTF-8").unwrap(), "zh-CN");
+        assert_eq!(check_language_tag("fil-PH").unwrap(), "fil-PH");
+        assert!(check_language_tag("english").is_err());
+        assert!(check_language_tag("C").is_err());
+        assert!(check_language_tag("12-CN").is_err());
+
+        assert_eq!(native_name("zh-CN"), Some("简体中文"));
+        assert_eq!(native_name("tlh"), None);
+        assert_eq!(english_name("zh-CN"), Some("Chinese (Simplified)"));
+        assert_eq!(english_name("zh-Hans-SG"), Some("Chinese"));
+        assert_eq!(english_name("de-AT"), Some("German"));
+        assert_eq!(english_name("tlh"), None);
+    }
+
+    #[test]
+    fn negotiates_against_available_languages() {
+        let available: Vec<String> = ["en", "zh-CN", "zh-Hant-TW", "pt-BR"]
+            .iter()
+            .map(|language| language.to_string())
+            .collect();
+
+        assert_eq!(negotiate(["zh-CN"], &available), Some(&"zh-CN".to_string()));
+        // Truncation from the right.
+        assert_eq!(
+            negotiate(["zh_Hant_TW.UTF-8"], &available),
+            Some(&"zh-Hant-TW".to_string())
+        );
+        // A bare language finds its shortest available extension.
+        assert_eq!(negotiate(["zh"], &available), Some(&"zh-CN".to_string()));
+        assert_eq!(negotiate(["pt"], &available), Some(&"pt-BR".to_string()));
+        // Preference order wins over match quality of later entries.
+        assert_eq!(
+            negotiate(["fr-FR", "zh-CN", "en"], &available),
+            Some(&"zh-CN".to_string())
+        );
+        assert_eq!(negotiate(["ko"], &available), None);
+        assert_eq!(negotiate([], &available), None);
+    }
+
+    #[test]
+    fn matching_is_script_and_region_aware() {
+        let available: Vec<String> = ["zh-CN", "zh-TW", "sr", "sr-Latn"]
+            .iter()
+            .map(|language| language.to_string())
+            .collect();
+
+        // The script is inferred from the region…
+        assert_eq!(negotiate(["zh-HK"], &available), Some(&"zh-TW".to_string()));
+        assert_eq!(negotiate(["zh-MO"], &available), Some(&"zh-TW".to_string()));
+        assert_eq!(negotiate(["zh-SG"], &available), Some(&"zh-CN".to_string()));
+        // …and a spelled-out script overrides it.
+        assert_eq!(
+            negotiate(["zh-Hans-SG"], &available),
+            Some(&"zh-CN".to_string())
+        );
+        assert_eq!(
+            negotiate(["zh-Hant-HK"], &available),
+            Some(&"zh-TW".to_string())
+        );
+
+        // Latin-script Serbian never silently gets the Cyrillic pack.
+        assert_eq!(
+            negotiate(["sr-Latn-RS"], &available),
+            Some(&"sr-Latn".to_string())
+        );
+        assert_eq!(negotiate(["sr-RS"], &available), Some(&"sr".to_string()));
+        let cyrillic_only = vec!["sr".to_string()];
+        assert_eq!(negotiate(["sr-Latn"], &cyrillic_only), None);
+
+        let parsed = LanguageTag::parse("zh_hant_hk.UTF-8").unwrap();
+        assert_eq!(parsed.language, "zh");
+        assert_eq!(parsed.script.as_deref(), Some("Hant"));
+        assert_eq!(parsed.region.as_deref(), Some("HK"));
+    }
+
+    #[test]
+    fn normalization_matrix() {
+        let fixture = include_str!("../test_data/locale_matrix.txt");
+        let mut checked = 0;
+        for line in fixture.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (raw, expected) = line
+                .split_once(" => ")
+                .unwrap_or_else(|| panic!("malformed fixture line: {line}"));
+            let expected = (expected != "(none)").then(|| expected.to_string());
+            assert_eq!(
+                normalize_locale(raw),
+                expected,
+                "normalize_locale({raw:?})"
+            );
+            checked += 1;
+        }
+        // Guard against the fixture silently going empty after an edit.
+        assert!(checked >= 50, "only {checked} fixture lines");
+    }
+
+    #[test]
+    fn negotiation_matrix() {
+        // (user preferences as the environment spells them, installed packs,
+        // expected pick)
+        let cases: &[(&[&str], &[&str], Option<&str>)] = &[
+            // A German Linux desktop with English packs installed.
+            (&["de-DE", "en-US"], &["en", "zh-CN"], Some("en")),
+            // The first preference wins even when later ones match better.
+            (&["zh-CN", "en-US"], &["en-US", "zh-CN"], Some("zh-CN")),
+            // Unparseable preferences are skipped, not fatal.
+            (&["", "fr_FR.UTF-8"], &["fr"], Some("fr")),
+            // Region narrows within the same language.
+            (&["en-GB"], &["en-US", "en-GB"], Some("en-GB")),
+            (&["en_US.UTF-8"], &["en-US", "en-GB"], Some("en-US")),
+            // A region with no exact pack settles for the same language;
+            // earlier candidates win exact score ties.
+            (&["pt-PT"], &["pt", "pt-BR"], Some("pt")),
+            (&["pt-PT"], &["pt-BR"], Some("pt-BR")),
+            // Chinese matches by written script, spelled or inferred.
+            (&["zh-Hans-CN"], &["zh-CN", "zh-TW"], Some("zh-CN")),
+            (&["zh-Hant-HK"], &["zh-CN", "zh-TW"], Some("zh-TW")),
+            (&["zh_SG.UTF-8"], &["zh-TW", "zh-CN"], Some("zh-CN")),
+            (&["zh-SG"], &["zh-TW"], None),
+            (&["zh-MO"], &["zh-CN", "zh-Hant-TW"], Some("zh-Hant-TW")),
+            // Windows spells Latin-script Serbian with an explicit script.
+            (&["sr-Latn-RS"], &["sr", "sr-Latn"], Some("sr-Latn")),
+            (&["sr-Latn-RS"], &["sr"], None),
+            (&["sr-RS"], &["sr", "sr-Latn"], Some("sr")),
+            // UN M49 area codes participate like regions.
+            (&["es-419"], &["es-ES", "es-419"], Some("es-419")),
+            // No shared language at all.
+            (&["fr"], &["de", "en"], None),
+            (&[], &["en"], None),
+        ];
+        for (preferred, available, expected) in cases {
+            let available: Vec<String> =
+                available.iter().map(|language| language.to_string()).collect();
+            assert_eq!(
+                negotiate(preferred.iter().copied(), &available).map(|s| s.as_str()),
+                *expected,
+                "negotiate({preferred:?}, {available:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_apple_languages_output() {
+        let output = "(\n    \"en-US\",\n    \"zh-Hans-CN\"\n)\n";
+        assert_eq!(
+            parse_apple_languages(output),
+            vec!["en-US".to_string(), "zh-Hans-CN".to_string()]
+        );
+    }
+}
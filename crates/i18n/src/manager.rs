@@ -0,0 +1,2088 @@
+//! The process-wide registry of loaded translations.
+//!
+//! Lookups happen on hot rendering paths and from extension host threads, so
+//! the manager is a plain static behind a read-write lock rather than a GPUI
+//! entity.
+
+use collections::{HashMap, HashSet};
+use gpui::SharedString;
+use parking_lot::RwLock;
+use std::sync::OnceLock;
+
+/// The language used when nothing else is configured. English strings come
+/// from [`crate::defaults`] rather than a language pack.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// The lookup surface of the i18n subsystem.
+///
+/// Code that renders translated strings should accept `&dyn Translations`
+/// (or a generic) rather than calling [`I18nManager::global`] directly, so
+/// unit tests can substitute [`FakeTranslations`] instead of mutating the
+/// process-wide manager.
+pub trait Translations: Send + Sync {
+    fn current_language(&self) -> String;
+    fn get_text(&self, key: &str) -> SharedString;
+    fn get_text_in_lang(&self, language: &str, key: &str) -> SharedString;
+
+    /// The number formatting the current language's pack declares, if any;
+    /// see [`crate::pack::NumberFormat`]. The default suits lookup surfaces
+    /// without pack metadata.
+    fn number_format(&self) -> Option<crate::pack::NumberFormat> {
+        None
+    }
+}
+
+impl Translations for I18nManager {
+    fn current_language(&self) -> String {
+        I18nManager::current_language(self)
+    }
+
+    fn get_text(&self, key: &str) -> SharedString {
+        I18nManager::get_text(self, key)
+    }
+
+    fn get_text_in_lang(&self, language: &str, key: &str) -> SharedString {
+        I18nManager::get_text_in_lang(self, language, key)
+    }
+
+    fn number_format(&self) -> Option<crate::pack::NumberFormat> {
+        let state = self.state.read();
+        state.number_format(&state.current_language).cloned()
+    }
+}
+
+/// An isolated, in-memory [`Translations`] implementation for unit tests.
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Default)]
+pub struct FakeTranslations {
+    pub language: String,
+    pub translations: HashMap<(String, String), SharedString>,
+    pub number_format: Option<crate::pack::NumberFormat>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl FakeTranslations {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            translations: HashMap::default(),
+            number_format: None,
+        }
+    }
+
+    pub fn insert(&mut self, language: &str, key: &str, translation: &str) {
+        self.translations.insert(
+            (language.to_string(), key.to_string()),
+            translation.to_string().into(),
+        );
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Translations for FakeTranslations {
+    fn current_language(&self) -> String {
+        self.language.clone()
+    }
+
+    fn get_text(&self, key: &str) -> SharedString {
+        self.get_text_in_lang(&self.language, key)
+    }
+
+    fn get_text_in_lang(&self, language: &str, key: &str) -> SharedString {
+        if let Some(translation) = self
+            .translations
+            .get(&(language.to_string(), key.to_string()))
+        {
+            return translation.clone();
+        }
+        match crate::defaults::default_text(key) {
+            Some(default) => SharedString::new_static(default),
+            None => self
+                .translations
+                .get(&(DEFAULT_LANGUAGE.to_string(), key.to_string()))
+                .cloned()
+                .unwrap_or_else(|| key.to_string().into()),
+        }
+    }
+
+    fn number_format(&self) -> Option<crate::pack::NumberFormat> {
+        self.number_format.clone()
+    }
+}
+
+/// A stored label that remembers its key instead of its resolved text.
+///
+/// Long-lived UI state (tab titles, status bar segments, persisted
+/// notifications) holds one of these and calls [`Self::resolve`] at render
+/// time, so a language switch re-resolves on the next frame instead of
+/// showing the old language until the state is rebuilt. `{placeholder}`
+/// arguments are substituted after lookup, using the same placeholder
+/// syntax the validator checks.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct TranslatedString {
+    key: SharedString,
+    args: Vec<(SharedString, SharedString)>,
+    number_args: Vec<(SharedString, i64)>,
+}
+
+impl TranslatedString {
+    pub fn new(key: impl Into<SharedString>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+            number_args: Vec::new(),
+        }
+    }
+
+    /// Adds a `{name}` substitution applied after lookup.
+    pub fn with_arg(
+        mut self,
+        name: impl Into<SharedString>,
+        value: impl Into<SharedString>,
+    ) -> Self {
+        self.args.push((name.into(), value.into()));
+        self
+    }
+
+    /// Adds a `{name}` substitution for an integer, rendered at resolve
+    /// time with the digits and grouping the active language's pack
+    /// declares (see [`crate::pack::NumberFormat`]). Keys listed in
+    /// [`crate::defaults::ASCII_NUMBER_KEYS`] opt out and keep plain ASCII;
+    /// values users copy into other tools — addresses, offsets — can also
+    /// just pass pre-formatted text to [`Self::with_arg`].
+    pub fn with_number(mut self, name: impl Into<SharedString>, value: i64) -> Self {
+        self.number_args.push((name.into(), value));
+        self
+    }
+
+    pub fn key(&self) -> &SharedString {
+        &self.key
+    }
+
+    /// Resolves against the current language, at render time.
+    pub fn resolve(&self) -> SharedString {
+        self.resolve_with(I18nManager::global())
+    }
+
+    /// Resolves with an explicit lookup surface, so tests can substitute
+    /// [`FakeTranslations`].
+    pub fn resolve_with(&self, translations: &dyn Translations) -> SharedString {
+        let text = translations.get_text(&self.key);
+        if self.args.is_empty() && self.number_args.is_empty() {
+            return text;
+        }
+        let mut resolved = text.to_string();
+        for (name, value) in &self.args {
+            resolved = resolved.replace(&format!("{{{name}}}"), value);
+        }
+        if !self.number_args.is_empty() {
+            let format = if crate::defaults::ASCII_NUMBER_KEYS.contains(&self.key.as_ref()) {
+                None
+            } else {
+                translations.number_format()
+            };
+            for (name, value) in &self.number_args {
+                resolved = resolved.replace(
+                    &format!("{{{name}}}"),
+                    &crate::text::format_number(*value, format.as_ref()),
+                );
+            }
+        }
+        resolved.into()
+    }
+}
+
+impl std::fmt::Display for TranslatedString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.resolve())
+    }
+}
+
+pub struct I18nManager {
+    state: RwLock<ManagerState>,
+    /// When set, a missing translation panics in debug builds (and logs an
+    /// error with a backtrace in release builds) instead of silently falling
+    /// back. Enabled with `ZED_I18N_STRICT=1`.
+    strict: std::sync::atomic::AtomicBool,
+    /// When set, UI-facing lookups suffix the resolved text with the key it
+    /// came from, so translators can map on-screen strings back to `i18n.*`
+    /// keys without grepping. Toggled from the command palette.
+    key_overlay: std::sync::atomic::AtomicBool,
+    /// When set, UI-facing lookups render the key instead of any text, so a
+    /// QA screenshot identifies every string on screen. Set from the
+    /// `show_keys` setting.
+    raw_keys: std::sync::atomic::AtomicBool,
+    /// See [`TranslationCache`]. Internally concurrent, so lookups from the
+    /// render thread and extension host threads never serialize behind each
+    /// other or behind registrations.
+    cache: TranslationCache,
+}
+
+#[derive(Default)]
+struct ManagerState {
+    current_language: String,
+    /// Strings loaded from the `dev_pack_path` pack source directory. They
+    /// outrank user overrides and every registered source, so a pack author
+    /// iterating on files sees exactly what's on disk.
+    dev_entries: HashMap<String, HashMap<String, SharedString>>,
+    /// Per-language overrides from the user's `translations.json`. These win
+    /// over every registered source. Values are `SharedString`s so lookups
+    /// hand out cheap clones instead of copying the text.
+    user_overrides: HashMap<String, HashMap<String, SharedString>>,
+    /// Keys that were looked up this session without finding a translation
+    /// in the language they were requested for, per language. Deduplicated,
+    /// and ordered for stable export.
+    missing_keys: HashMap<String, std::collections::BTreeSet<String>>,
+    /// Registered translation tables in registration order. Sources are kept
+    /// separate rather than merged so a pack's strings can be removed
+    /// exactly when it is uninstalled.
+    sources: Vec<TranslationSource>,
+    /// Whether any loaded table contains an `@platform` key. Platform
+    /// probing doubles every lookup, so it's skipped entirely until some
+    /// source or override actually uses the suffix.
+    has_platform_variants: bool,
+    /// Parent edges declared by sparse packs through `extends` in their
+    /// metadata, e.g. `en-GB` → `en-US`. Lookups that miss in a language
+    /// continue through its chain.
+    parents: HashMap<String, String>,
+    /// The number formatting each language's pack declares in its metadata.
+    /// Languages without an entry inherit through the parent chain, then
+    /// fall back to plain ASCII digits.
+    number_formats: HashMap<String, crate::pack::NumberFormat>,
+    /// The font fallback hints each language's pack declares in its
+    /// metadata. Languages without an entry inherit through the parent
+    /// chain, like number formats.
+    font_hints: HashMap<String, crate::pack::FontHints>,
+    /// The documentation link routing each language's pack declares in its
+    /// metadata. Languages without an entry inherit through the parent
+    /// chain, like number formats.
+    docs_links: HashMap<String, crate::pack::DocsLinks>,
+    /// The user-configured fallback chain from `fallback_i18n_langs`.
+    /// Consulted in order when a lookup misses in the requested language,
+    /// before the built-in English fallback.
+    fallback_languages: Vec<String>,
+    /// One `SharedString` per distinct translation value. Many values repeat
+    /// across languages and packs ("OK", product names, untranslated entries
+    /// copied from the template); registration reuses the interned clone so
+    /// the text is allocated once no matter how many packs carry it. Entries
+    /// stay pooled for the life of the process — values are small and
+    /// bounded by the union of distinct pack contents.
+    interned_values: HashSet<SharedString>,
+}
+
+impl ManagerState {
+    /// Finds the winning translation for `key` in `language`: user overrides
+    /// first, then registered sources, most recently registered first. When
+    /// a table defines a variant for this build's platform
+    /// (`key@macos`/`@linux`/`@windows`), the variant wins over the bare
+    /// key. A miss continues through the language's parent chain, so a
+    /// sparse `en-GB` pack only needs the keys that differ from `en-US`.
+    fn lookup(&self, language: &str, key: &str) -> Option<&SharedString> {
+        // Call sites still using a deprecated key name resolve to the
+        // current one; registration canonicalizes the stored side.
+        let key = crate::defaults::canonical_key(key);
+        let mut language = language;
+        // The chain is finite pack data, but bound the walk in case a bad
+        // pack introduces a parent cycle.
+        for _ in 0..=self.parents.len() {
+            if self.has_platform_variants {
+                let variant = format!("{key}@{}", crate::keys::CURRENT_PLATFORM);
+                if let Some(translation) = self.lookup_exact(language, &variant) {
+                    return Some(translation);
+                }
+            }
+            if let Some(translation) = self.lookup_exact(language, key) {
+                return Some(translation);
+            }
+            language = self.parents.get(language)?.as_str();
+        }
+        None
+    }
+
+    /// Like [`Self::lookup`], but a miss continues through the configured
+    /// fallback chain, each entry walking its own parent chain. The English
+    /// fallback stays at the call sites, after this returns `None`.
+    fn lookup_with_fallbacks(&self, language: &str, key: &str) -> Option<&SharedString> {
+        if let Some(translation) = self.lookup(language, key) {
+            return Some(translation);
+        }
+        self.fallback_languages
+            .iter()
+            .filter(|fallback| fallback.as_str() != language)
+            .find_map(|fallback| self.lookup(fallback, key))
+    }
+
+    /// The number formatting in effect for `language`: its own pack's
+    /// declaration, or the nearest ancestor's through the parent chain.
+    fn number_format(&self, language: &str) -> Option<&crate::pack::NumberFormat> {
+        let mut language = language;
+        // Bounded like [`Self::lookup`], in case of a parent cycle.
+        for _ in 0..=self.parents.len() {
+            if let Some(format) = self.number_formats.get(language) {
+                return Some(format);
+            }
+            language = self.parents.get(language)?.as_str();
+        }
+        None
+    }
+
+    /// The font fallback hints in effect for `language`: its own pack's
+    /// declaration, or the nearest ancestor's through the parent chain.
+    fn font_hints(&self, language: &str) -> Option<&crate::pack::FontHints> {
+        let mut language = language;
+        // Bounded like [`Self::lookup`], in case of a parent cycle.
+        for _ in 0..=self.parents.len() {
+            if let Some(hints) = self.font_hints.get(language) {
+                return Some(hints);
+            }
+            language = self.parents.get(language)?.as_str();
+        }
+        None
+    }
+
+    /// The documentation link routing in effect for `language`: its own
+    /// pack's declaration, or the nearest ancestor's through the parent
+    /// chain.
+    fn docs_links(&self, language: &str) -> Option<&crate::pack::DocsLinks> {
+        let mut language = language;
+        // Bounded like [`Self::lookup`], in case of a parent cycle.
+        for _ in 0..=self.parents.len() {
+            if let Some(links) = self.docs_links.get(language) {
+                return Some(links);
+            }
+            language = self.parents.get(language)?.as_str();
+        }
+        None
+    }
+
+    fn lookup_exact(&self, language: &str, key: &str) -> Option<&SharedString> {
+        if let Some(translation) = self
+            .dev_entries
+            .get(language)
+            .and_then(|entries| entries.get(key))
+        {
+            return Some(translation);
+        }
+        if let Some(translation) = self
+            .user_overrides
+            .get(language)
+            .and_then(|overrides| overrides.get(key))
+        {
+            return Some(translation);
+        }
+        self.sources
+            .iter()
+            .rev()
+            .filter(|source| source.language == language)
+            .find_map(|source| source.translations.get(key))
+    }
+
+    fn refresh_platform_variants(&mut self) {
+        self.has_platform_variants = self
+            .user_overrides
+            .values()
+            .flat_map(|overrides| overrides.keys())
+            .chain(self.dev_entries.values().flat_map(|entries| entries.keys()))
+            .chain(
+                self.sources
+                    .iter()
+                    .flat_map(|source| source.translations.keys()),
+            )
+            .any(|key| crate::keys::split_platform(key).1.is_some());
+    }
+
+    /// Replaces what `source_id` provides for `language`. Deprecated key
+    /// names are stored under their current names, so packs built against an
+    /// older key schema keep working through the aliases in
+    /// [`crate::defaults::KEY_ALIASES`].
+    fn insert_source(
+        &mut self,
+        source_id: &str,
+        language: &str,
+        entries: impl IntoIterator<Item = (String, String)>,
+        translators: Vec<String>,
+    ) {
+        self.sources
+            .retain(|source| !(source.id == source_id && source.language == language));
+        let interned_values = &mut self.interned_values;
+        self.sources.push(TranslationSource {
+            id: source_id.to_string(),
+            language: language.to_string(),
+            translations: entries
+                .into_iter()
+                .map(|(key, value)| {
+                    // A platform variant of a deprecated key canonicalizes
+                    // its base and keeps the suffix.
+                    let (base, platform) = crate::keys::split_platform(&key);
+                    let canonical = crate::defaults::canonical_key(base);
+                    let value = intern_value(interned_values, canonical, value);
+                    if canonical == base {
+                        (key, value)
+                    } else {
+                        match platform {
+                            Some(platform) => (format!("{canonical}@{platform}"), value),
+                            None => (canonical.to_string(), value),
+                        }
+                    }
+                })
+                .collect(),
+            translators,
+        });
+        self.refresh_platform_variants();
+    }
+}
+
+/// Cumulative counters and current occupancy of the resolution cache,
+/// for tuning the capacity with data. Surfaced by the
+/// `i18n: copy cache stats` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub size: usize,
+    pub capacity: usize,
+}
+
+/// Memoizes fully resolved texts per (language, key), in front of the
+/// canonicalization, platform probing, and chain walking `lookup` does.
+/// Invalidated wholesale whenever registered translations change; the
+/// counters survive invalidation so tuning sees the whole session.
+///
+/// Entries live in a lock-free `moka` cache, so concurrent lookups never
+/// serialize behind each other; the surrounding `RwLock` is write-locked
+/// only to rebuild the cache when the capacity setting changes.
+struct TranslationCache {
+    entries: parking_lot::RwLock<moka::sync::Cache<(String, String), SharedString>>,
+    capacity: std::sync::atomic::AtomicUsize,
+    hits: std::sync::atomic::AtomicU64,
+    misses: std::sync::atomic::AtomicU64,
+    /// Incremented from the cache's eviction listener. Shared through an
+    /// `Arc` because the listener outlives the borrow a field would need.
+    evictions: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+
+/// The capacity used until the `translation_cache_capacity` setting is
+/// applied.
+pub const DEFAULT_TRANSLATION_CACHE_CAPACITY: usize = 1000;
+
+impl TranslationCache {
+    fn new(capacity: usize) -> Self {
+        let evictions = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+        Self {
+            entries: parking_lot::RwLock::new(Self::build_entries(capacity, evictions.clone())),
+            capacity: std::sync::atomic::AtomicUsize::new(capacity),
+            hits: std::sync::atomic::AtomicU64::new(0),
+            misses: std::sync::atomic::AtomicU64::new(0),
+            evictions,
+        }
+    }
+
+    fn build_entries(
+        capacity: usize,
+        evictions: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    ) -> moka::sync::Cache<(String, String), SharedString> {
+        moka::sync::Cache::builder()
+            .max_capacity(capacity as u64)
+            .eviction_listener(move |_key, _value, cause| {
+                // Explicit invalidation and overwrites also report here;
+                // only capacity pressure counts as an eviction.
+                if cause == moka::notification::RemovalCause::Size {
+                    evictions.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
+            })
+            .build()
+    }
+
+    fn get(&self, language: &str, key: &str) -> Option<SharedString> {
+        if self.capacity.load(std::sync::atomic::Ordering::Relaxed) == 0 {
+            return None;
+        }
+        let text = self
+            .entries
+            .read()
+            .get(&(language.to_string(), key.to_string()));
+        match text.is_some() {
+            true => &self.hits,
+            false => &self.misses,
+        }
+        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        text
+    }
+
+    fn insert(&self, language: String, key: String, text: SharedString) {
+        if self.capacity.load(std::sync::atomic::Ordering::Relaxed) == 0 {
+            return;
+        }
+        self.entries.read().insert((language, key), text);
+    }
+
+    fn invalidate(&self) {
+        self.entries.read().invalidate_all();
+    }
+}
+
+/// Returns the shared text for a translation value: the static English
+/// reference string when the value equals its key's default (an
+/// untranslated entry costs no heap at all), or the pooled clone shared
+/// with every other source carrying the same value.
+fn intern_value(
+    pool: &mut HashSet<SharedString>,
+    canonical_key: &str,
+    value: String,
+) -> SharedString {
+    if let Some(default) = crate::defaults::default_text(canonical_key) {
+        if default == value {
+            return SharedString::new_static(default);
+        }
+    }
+    if let Some(existing) = pool.get(value.as_str()) {
+        return existing.clone();
+    }
+    let value = SharedString::from(value);
+    pool.insert(value.clone());
+    value
+}
+
+struct TranslationSource {
+    /// The extension id of the pack that registered these translations, or
+    /// another stable tag for built-in sources.
+    id: String,
+    language: String,
+    translations: HashMap<String, SharedString>,
+    /// Credits from the pack's manifest or its translation file's reserved
+    /// `translators` entry, for display in the UI.
+    translators: Vec<String>,
+}
+
+impl I18nManager {
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<I18nManager> = OnceLock::new();
+        GLOBAL.get_or_init(|| I18nManager {
+            state: RwLock::new(ManagerState {
+                current_language: DEFAULT_LANGUAGE.to_string(),
+                dev_entries: HashMap::default(),
+                user_overrides: HashMap::default(),
+                missing_keys: HashMap::default(),
+                sources: Vec::new(),
+                has_platform_variants: false,
+                parents: HashMap::default(),
+                number_formats: HashMap::default(),
+                font_hints: HashMap::default(),
+                docs_links: HashMap::default(),
+                fallback_languages: Vec::new(),
+                interned_values: HashSet::default(),
+            }),
+            strict: std::sync::atomic::AtomicBool::new(
+                std::env::var("ZED_I18N_STRICT").is_ok_and(|value| value == "1"),
+            ),
+            key_overlay: std::sync::atomic::AtomicBool::new(false),
+            raw_keys: std::sync::atomic::AtomicBool::new(false),
+            cache: TranslationCache::new(DEFAULT_TRANSLATION_CACHE_CAPACITY),
+        })
+    }
+
+    pub fn set_strict(&self, strict: bool) {
+        self.strict
+            .store(strict, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn set_key_overlay(&self, enabled: bool) {
+        self.key_overlay
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn key_overlay_enabled(&self) -> bool {
+        self.key_overlay.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn set_raw_keys(&self, enabled: bool) {
+        self.raw_keys
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn raw_keys_enabled(&self) -> bool {
+        self.raw_keys.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Applies the developer key overlay to a resolved text. A no-op when the
+    /// overlay is off, or when the text already is the key (an unknown key
+    /// needs no annotation).
+    fn annotate(&self, key: &str, text: SharedString) -> SharedString {
+        if self.raw_keys_enabled() {
+            // The brackets distinguish the forced raw-key mode from a
+            // missing translation, which renders the bare key.
+            return format!("⟦{key}⟧").into();
+        }
+        if !self.key_overlay_enabled() || text == key {
+            return text;
+        }
+        format!("{text} ⟦{key}⟧").into()
+    }
+
+    pub fn current_language(&self) -> String {
+        self.state.read().current_language.clone()
+    }
+
+    pub fn set_current_language(&self, language: &str) {
+        self.state.write().current_language = language.to_string();
+        self.cache.invalidate();
+    }
+
+    /// Registers the translations a source provides for `language`,
+    /// replacing anything the same source registered for that language
+    /// before. On key conflicts between sources, the most recently
+    /// registered source wins. Deprecated key names are stored under their
+    /// current names, so packs built against an older key schema keep
+    /// working through the aliases in [`crate::defaults::KEY_ALIASES`].
+    pub fn register_translations(
+        &self,
+        source_id: &str,
+        language: &str,
+        entries: impl IntoIterator<Item = (String, String)>,
+    ) {
+        self.state
+            .write()
+            .insert_source(source_id, language, entries, Vec::new());
+        self.cache.invalidate();
+    }
+
+    /// Registers a freshly loaded pack (when one provides the language) and
+    /// makes `language` current in a single critical section, so readers see
+    /// either the old language or the new one with its strings fully
+    /// published — never a mixed state. The language's missing-key log is
+    /// cleared, since earlier misses may now be covered.
+    pub fn switch_language(
+        &self,
+        language: &str,
+        source: Option<(String, Vec<(String, String)>, Vec<String>)>,
+    ) {
+        let mut state = self.state.write();
+        if let Some((source_id, entries, translators)) = source {
+            state.insert_source(&source_id, language, entries, translators);
+        }
+        state.current_language = language.to_string();
+        state.missing_keys.remove(language);
+        drop(state);
+        self.cache.invalidate();
+    }
+
+    /// Records (or clears) the parent language a sparse pack for `language`
+    /// declares through `extends` in its metadata. Lookups that miss in
+    /// `language` continue through the chain of recorded parents.
+    pub fn set_language_parent(&self, language: &str, parent: Option<String>) {
+        let mut state = self.state.write();
+        match parent {
+            Some(parent) => {
+                state.parents.insert(language.to_string(), parent);
+            }
+            None => {
+                state.parents.remove(language);
+            }
+        }
+        drop(state);
+        self.cache.invalidate();
+    }
+
+    /// Records (or clears) the number formatting `language`'s pack declares
+    /// in its metadata. Substituted numeric placeholders render with these
+    /// digits and grouping; languages without a declaration inherit their
+    /// parent's, then fall back to plain ASCII.
+    pub fn set_number_format(&self, language: &str, format: Option<crate::pack::NumberFormat>) {
+        let mut state = self.state.write();
+        match format {
+            Some(format) => {
+                state.number_formats.insert(language.to_string(), format);
+            }
+            None => {
+                state.number_formats.remove(language);
+            }
+        }
+        // No cache invalidation: the cache stores texts before placeholder
+        // substitution, and shaping happens at substitution time.
+    }
+
+    /// The number formatting in effect for `language`, following the parent
+    /// chain like translation lookups do.
+    pub fn number_format_in_lang(&self, language: &str) -> Option<crate::pack::NumberFormat> {
+        self.state.read().number_format(language).cloned()
+    }
+
+    /// Records (or clears) the font fallback hints `language`'s pack
+    /// declares in its metadata. The UI font setup appends them to the
+    /// fallback stack while the language is active.
+    pub fn set_font_hints(&self, language: &str, hints: Option<crate::pack::FontHints>) {
+        let mut state = self.state.write();
+        match hints {
+            Some(hints) => {
+                state.font_hints.insert(language.to_string(), hints);
+            }
+            None => {
+                state.font_hints.remove(language);
+            }
+        }
+    }
+
+    /// The font fallback hints in effect for the current language, following
+    /// the parent chain like translation lookups do.
+    pub fn font_hints(&self) -> Option<crate::pack::FontHints> {
+        let state = self.state.read();
+        state.font_hints(&state.current_language).cloned()
+    }
+
+    /// Like [`Self::font_hints`], but for an explicit language.
+    pub fn font_hints_in_lang(&self, language: &str) -> Option<crate::pack::FontHints> {
+        self.state.read().font_hints(language).cloned()
+    }
+
+    /// Records (or clears) the documentation link routing `language`'s pack
+    /// declares in its metadata. Docs URLs opened from the UI resolve
+    /// through it while the language is active.
+    pub fn set_docs_links(&self, language: &str, links: Option<crate::pack::DocsLinks>) {
+        let mut state = self.state.write();
+        match links {
+            Some(links) => {
+                state.docs_links.insert(language.to_string(), links);
+            }
+            None => {
+                state.docs_links.remove(language);
+            }
+        }
+    }
+
+    /// The documentation link routing in effect for the current language,
+    /// following the parent chain like translation lookups do.
+    pub fn docs_links(&self) -> Option<crate::pack::DocsLinks> {
+        let state = self.state.read();
+        state.docs_links(&state.current_language).cloned()
+    }
+
+    /// Like [`Self::docs_links`], but for an explicit language.
+    pub fn docs_links_in_lang(&self, language: &str) -> Option<crate::pack::DocsLinks> {
+        self.state.read().docs_links(language).cloned()
+    }
+
+    /// Replaces the dev pack's strings for `language`, dropping whatever an
+    /// earlier dev pack provided. Dev entries outrank user overrides and
+    /// every registered source; see the `dev_pack_path` setting.
+    pub fn set_dev_translations(
+        &self,
+        language: &str,
+        entries: impl IntoIterator<Item = (String, String)>,
+    ) {
+        let mut state = self.state.write();
+        let interned_values = &mut state.interned_values;
+        let entries: HashMap<String, SharedString> = entries
+            .into_iter()
+            .map(|(key, value)| {
+                // The same canonicalization registration applies, so a dev
+                // pack written against an older key schema still works.
+                let (base, platform) = crate::keys::split_platform(&key);
+                let canonical = crate::defaults::canonical_key(base);
+                let value = intern_value(interned_values, canonical, value);
+                if canonical == base {
+                    (key, value)
+                } else {
+                    match platform {
+                        Some(platform) => (format!("{canonical}@{platform}"), value),
+                        None => (canonical.to_string(), value),
+                    }
+                }
+            })
+            .collect();
+        state.dev_entries = HashMap::from_iter([(language.to_string(), entries)]);
+        state.refresh_platform_variants();
+        drop(state);
+        self.cache.invalidate();
+    }
+
+    /// Removes the dev pack's strings, when `dev_pack_path` is unset.
+    pub fn clear_dev_translations(&self) {
+        let mut state = self.state.write();
+        if state.dev_entries.is_empty() {
+            return;
+        }
+        state.dev_entries = HashMap::default();
+        state.refresh_platform_variants();
+        drop(state);
+        self.cache.invalidate();
+    }
+
+    /// Replaces the configured fallback chain. Lookups that miss in the
+    /// requested language (and its parent chain) try these languages in
+    /// order before the built-in English fallback.
+    pub fn set_fallback_languages(&self, languages: Vec<String>) {
+        self.state.write().fallback_languages = languages;
+        self.cache.invalidate();
+    }
+
+    /// Resizes the resolution cache. The cache restarts empty — the working
+    /// set repopulates within a frame — and the counters carry over. A
+    /// capacity of zero disables caching.
+    pub fn set_cache_capacity(&self, capacity: usize) {
+        if self
+            .cache
+            .capacity
+            .swap(capacity, std::sync::atomic::Ordering::Relaxed)
+            == capacity
+        {
+            return;
+        }
+        // A moka cache can't be resized in place; the setting changes
+        // rarely enough that rebuilding is fine.
+        *self.cache.entries.write() =
+            TranslationCache::build_entries(capacity, self.cache.evictions.clone());
+    }
+
+    /// The resolution cache's cumulative counters and current occupancy.
+    pub fn cache_stats(&self) -> CacheStats {
+        let entries = self.cache.entries.read();
+        // Evictions and the entry count are maintained lazily; flush so the
+        // numbers reflect every lookup made before this call.
+        entries.run_pending_tasks();
+        CacheStats {
+            hits: self.cache.hits.load(std::sync::atomic::Ordering::Relaxed),
+            misses: self.cache.misses.load(std::sync::atomic::Ordering::Relaxed),
+            evictions: self
+                .cache
+                .evictions
+                .load(std::sync::atomic::Ordering::Relaxed),
+            size: entries.entry_count() as usize,
+            capacity: self
+                .cache
+                .capacity
+                .load(std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+
+    /// Records who translated the strings a source registered for
+    /// `language`. A no-op if the source hasn't registered translations for
+    /// that language, so call it after [`Self::register_translations`].
+    pub fn set_translators(&self, source_id: &str, language: &str, translators: Vec<String>) {
+        let mut state = self.state.write();
+        if let Some(source) = state
+            .sources
+            .iter_mut()
+            .find(|source| source.id == source_id && source.language == language)
+        {
+            source.translators = translators;
+        }
+    }
+
+    /// Returns the translator credits for a language across every registered
+    /// source, in registration order with duplicates removed.
+    pub fn translators(&self, language: &str) -> Vec<String> {
+        let state = self.state.read();
+        let mut result: Vec<String> = Vec::new();
+        for source in &state.sources {
+            if source.language != language {
+                continue;
+            }
+            for translator in &source.translators {
+                if !result.contains(translator) {
+                    result.push(translator.clone());
+                }
+            }
+        }
+        result
+    }
+
+    /// Removes every translation the given source registered, across all
+    /// languages. Called when the providing language pack is uninstalled.
+    pub fn unregister_source(&self, source_id: &str) {
+        let mut state = self.state.write();
+        state.sources.retain(|source| source.id != source_id);
+        state.refresh_platform_variants();
+        drop(state);
+        self.cache.invalidate();
+    }
+
+    /// Loads the user's personal translation overrides, replacing any
+    /// previously loaded set. The file maps language tags to key/value
+    /// tables:
+    ///
+    /// ```json
+    /// { "zh-CN": { "i18n.menu.file.save": "保存文件" } }
+    /// ```
+    pub fn load_user_overrides(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        if !path.exists() {
+            let mut state = self.state.write();
+            state.user_overrides = HashMap::default();
+            state.refresh_platform_variants();
+            drop(state);
+            self.cache.invalidate();
+            return Ok(());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        let overrides: HashMap<String, HashMap<String, SharedString>> =
+            serde_json_lenient::from_str(&contents)?;
+        let mut state = self.state.write();
+        state.user_overrides = overrides;
+        state.refresh_platform_variants();
+        drop(state);
+        self.cache.invalidate();
+        Ok(())
+    }
+
+    /// Returns the translation for `key` in the current language. Misses
+    /// fall back to the English default; keys outside the reference table
+    /// (extension keys) fall back to whatever was registered for English, so
+    /// a pack that covers only part of an extension's strings shows that
+    /// extension's English rather than raw `i18n.*` keys. Only a key nobody
+    /// registered English text for renders as itself.
+    pub fn get_text(&self, key: &str) -> SharedString {
+        let state = self.state.read();
+        let language = state.current_language.clone();
+        // The cache stores the text before overlay annotation, so toggling
+        // the overlay needs no invalidation.
+        if let Some(text) = self.cache.get(&language, key) {
+            return self.annotate(key, text);
+        }
+        if let Some(translation) = state.lookup_with_fallbacks(&language, key) {
+            let translation = translation.clone();
+            drop(state);
+            self.cache
+                .insert(language, key.to_string(), translation.clone());
+            return self.annotate(key, translation);
+        }
+        let fallback = self.english_fallback(&state, &language, key);
+        drop(state);
+        // English renders straight from the reference strings, so a miss
+        // there only means the key itself is unknown.
+        if language != DEFAULT_LANGUAGE || fallback.is_none() {
+            self.record_missing(&language, key);
+        }
+        let text: SharedString = match fallback {
+            Some(text) => text,
+            None => key.to_string().into(),
+        };
+        self.cache.insert(language, key.to_string(), text.clone());
+        self.annotate(key, text)
+    }
+
+    /// The last-resort text for a key that missed in `language`: the English
+    /// reference string, or for keys outside the reference table whatever a
+    /// source registered for English.
+    fn english_fallback(
+        &self,
+        state: &ManagerState,
+        language: &str,
+        key: &str,
+    ) -> Option<SharedString> {
+        if let Some(default) = crate::defaults::default_text(key) {
+            return Some(SharedString::new_static(default));
+        }
+        // When English is current, `lookup` already probed its tables.
+        if language == DEFAULT_LANGUAGE {
+            return None;
+        }
+        state.lookup(DEFAULT_LANGUAGE, key).cloned()
+    }
+
+    /// The translation the current language actually provides for `key`, or
+    /// `None` when only the English fallback would apply. For callers that
+    /// already carry English text of their own — like the settings schema's
+    /// doc comments — and substitute a translation only when one exists.
+    /// Uncached: misses here aren't recorded as missing translations, and
+    /// the callers run rarely.
+    pub fn translated_text(&self, key: &str) -> Option<SharedString> {
+        let state = self.state.read();
+        state
+            .lookup_with_fallbacks(&state.current_language, key)
+            .cloned()
+    }
+
+    /// Like [`Self::get_text`], but for a key resolved at compile time by
+    /// [`crate::i18n_key!`]. The English default comes from the key's
+    /// precomputed table index, and the unknown-key fallback reuses the
+    /// key's static text, so neither path hashes a string or allocates;
+    /// only lookups into registered sources and overrides still do.
+    pub fn get_text_keyed(&self, key: crate::keys::I18nKey) -> SharedString {
+        let state = self.state.read();
+        if let Some(text) = self.cache.get(&state.current_language, key.text()) {
+            return self.annotate(key.text(), text);
+        }
+        if let Some(translation) = state.lookup_with_fallbacks(&state.current_language, key.text())
+        {
+            let translation = translation.clone();
+            self.cache.insert(
+                state.current_language.clone(),
+                key.text().to_string(),
+                translation.clone(),
+            );
+            return self.annotate(key.text(), translation);
+        }
+        // The precomputed index replaces `english_fallback`'s table search.
+        let fallback = match key.default_text() {
+            Some(default) => Some(SharedString::new_static(default)),
+            None if state.current_language != DEFAULT_LANGUAGE => {
+                state.lookup(DEFAULT_LANGUAGE, key.text()).cloned()
+            }
+            None => None,
+        };
+        if state.current_language != DEFAULT_LANGUAGE || fallback.is_none() {
+            let language = state.current_language.clone();
+            drop(state);
+            self.record_missing(&language, key.text());
+        }
+        match fallback {
+            Some(text) => self.annotate(key.text(), text),
+            None => SharedString::new_static(key.text()),
+        }
+    }
+
+    /// Like [`Self::get_text_keyed`], but with an English default written at
+    /// the call site, for keys that haven't been folded into the reference
+    /// table yet. The reference table still wins when it defines the key,
+    /// so a call site can never silently shadow the canonical text. Used
+    /// through [`crate::t_default!`].
+    pub fn get_text_or(&self, key: crate::keys::I18nKey, default: &'static str) -> SharedString {
+        let state = self.state.read();
+        if let Some(translation) = state.lookup_with_fallbacks(&state.current_language, key.text())
+        {
+            return self.annotate(key.text(), translation.clone());
+        }
+        if state.current_language != DEFAULT_LANGUAGE {
+            let language = state.current_language.clone();
+            drop(state);
+            self.record_missing(&language, key.text());
+        }
+        let text = key.default_text().unwrap_or(default);
+        self.annotate(key.text(), SharedString::new_static(text))
+    }
+
+    /// Records a miss in the session log. Some call sites miss on every
+    /// frame, so only the first miss per (language, key) is logged as a
+    /// warning; the full accumulated set stays available through
+    /// [`Self::missing_keys`].
+    fn record_missing(&self, language: &str, key: &str) {
+        let first_miss = self
+            .state
+            .write()
+            .missing_keys
+            .entry(language.to_string())
+            .or_default()
+            .insert(key.to_string());
+        if first_miss {
+            log::warn!("missing translation for key {key:?} in language {language}");
+        }
+        if self.strict.load(std::sync::atomic::Ordering::Relaxed) {
+            util::debug_panic!("missing translation for key {key:?} in language {language}");
+        }
+    }
+
+    /// Like [`Self::get_text`], but looks up in the given language instead
+    /// of the current one. Intended for tooling and extension queries; misses
+    /// are not recorded in the session log.
+    pub fn get_text_in_lang(&self, language: &str, key: &str) -> SharedString {
+        let state = self.state.read();
+        if let Some(translation) = state.lookup_with_fallbacks(language, key) {
+            return translation.clone();
+        }
+        match self.english_fallback(&state, language, key) {
+            Some(text) => text,
+            None => key.to_string().into(),
+        }
+    }
+
+    /// Resolves many keys in the current language while holding the lock
+    /// only once, in input order. Misses are recorded in the session log
+    /// after the lock is released.
+    pub fn get_texts<'a>(&self, keys: impl IntoIterator<Item = &'a str>) -> Vec<SharedString> {
+        let state = self.state.read();
+        let language = state.current_language.clone();
+        let mut misses = Vec::new();
+        let texts = keys
+            .into_iter()
+            .map(|key| match state.lookup_with_fallbacks(&language, key) {
+                Some(translation) => self.annotate(key, translation.clone()),
+                None => {
+                    let fallback = self.english_fallback(&state, &language, key);
+                    if language != DEFAULT_LANGUAGE || fallback.is_none() {
+                        misses.push(key);
+                    }
+                    match fallback {
+                        Some(text) => self.annotate(key, text),
+                        None => key.to_string().into(),
+                    }
+                }
+            })
+            .collect();
+        drop(state);
+        for key in misses {
+            self.record_missing(&language, key);
+        }
+        texts
+    }
+
+    /// Returns how many of the reference keys have a translation registered
+    /// for `language` (from user overrides or sources), for coverage
+    /// reporting. English is fully covered by definition.
+    pub fn translated_reference_key_count(&self, language: &str) -> usize {
+        if language == DEFAULT_LANGUAGE {
+            return crate::defaults::DEFAULT_TEXTS.len();
+        }
+        let state = self.state.read();
+        crate::defaults::DEFAULT_TEXTS
+            .iter()
+            .filter(|(key, _)| state.lookup(language, key).is_some())
+            .count()
+    }
+
+    /// The fraction (0.0–1.0) of the reference keys that resolve to a
+    /// translation for `language`. Shown in the About dialog and the
+    /// language selector so users can see why parts of the UI remain in
+    /// English. English is fully covered by definition.
+    pub fn coverage(&self, language: &str) -> f32 {
+        let total = crate::defaults::DEFAULT_TEXTS.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.translated_reference_key_count(language) as f32 / total as f32
+    }
+
+    /// Returns every language a registered source or the dev pack provides,
+    /// plus the default language, sorted and deduplicated. This is the
+    /// candidate set for locale negotiation and the language selector.
+    pub fn available_languages(&self) -> Vec<String> {
+        let state = self.state.read();
+        let mut languages: Vec<String> = state
+            .sources
+            .iter()
+            .map(|source| source.language.clone())
+            .chain(state.dev_entries.keys().cloned())
+            .chain([DEFAULT_LANGUAGE.to_string()])
+            .collect();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    /// Returns the fully resolved translation table for a language: every
+    /// reference key with the value a user on that language actually sees —
+    /// user overrides, then registered sources, then the English default —
+    /// plus any keys beyond the reference set that overrides or sources
+    /// provide. Misses are not recorded in the session log.
+    pub fn effective_translations(
+        &self,
+        language: &str,
+    ) -> std::collections::BTreeMap<String, String> {
+        let state = self.state.read();
+        let mut effective = std::collections::BTreeMap::new();
+        for (key, default) in crate::defaults::DEFAULT_TEXTS {
+            let value = match state.lookup(language, key) {
+                Some(translation) => translation.to_string(),
+                None => (*default).to_string(),
+            };
+            effective.insert((*key).to_string(), value);
+        }
+        let extra_keys = state
+            .user_overrides
+            .get(language)
+            .into_iter()
+            .flat_map(|overrides| overrides.keys())
+            .chain(
+                state
+                    .sources
+                    .iter()
+                    .filter(|source| source.language == language)
+                    .flat_map(|source| source.translations.keys()),
+            );
+        for key in extra_keys {
+            if !effective.contains_key(key) {
+                if let Some(value) = state.lookup(language, key) {
+                    effective.insert(key.clone(), value.to_string());
+                }
+            }
+        }
+        effective
+    }
+
+    /// Returns the keys that missed translation this session, per language.
+    pub fn missing_keys(&self) -> HashMap<String, std::collections::BTreeSet<String>> {
+        self.state.read().missing_keys.clone()
+    }
+
+    pub fn clear_missing_keys(&self) {
+        self.state.write().missing_keys.clear();
+    }
+
+    /// Writes the session's missing-key log as JSON, mapping each language
+    /// to the sorted keys that had no translation, for handing to
+    /// translators.
+    pub fn export_missing_keys(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        let missing = self.missing_keys();
+        let json = serde_json::to_string_pretty(&missing)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The manager is a process-wide singleton, so tests that change the
+    /// current language must not run concurrently. Shared with
+    /// [`crate::test_utils::with_locale`], which serializes on it too.
+    use crate::test_utils::GLOBAL_MANAGER_LOCK as TEST_LOCK;
+
+    #[test]
+    fn deprecated_keys_resolve_through_aliases_in_both_directions() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        // A pack built before the rename still registers the old name.
+        manager.register_translations(
+            "alias-test-pack",
+            "zz-alias-test",
+            [("i18n.menu.other.settings".to_string(), "设置".to_string())],
+        );
+        assert_eq!(
+            manager.get_text_in_lang("zz-alias-test", "i18n.menu.zed.open_settings"),
+            "设置"
+        );
+        // A call site that still uses the old name finds it too.
+        assert_eq!(
+            manager.get_text_in_lang("zz-alias-test", "i18n.menu.other.settings"),
+            "设置"
+        );
+        manager.unregister_source("alias-test-pack");
+    }
+
+    #[test]
+    fn translators_are_collected_per_language_without_duplicates() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "credits-pack-a",
+            "zz-credits-test",
+            [("i18n.menu.file.save".to_string(), "a".to_string())],
+        );
+        manager.register_translations(
+            "credits-pack-b",
+            "zz-credits-test",
+            [("i18n.menu.file.open".to_string(), "b".to_string())],
+        );
+        manager.set_translators(
+            "credits-pack-a",
+            "zz-credits-test",
+            vec!["lin".to_string(), "sol".to_string()],
+        );
+        manager.set_translators(
+            "credits-pack-b",
+            "zz-credits-test",
+            vec!["sol".to_string(), "kim".to_string()],
+        );
+        // Setting credits for an unregistered source is a no-op.
+        manager.set_translators("credits-pack-c", "zz-credits-test", vec!["x".to_string()]);
+
+        assert_eq!(
+            manager.translators("zz-credits-test"),
+            vec!["lin".to_string(), "sol".to_string(), "kim".to_string()]
+        );
+        assert_eq!(manager.translators("zz-credits-none"), Vec::<String>::new());
+
+        manager.unregister_source("credits-pack-a");
+        manager.unregister_source("credits-pack-b");
+    }
+
+    #[test]
+    fn lookup_falls_back_to_defaults_and_key() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "test-pack",
+            "zz-manager-test",
+            [("i18n.menu.file.save".to_string(), "translated".to_string())],
+        );
+        manager.set_current_language("zz-manager-test");
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "translated");
+        assert_eq!(manager.get_text("i18n.menu.file.title"), "File");
+        assert_eq!(manager.get_text("i18n.bogus.key"), "i18n.bogus.key");
+
+        // The interned-key path resolves identically.
+        assert_eq!(
+            manager.get_text_keyed(crate::i18n_key!("i18n.menu.file.save")),
+            "translated"
+        );
+        assert_eq!(
+            manager.get_text_keyed(crate::i18n_key!("i18n.menu.file.title")),
+            "File"
+        );
+        assert_eq!(
+            manager.get_text_keyed(crate::i18n_key!("i18n.bogus.key")),
+            "i18n.bogus.key"
+        );
+
+        // Inline defaults apply only until the reference table defines the
+        // key; afterwards the canonical text wins.
+        assert_eq!(
+            crate::t_default!("i18n.editor.new_thing", "New Thing"),
+            "New Thing"
+        );
+        assert_eq!(crate::t_default!("i18n.menu.file.title", "Wrong"), "File");
+        assert_eq!(crate::a11y_label!("close_button"), "Close");
+
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        assert_eq!(
+            manager.get_text_in_lang("zz-manager-test", "i18n.menu.file.save"),
+            "translated"
+        );
+        manager.set_current_language("zz-manager-test");
+        assert_eq!(
+            manager.get_texts(["i18n.menu.file.save", "i18n.menu.file.title"]),
+            vec!["translated".to_string(), "File".to_string()]
+        );
+        manager.unregister_source("test-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn effective_translations_resolve_overrides_sources_and_defaults() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "effective-test-pack",
+            "zz-effective-test",
+            [
+                ("i18n.menu.file.save".to_string(), "保存".to_string()),
+                ("i18n.ext.some_ext.hello".to_string(), "你好".to_string()),
+            ],
+        );
+
+        let effective = manager.effective_translations("zz-effective-test");
+        assert_eq!(
+            effective.get("i18n.menu.file.save").map(String::as_str),
+            Some("保存")
+        );
+        // Untranslated reference keys resolve to the English default…
+        assert_eq!(
+            effective.get("i18n.menu.file.title").map(String::as_str),
+            Some("File")
+        );
+        // …and keys beyond the reference set ride along.
+        assert_eq!(
+            effective.get("i18n.ext.some_ext.hello").map(String::as_str),
+            Some("你好")
+        );
+        assert_eq!(effective.len(), crate::defaults::DEFAULT_TEXTS.len() + 1);
+
+        // Coverage counts only reference keys, and English is always full.
+        assert_eq!(
+            manager.translated_reference_key_count("zz-effective-test"),
+            1
+        );
+        assert_eq!(
+            manager.translated_reference_key_count(DEFAULT_LANGUAGE),
+            crate::defaults::DEFAULT_TEXTS.len()
+        );
+        let expected = 1.0 / crate::defaults::DEFAULT_TEXTS.len() as f32;
+        assert!((manager.coverage("zz-effective-test") - expected).abs() < f32::EPSILON);
+        assert!((manager.coverage(DEFAULT_LANGUAGE) - 1.0).abs() < f32::EPSILON);
+
+        manager.unregister_source("effective-test-pack");
+    }
+
+    #[test]
+    fn key_overlay_annotates_translations_and_defaults_but_not_unknown_keys() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "overlay-test-pack",
+            "zz-overlay-test",
+            [("i18n.menu.file.save".to_string(), "保存".to_string())],
+        );
+        manager.set_current_language("zz-overlay-test");
+        manager.set_key_overlay(true);
+
+        assert_eq!(
+            manager.get_text("i18n.menu.file.save"),
+            "保存 ⟦i18n.menu.file.save⟧"
+        );
+        assert_eq!(
+            manager.get_text("i18n.menu.file.title"),
+            "File ⟦i18n.menu.file.title⟧"
+        );
+        // An unknown key already renders as itself; annotating would just
+        // repeat it.
+        assert_eq!(manager.get_text("i18n.bogus.key"), "i18n.bogus.key");
+        assert_eq!(
+            manager.get_texts(["i18n.menu.file.save"]),
+            vec!["保存 ⟦i18n.menu.file.save⟧".to_string()]
+        );
+        // Tooling lookups stay raw.
+        assert_eq!(
+            manager.get_text_in_lang("zz-overlay-test", "i18n.menu.file.save"),
+            "保存"
+        );
+
+        manager.set_key_overlay(false);
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "保存");
+
+        manager.unregister_source("overlay-test-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn sparse_packs_resolve_through_their_parent_chain() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "parent-pack",
+            "zz-parent-test",
+            [
+                ("i18n.menu.file.save".to_string(), "parent save".to_string()),
+                ("i18n.menu.file.open".to_string(), "parent open".to_string()),
+            ],
+        );
+        manager.register_translations(
+            "child-pack",
+            "zz-child-test",
+            [("i18n.menu.file.save".to_string(), "child save".to_string())],
+        );
+        manager.set_language_parent("zz-child-test", Some("zz-parent-test".to_string()));
+        manager.set_current_language("zz-child-test");
+
+        // The child's own key wins; the rest resolve through the parent.
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "child save");
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "parent open");
+        // Inherited keys count as covered.
+        assert_eq!(manager.translated_reference_key_count("zz-child-test"), 2);
+
+        manager.set_language_parent("zz-child-test", None);
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "Open…");
+
+        manager.unregister_source("parent-pack");
+        manager.unregister_source("child-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    #[test]
+    fn cache_stats_count_hits_misses_and_evictions() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "cache-pack",
+            "zz-cache-test",
+            [
+                ("i18n.menu.file.save".to_string(), "cached save".to_string()),
+                ("i18n.menu.file.open".to_string(), "cached open".to_string()),
+                ("i18n.menu.file.new".to_string(), "cached new".to_string()),
+            ],
+        );
+        manager.set_current_language("zz-cache-test");
+        manager.set_cache_capacity(2);
+        let before = manager.cache_stats();
+        assert_eq!(before.size, 0);
+
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "cached save");
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "cached save");
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "cached open");
+        // The third distinct key pushes the cache past capacity, so one
+        // entry is evicted.
+        assert_eq!(manager.get_text("i18n.menu.file.new"), "cached new");
+
+        let after = manager.cache_stats();
+        assert_eq!(after.hits - before.hits, 1);
+        assert_eq!(after.misses - before.misses, 3);
+        assert_eq!(after.evictions - before.evictions, 1);
+        assert_eq!(after.size, 2);
+        assert_eq!(after.capacity, 2);
+
+        manager.set_cache_capacity(DEFAULT_TRANSLATION_CACHE_CAPACITY);
+        manager.unregister_source("cache-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    #[test]
+    fn concurrent_lookups_during_language_switches() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "stress-pack-a",
+            "zz-stress-a-test",
+            (0..32).map(|n| (format!("i18n.stress.key{n}"), format!("a{n}"))),
+        );
+        manager.register_translations(
+            "stress-pack-b",
+            "zz-stress-b-test",
+            (0..32).map(|n| (format!("i18n.stress.key{n}"), format!("b{n}"))),
+        );
+        manager.set_current_language("zz-stress-a-test");
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for round in 0..500 {
+                        let n = round % 32;
+                        let key = format!("i18n.stress.key{n}");
+                        let text = manager.get_text(&key);
+                        // Whichever switch a lookup races against, it sees a
+                        // fully published table — never a mixed or empty
+                        // state.
+                        assert!(
+                            text == format!("a{n}").as_str()
+                                || text == format!("b{n}").as_str(),
+                            "unexpected text {text:?} for {key}"
+                        );
+                    }
+                });
+            }
+            scope.spawn(|| {
+                for round in 0..200 {
+                    let language = if round % 2 == 0 {
+                        "zz-stress-b-test"
+                    } else {
+                        "zz-stress-a-test"
+                    };
+                    manager.switch_language(language, None);
+                }
+            });
+        });
+
+        manager.unregister_source("stress-pack-a");
+        manager.unregister_source("stress-pack-b");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    #[test]
+    fn identical_values_share_one_allocation() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "intern-pack-a",
+            "zz-intern-a-test",
+            [
+                ("i18n.menu.file.save".to_string(), "Enregistrer".to_string()),
+                ("i18n.menu.file.open".to_string(), "Open…".to_string()),
+            ],
+        );
+        manager.register_translations(
+            "intern-pack-b",
+            "zz-intern-b-test",
+            [("i18n.menu.file.save".to_string(), "Enregistrer".to_string())],
+        );
+
+        // The same value registered by two packs resolves to one allocation…
+        let a = manager.get_text_in_lang("zz-intern-a-test", "i18n.menu.file.save");
+        let b = manager.get_text_in_lang("zz-intern-b-test", "i18n.menu.file.save");
+        assert_eq!(a, "Enregistrer");
+        assert!(std::ptr::eq(a.as_ptr(), b.as_ptr()));
+        // …and a value equal to its key's English default costs no heap.
+        let untranslated = manager.get_text_in_lang("zz-intern-a-test", "i18n.menu.file.open");
+        assert!(std::ptr::eq(
+            untranslated.as_ptr(),
+            crate::defaults::default_text("i18n.menu.file.open")
+                .map(str::as_ptr)
+                .unwrap_or(std::ptr::null())
+        ));
+
+        manager.unregister_source("intern-pack-a");
+        manager.unregister_source("intern-pack-b");
+    }
+
+    #[test]
+    fn raw_keys_mode_renders_every_lookup_as_its_key() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "raw-keys-pack",
+            "zz-raw-keys-test",
+            [("i18n.menu.file.save".to_string(), "raw save".to_string())],
+        );
+        manager.set_current_language("zz-raw-keys-test");
+        manager.set_raw_keys(true);
+
+        // Translated, defaulted, and stored lookups all render the key…
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "⟦i18n.menu.file.save⟧");
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "⟦i18n.menu.file.open⟧");
+        assert_eq!(
+            TranslatedString::new("i18n.menu.file.new").resolve(),
+            "⟦i18n.menu.file.new⟧"
+        );
+        // …while a key nobody registered still renders as itself, unmarked.
+        assert_eq!(manager.get_text("i18n.unknown.key"), "i18n.unknown.key");
+
+        manager.set_raw_keys(false);
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "raw save");
+
+        manager.unregister_source("raw-keys-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    #[test]
+    fn configured_fallback_languages_are_tried_before_english() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "chain-primary-pack",
+            "zz-chain-primary-test",
+            [("i18n.menu.file.open".to_string(), "primary open".to_string())],
+        );
+        manager.register_translations(
+            "chain-fallback-pack",
+            "zz-chain-fallback-test",
+            [("i18n.menu.file.save".to_string(), "fallback save".to_string())],
+        );
+        manager.set_fallback_languages(vec!["zz-chain-fallback-test".to_string()]);
+        manager.set_current_language("zz-chain-primary-test");
+
+        // The requested language's own key wins…
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "primary open");
+        // …a miss there continues through the configured chain…
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "fallback save");
+        // …and a miss everywhere still lands on the English default.
+        assert_eq!(manager.get_text("i18n.menu.file.new"), "New");
+
+        manager.set_fallback_languages(Vec::new());
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "Save");
+
+        manager.unregister_source("chain-primary-pack");
+        manager.unregister_source("chain-fallback-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    #[test]
+    fn registered_english_backs_fill_keys_outside_the_reference_table() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        // An extension registers its own English strings…
+        manager.register_translations(
+            "backfill-ext",
+            DEFAULT_LANGUAGE,
+            [("i18n.ext.backfill_ext.hello".to_string(), "Hello".to_string())],
+        );
+        // …and a pack translates only part of them.
+        manager.register_translations(
+            "backfill-pack",
+            "zz-backfill-test",
+            [("i18n.menu.file.save".to_string(), "保存".to_string())],
+        );
+        manager.set_current_language("zz-backfill-test");
+
+        // The untranslated extension key shows its English, not the raw key.
+        assert_eq!(manager.get_text("i18n.ext.backfill_ext.hello"), "Hello");
+        assert_eq!(
+            manager.get_texts(["i18n.ext.backfill_ext.hello"]),
+            vec!["Hello".to_string()]
+        );
+        assert_eq!(
+            manager.get_text_in_lang("zz-backfill-test", "i18n.ext.backfill_ext.hello"),
+            "Hello"
+        );
+        // A miss is still a miss for the coverage log.
+        assert!(
+            manager.missing_keys()["zz-backfill-test"].contains("i18n.ext.backfill_ext.hello")
+        );
+
+        manager.unregister_source("backfill-ext");
+        manager.unregister_source("backfill-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    #[test]
+    fn platform_variants_win_over_the_bare_key_on_their_platform_only() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        let other_platform = crate::keys::PLATFORM_SUFFIXES
+            .iter()
+            .find(|platform| **platform != crate::keys::CURRENT_PLATFORM)
+            .unwrap();
+        manager.register_translations(
+            "platform-test-pack",
+            "zz-platform-test",
+            [
+                ("i18n.menu.file.open".to_string(), "打开…".to_string()),
+                (
+                    format!("i18n.menu.file.open@{}", crate::keys::CURRENT_PLATFORM),
+                    "本平台打开…".to_string(),
+                ),
+                (
+                    format!("i18n.menu.file.save@{other_platform}"),
+                    "别处保存".to_string(),
+                ),
+            ],
+        );
+        manager.set_current_language("zz-platform-test");
+
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "本平台打开…");
+        // A variant for another platform never applies here.
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "Save");
+
+        manager.unregister_source("platform-test-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn switching_languages_publishes_the_pack_and_clears_its_missing_log() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.clear_missing_keys();
+        manager.set_current_language("zz-switch-test");
+        // A miss recorded before the pack arrives…
+        manager.get_text("i18n.menu.file.save");
+        assert!(manager.missing_keys().contains_key("zz-switch-test"));
+
+        manager.switch_language(
+            "zz-switch-test",
+            Some((
+                "switch-test-pack".to_string(),
+                vec![("i18n.menu.file.save".to_string(), "保存".to_string())],
+                vec!["lin".to_string()],
+            )),
+        );
+        // …is dropped once the switch publishes the pack's strings.
+        assert!(!manager.missing_keys().contains_key("zz-switch-test"));
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "保存");
+        assert_eq!(
+            manager.translators("zz-switch-test"),
+            vec!["lin".to_string()]
+        );
+
+        manager.switch_language(DEFAULT_LANGUAGE, None);
+        assert_eq!(manager.current_language(), DEFAULT_LANGUAGE);
+        manager.unregister_source("switch-test-pack");
+    }
+
+    #[test]
+    fn missing_lookups_are_logged_once_per_key() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.clear_missing_keys();
+        manager.set_current_language("zz-missing-test");
+
+        manager.get_text("i18n.menu.file.save");
+        manager.get_text("i18n.menu.file.save");
+        manager.get_text("i18n.menu.file.open");
+
+        let missing = manager.missing_keys();
+        let keys: Vec<_> = missing["zz-missing-test"].iter().cloned().collect();
+        assert_eq!(keys, vec!["i18n.menu.file.open", "i18n.menu.file.save"]);
+
+        manager.clear_missing_keys();
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn translated_strings_re_resolve_after_a_language_switch() {
+        let mut translations = FakeTranslations::new("zz-stored-test");
+        translations.insert("zz-stored-test", "i18n.menu.file.save", "保存 {name}");
+
+        let label = TranslatedString::new("i18n.menu.file.save").with_arg("name", "a.txt");
+        assert_eq!(label.resolve_with(&translations), "保存 a.txt");
+
+        // The same stored value follows the language without being rebuilt.
+        translations.language = DEFAULT_LANGUAGE.to_string();
+        assert_eq!(label.resolve_with(&translations), "Save");
+    }
+
+    #[test]
+    fn numeric_placeholders_shape_with_the_active_number_format() {
+        let mut translations = FakeTranslations::new("zz-digits-test");
+        translations.insert("zz-digits-test", "i18n.a.b.c", "Count: {count}");
+        translations.number_format = Some(crate::pack::NumberFormat {
+            digits: Some("٠١٢٣٤٥٦٧٨٩".to_string()),
+            grouping: crate::pack::DigitGrouping::Western,
+            group_separator: "٬".to_string(),
+        });
+
+        let label = TranslatedString::new("i18n.a.b.c").with_number("count", 1234567);
+        assert_eq!(label.resolve_with(&translations), "Count: ١٬٢٣٤٬٥٦٧");
+
+        // Keys on the opt-out list keep plain ASCII digits: line and column
+        // numbers get pasted into terminals and other tools.
+        translations.insert(
+            "zz-digits-test",
+            "i18n.status.cursor_position",
+            "سطر {line}، عمود {column}",
+        );
+        let label = TranslatedString::new("i18n.status.cursor_position")
+            .with_number("line", 1234)
+            .with_number("column", 7);
+        assert_eq!(label.resolve_with(&translations), "سطر 1234، عمود 7");
+    }
+
+    #[test]
+    fn number_formats_follow_the_parent_chain() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        let parent_format = crate::pack::NumberFormat {
+            digits: None,
+            grouping: crate::pack::DigitGrouping::Indian,
+            group_separator: ",".to_string(),
+        };
+        manager.set_number_format("zz-digits-parent-test", Some(parent_format.clone()));
+        manager.set_language_parent(
+            "zz-digits-child-test",
+            Some("zz-digits-parent-test".to_string()),
+        );
+        assert_eq!(
+            manager.number_format_in_lang("zz-digits-child-test"),
+            Some(parent_format.clone())
+        );
+
+        // The child's own declaration wins over the inherited one.
+        let child_format = crate::pack::NumberFormat {
+            grouping: crate::pack::DigitGrouping::None,
+            ..parent_format
+        };
+        manager.set_number_format("zz-digits-child-test", Some(child_format.clone()));
+        assert_eq!(
+            manager.number_format_in_lang("zz-digits-child-test"),
+            Some(child_format)
+        );
+
+        manager.set_number_format("zz-digits-child-test", None);
+        manager.set_number_format("zz-digits-parent-test", None);
+        manager.set_language_parent("zz-digits-child-test", None);
+        assert_eq!(manager.number_format_in_lang("zz-digits-child-test"), None);
+    }
+
+    #[test]
+    fn font_hints_follow_the_parent_chain() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        let parent_hints = crate::pack::FontHints {
+            preferred_fonts: vec!["Noto Sans Thai".to_string()],
+            requires_cjk_fallback: false,
+        };
+        manager.set_font_hints("zz-fonts-parent-test", Some(parent_hints.clone()));
+        manager.set_language_parent(
+            "zz-fonts-child-test",
+            Some("zz-fonts-parent-test".to_string()),
+        );
+        assert_eq!(
+            manager.font_hints_in_lang("zz-fonts-child-test"),
+            Some(parent_hints.clone())
+        );
+
+        // The child's own declaration wins over the inherited one.
+        let child_hints = crate::pack::FontHints {
+            requires_cjk_fallback: true,
+            ..parent_hints
+        };
+        manager.set_font_hints("zz-fonts-child-test", Some(child_hints.clone()));
+        assert_eq!(
+            manager.font_hints_in_lang("zz-fonts-child-test"),
+            Some(child_hints)
+        );
+
+        manager.set_font_hints("zz-fonts-child-test", None);
+        manager.set_font_hints("zz-fonts-parent-test", None);
+        manager.set_language_parent("zz-fonts-child-test", None);
+        assert_eq!(manager.font_hints_in_lang("zz-fonts-child-test"), None);
+    }
+
+    #[test]
+    fn translated_text_skips_the_english_fallback() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "schema-text-test-pack",
+            "zz-schema-text-test",
+            [(
+                "i18n.settings_schema.theme".to_string(),
+                "界面主题。".to_string(),
+            )],
+        );
+        manager.set_current_language("zz-schema-text-test");
+
+        assert_eq!(
+            manager
+                .translated_text("i18n.settings_schema.theme")
+                .as_deref(),
+            Some("界面主题。")
+        );
+        // A key the pack doesn't cover reports `None` even though `get_text`
+        // would fall back to the English reference string.
+        assert_eq!(manager.translated_text("i18n.menu.file.save"), None);
+
+        manager.unregister_source("schema-text-test-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn docs_links_follow_the_parent_chain() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        let parent_links = crate::pack::DocsLinks {
+            base_url: Some("https://zed.dev/zh/docs".to_string()),
+            overrides: Default::default(),
+        };
+        manager.set_docs_links("zz-docs-parent-test", Some(parent_links.clone()));
+        manager.set_language_parent(
+            "zz-docs-child-test",
+            Some("zz-docs-parent-test".to_string()),
+        );
+        assert_eq!(
+            manager.docs_links_in_lang("zz-docs-child-test"),
+            Some(parent_links.clone())
+        );
+
+        // The child's own declaration wins over the inherited one.
+        let child_links = crate::pack::DocsLinks {
+            base_url: Some("https://zed.dev/zh-tw/docs".to_string()),
+            ..parent_links
+        };
+        manager.set_docs_links("zz-docs-child-test", Some(child_links.clone()));
+        assert_eq!(
+            manager.docs_links_in_lang("zz-docs-child-test"),
+            Some(child_links)
+        );
+
+        manager.set_docs_links("zz-docs-child-test", None);
+        manager.set_docs_links("zz-docs-parent-test", None);
+        manager.set_language_parent("zz-docs-child-test", None);
+        assert_eq!(manager.docs_links_in_lang("zz-docs-child-test"), None);
+    }
+
+    #[test]
+    fn user_overrides_win_over_registered_sources() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "override-test-pack",
+            "zz-override-test",
+            [("i18n.menu.file.save".to_string(), "from pack".to_string())],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translations.json");
+        std::fs::write(
+            &path,
+            r#"{"zz-override-test": {"i18n.menu.file.save": "from user"}}"#,
+        )
+        .unwrap();
+        manager.load_user_overrides(&path).unwrap();
+        manager.set_current_language("zz-override-test");
+
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "from user");
+
+        manager
+            .load_user_overrides(&dir.path().join("missing.json"))
+            .unwrap();
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "from pack");
+
+        manager.unregister_source("override-test-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn unregistering_a_source_removes_exactly_its_strings() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "pack-a",
+            "zz-unregister-test",
+            [("i18n.menu.file.save".to_string(), "from a".to_string())],
+        );
+        manager.register_translations(
+            "pack-b",
+            "zz-unregister-test",
+            [
+                ("i18n.menu.file.save".to_string(), "from b".to_string()),
+                ("i18n.menu.file.open".to_string(), "open b".to_string()),
+            ],
+        );
+        manager.set_current_language("zz-unregister-test");
+
+        // The most recently registered source wins on conflicts…
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "from b");
+        // …and removing it re-exposes the earlier source.
+        manager.unregister_source("pack-b");
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "from a");
+        assert_eq!(manager.get_text("i18n.menu.file.open"), "Open…");
+
+        manager.unregister_source("pack-a");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn dev_translations_outrank_registered_sources() {
+        let _guard = TEST_LOCK.lock();
+        let manager = I18nManager::global();
+        manager.register_translations(
+            "installed-pack",
+            "zz-dev-test",
+            [("i18n.menu.file.save".to_string(), "pack save".to_string())],
+        );
+        manager.set_current_language("zz-dev-test");
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "pack save");
+
+        manager.set_dev_translations(
+            "zz-dev-test",
+            [("i18n.menu.file.save".to_string(), "dev save".to_string())],
+        );
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "dev save");
+        assert!(
+            manager
+                .available_languages()
+                .contains(&"zz-dev-test".to_string())
+        );
+
+        // Unsetting the dev pack re-exposes the installed pack's strings.
+        manager.clear_dev_translations();
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "pack save");
+
+        manager.unregister_source("installed-pack");
+        manager.set_current_language(DEFAULT_LANGUAGE);
+        manager.clear_missing_keys();
+    }
+
+    proptest::proptest! {
+        // These run against [`FakeTranslations`], so they don't need
+        // `TEST_LOCK` and parallel proptest cases can't interfere.
+        #[test]
+        fn placeholders_substitute_exactly_for_brace_free_values(
+            name in "[a-z][a-z0-9_]{0,8}",
+            (prefix, middle, suffix) in ("[^{}]{0,20}", "[^{}]{0,20}", "[^{}]{0,20}"),
+            (first, second) in ("[^{}]{0,20}", "[^{}]{0,20}"),
+        ) {
+            // A distinct second name that can't overlap the first, even
+            // when the generated names share a prefix.
+            let other = format!("{name}x");
+            let mut fake = FakeTranslations::new("en");
+            fake.insert(
+                "en",
+                "i18n.test.key",
+                &format!("{prefix}{{{name}}}{middle}{{{other}}}{suffix}"),
+            );
+            let resolved = TranslatedString::new("i18n.test.key")
+                .with_arg(name.clone(), first.clone())
+                .with_arg(other, second.clone())
+                .resolve_with(&fake);
+            let expected = format!("{prefix}{first}{middle}{second}{suffix}");
+            proptest::prop_assert_eq!(resolved.as_ref(), expected.as_str());
+        }
+
+        #[test]
+        fn adversarial_values_neither_break_nor_loop(
+            name in "[a-z][a-z0-9_]{0,8}",
+            text in "[^{}]{0,20}",
+            value in "\\PC{0,20}",
+        ) {
+            let mut fake = FakeTranslations::new("en");
+            // A template without placeholders ignores every argument, even
+            // ones whose values contain braces…
+            fake.insert("en", "i18n.test.key", &text);
+            let resolved = TranslatedString::new("i18n.test.key")
+                .with_arg(name.clone(), value.clone())
+                .resolve_with(&fake);
+            proptest::prop_assert_eq!(resolved.as_ref(), text.as_str());
+
+            // …and a value containing its own placeholder is substituted
+            // once, not re-expanded.
+            fake.insert("en", "i18n.test.key", &format!("{{{name}}}"));
+            let nested = format!("{{{name}}}{value}");
+            let resolved = TranslatedString::new("i18n.test.key")
+                .with_arg(name, nested.clone())
+                .resolve_with(&fake);
+            proptest::prop_assert_eq!(resolved.as_ref(), nested.as_str());
+        }
+    }
+}
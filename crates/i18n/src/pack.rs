@@ -0,0 +1,508 @@
+//! The on-disk layout and metadata of an installed language pack.
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The name of the metadata file at the root of every pack.
+pub const METADATA_FILE_NAME: &str = "metadata.json";
+
+/// The translation schema version this build reads and writes.
+///
+/// The schema version covers the pack layout, the translation file format,
+/// and the key schema. It is bumped only on incompatible changes; packs
+/// declaring a newer version than this are rejected at import time rather
+/// than misinterpreted. Version history:
+///
+/// * 1 — the initial layout.
+/// * 2 — `translation.json` carries its own `schema_version` entry, and the
+///   settings menu keys moved under `i18n.menu.zed`.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// The oldest schema version this build can still load.
+pub const MIN_SUPPORTED_SCHEMA_VERSION: u32 = 1;
+
+/// The reserved entry in `translation.json` that declares which schema the
+/// file was written against. Files without it are treated as version 1.
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// The directory a pack can ship instead of a single `translation.json`,
+/// holding one file per key area (`menu.json`, `editor.json`, …). The files
+/// are merged on load; a key appearing in more than one of them is an error.
+pub const SPLIT_TRANSLATIONS_DIR_NAME: &str = "translations";
+
+/// The reserved entry in a translation file naming its translators, as an
+/// array of strings. Extensions have no per-language manifest, so their
+/// credits travel inside the translation file itself; installed packs can
+/// use either this or [`PackMetadata::translators`].
+pub const TRANSLATORS_KEY: &str = "translators";
+
+/// The contents of a pack's `metadata.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackMetadata {
+    /// Human-readable pack name, e.g. "简体中文".
+    pub name: String,
+    /// The IETF language tag the pack provides, e.g. `zh-CN`.
+    pub language: String,
+    /// The pack's own version, as chosen by its maintainers.
+    pub version: String,
+    /// The translation schema version the pack was built against.
+    pub schema_version: u32,
+    /// The people who translated the pack, in the form its maintainers
+    /// chose to be credited — shown in the UI when the language is active.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub translators: Vec<String>,
+    /// The GitHub repository (as `owner/repo`) where missing or wrong
+    /// translations should be reported. When unset, reports go to the Zed
+    /// repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_repository: Option<String>,
+    /// The language this pack extends, for sparse regional-variant packs: an
+    /// `en-GB` pack extending `en-US` (or `pt-BR` extending `pt`) carries
+    /// only the keys that differ, and the runtime resolves the rest through
+    /// the parent's pack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// How the pack's locale writes numbers in translated text; see
+    /// [`NumberFormat`]. When unset, numeric placeholders render with ASCII
+    /// digits and no grouping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_format: Option<NumberFormat>,
+    /// Font families the pack's script renders best in, in preference
+    /// order; appended to the UI font's fallback stack while the language
+    /// is active, so scripts like Thai or Arabic don't render as tofu on
+    /// systems whose default fallbacks lack them. Families missing from the
+    /// system are skipped at resolution time, and the user's own configured
+    /// fallbacks still come first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preferred_fonts: Vec<String>,
+    /// Whether the language needs a CJK-capable fallback font. When set,
+    /// the built-in list of common CJK system families is appended after
+    /// `preferred_fonts`, so every CJK pack doesn't have to repeat the
+    /// per-platform family names.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub requires_cjk_fallback: bool,
+    /// Where the pack's maintainers host translated documentation, e.g.
+    /// `https://zed.dev/zh/docs`. While the language is active, docs links
+    /// in the UI route to the same page path under this base; pages the
+    /// translation doesn't cover can be redirected back (or anywhere else)
+    /// through `docs_overrides`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_base_url: Option<String>,
+    /// Full replacement URLs for individual docs pages, keyed by the page
+    /// path relative to the docs root (`""` for the landing page, fragments
+    /// included). An override wins over `docs_base_url`, and works without
+    /// one — a pack that has translated only a handful of pages can route
+    /// just those.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub docs_overrides: BTreeMap<String, String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// How a locale writes the numbers substituted into `{placeholder}`s, as
+/// declared in a pack's `metadata.json`. Declaring the digits directly
+/// instead of naming a numbering system keeps the runtime free of a digit
+/// registry: any system a pack author needs is ten characters away.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumberFormat {
+    /// The locale's digits for 0 through 9, in order, as a ten-character
+    /// string — e.g. `"٠١٢٣٤٥٦٧٨٩"` for Eastern Arabic numerals. Omit to
+    /// keep ASCII digits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digits: Option<String>,
+    /// How integer digits group.
+    #[serde(default)]
+    pub grouping: DigitGrouping,
+    /// The separator written between digit groups, e.g. `","`, `"."`, or
+    /// `"٬"`. Ignored when `grouping` is `"none"`.
+    #[serde(default = "default_group_separator")]
+    pub group_separator: String,
+}
+
+impl NumberFormat {
+    /// Checks the declaration is usable; a pack declaring a digit set that
+    /// isn't exactly ten characters is ignored rather than misread.
+    pub fn check(&self) -> Result<()> {
+        if let Some(digits) = &self.digits {
+            anyhow::ensure!(
+                digits.chars().count() == 10,
+                "number_format.digits must be exactly ten characters (the locale's 0–9), \
+                 got {digits:?}",
+            );
+        }
+        Ok(())
+    }
+}
+
+fn default_group_separator() -> String {
+    ",".to_string()
+}
+
+/// The font fallback hints a pack declares, combining
+/// [`PackMetadata::preferred_fonts`] and
+/// [`PackMetadata::requires_cjk_fallback`]. Published into the manager per
+/// language and consulted by the UI font setup on every frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FontHints {
+    pub preferred_fonts: Vec<String>,
+    pub requires_cjk_fallback: bool,
+}
+
+/// The documentation link routing a pack declares, combining
+/// [`PackMetadata::docs_base_url`] and [`PackMetadata::docs_overrides`].
+/// Published into the manager per language and consulted whenever the UI
+/// opens a docs URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocsLinks {
+    pub base_url: Option<String>,
+    pub overrides: BTreeMap<String, String>,
+}
+
+impl DocsLinks {
+    /// The localized URL for the docs page at `page` — a path relative to
+    /// the docs root, `""` for the landing page, fragment included — or
+    /// `None` when neither an override nor the base URL covers it.
+    pub fn resolve(&self, page: &str) -> Option<String> {
+        if let Some(url) = self.overrides.get(page) {
+            return Some(url.clone());
+        }
+        let base = self.base_url.as_deref()?.trim_end_matches('/');
+        if page.is_empty() {
+            Some(base.to_string())
+        } else if page.starts_with('#') {
+            Some(format!("{base}{page}"))
+        } else {
+            Some(format!("{base}/{page}"))
+        }
+    }
+}
+
+/// The digit-grouping system of a [`NumberFormat`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigitGrouping {
+    /// Groups of three: 1,234,567.
+    #[default]
+    Western,
+    /// The Indian system — the last three digits, then groups of two:
+    /// 12,34,567.
+    Indian,
+    None,
+}
+
+impl PackMetadata {
+    /// The font fallback hints this pack declares, or `None` when it
+    /// declares nothing — so a sparse variant pack inherits its parent's
+    /// hints instead of clearing them.
+    pub fn font_hints(&self) -> Option<FontHints> {
+        if self.preferred_fonts.is_empty() && !self.requires_cjk_fallback {
+            return None;
+        }
+        Some(FontHints {
+            preferred_fonts: self.preferred_fonts.clone(),
+            requires_cjk_fallback: self.requires_cjk_fallback,
+        })
+    }
+
+    /// The documentation link routing this pack declares, or `None` when it
+    /// declares nothing — so a sparse variant pack inherits its parent's
+    /// routing instead of clearing it.
+    pub fn docs_links(&self) -> Option<DocsLinks> {
+        if self.docs_base_url.is_none() && self.docs_overrides.is_empty() {
+            return None;
+        }
+        Some(DocsLinks {
+            base_url: self.docs_base_url.clone(),
+            overrides: self.docs_overrides.clone(),
+        })
+    }
+
+    pub fn load(pack_dir: &Path) -> Result<Self> {
+        let path = pack_dir.join(METADATA_FILE_NAME);
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("language pack has no {METADATA_FILE_NAME}"))?;
+        let metadata: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        Ok(metadata)
+    }
+
+    /// Checks that this pack's schema version is one this build understands.
+    pub fn check_schema_compatibility(&self) -> Result<()> {
+        anyhow::ensure!(
+            self.schema_version >= MIN_SUPPORTED_SCHEMA_VERSION,
+            "language pack {} uses translation schema version {}, which is no longer \
+             supported (oldest supported: {MIN_SUPPORTED_SCHEMA_VERSION}); ask the pack's \
+             maintainers for an updated release",
+            self.name,
+            self.schema_version,
+        );
+        anyhow::ensure!(
+            self.schema_version <= CURRENT_SCHEMA_VERSION,
+            "language pack {} uses translation schema version {}, but this build of Zed \
+             only supports up to {CURRENT_SCHEMA_VERSION}; update Zed to use this pack",
+            self.name,
+            self.schema_version,
+        );
+        Ok(())
+    }
+}
+
+/// The name of the optional review-tracking sidecar next to
+/// `translation.json`.
+pub const REVIEW_METADATA_FILE_NAME: &str = "translation.meta.json";
+
+/// How far a translated entry has progressed through a language team's
+/// review process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReviewStatus {
+    /// Produced by machine translation and never touched by a human.
+    MachineTranslated,
+    /// Written or edited by a human, but not yet signed off by a reviewer.
+    NeedsReview,
+    Reviewed,
+}
+
+impl ReviewStatus {
+    /// The kebab-case form used on disk and in human-readable output.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MachineTranslated => "machine-translated",
+            Self::NeedsReview => "needs-review",
+            Self::Reviewed => "reviewed",
+        }
+    }
+}
+
+/// The review state of a single translation entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewEntry {
+    pub status: ReviewStatus,
+    /// Who signed off on (or is assigned to) the entry, in whatever form
+    /// the language team uses — a name, a handle, an email address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewer: Option<String>,
+}
+
+/// The contents of a pack's optional `translation.meta.json`: a flat map
+/// from translation key to review state. Keys absent from the sidecar have
+/// no tracked state; an absent sidecar disables review tracking entirely,
+/// so packs from solo translators don't drown in warnings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReviewMetadata {
+    pub entries: std::collections::BTreeMap<String, ReviewEntry>,
+}
+
+impl ReviewMetadata {
+    /// Accepts comments and trailing commas, like `translation.json` itself.
+    pub fn parse(contents: &str) -> Result<Self> {
+        serde_json_lenient::from_str(contents).context("failed to parse review metadata")
+    }
+
+    /// Loads the sidecar from a pack directory, returning `None` when the
+    /// pack doesn't track review state.
+    pub fn load(pack_dir: &Path) -> Result<Option<Self>> {
+        let path = pack_dir.join(REVIEW_METADATA_FILE_NAME);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(error).with_context(|| format!("failed to read {}", path.display()));
+            }
+        };
+        Ok(Some(
+            Self::parse(&contents).with_context(|| format!("in {}", path.display()))?,
+        ))
+    }
+
+    pub fn entry(&self, key: &str) -> Option<&ReviewEntry> {
+        self.entries.get(key)
+    }
+}
+
+/// A key-schema migration from `from` to `from + 1`.
+pub struct SchemaMigration {
+    pub from: u32,
+    /// Key renames this migration applies.
+    pub renames: &'static [(&'static str, &'static str)],
+}
+
+/// Every migration between [`MIN_SUPPORTED_SCHEMA_VERSION`] and
+/// [`CURRENT_SCHEMA_VERSION`], in order. The runtime applies these
+/// best-effort when loading an old file; `zed-i18n migrate` applies them
+/// permanently.
+pub static SCHEMA_MIGRATIONS: &[SchemaMigration] = &[SchemaMigration {
+    from: 1,
+    // The version 2 renames are exactly the deprecation aliases.
+    renames: crate::defaults::KEY_ALIASES,
+}];
+
+/// Brings entries parsed from a schema-`from_version` translation file up to
+/// the current key schema, returning the number of keys renamed. A rename
+/// whose target already exists drops the old entry rather than clobbering
+/// the newer one.
+pub fn migrate_entries(
+    entries: &mut serde_json::Map<String, serde_json::Value>,
+    from_version: u32,
+) -> usize {
+    let mut renamed = 0;
+    for migration in SCHEMA_MIGRATIONS {
+        if migration.from < from_version {
+            continue;
+        }
+        for (old, new) in migration.renames {
+            if let Some(value) = entries.remove(*old) {
+                if !entries.contains_key(*new) {
+                    entries.insert((*new).to_string(), value);
+                }
+                renamed += 1;
+            }
+        }
+    }
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(schema_version: u32) -> PackMetadata {
+        PackMetadata {
+            name: "简体中文".to_string(),
+            language: "zh-CN".to_string(),
+            version: "1.0.0".to_string(),
+            schema_version,
+            translators: Vec::new(),
+            issue_repository: None,
+            extends: None,
+            number_format: None,
+            preferred_fonts: Vec::new(),
+            requires_cjk_fallback: false,
+            docs_base_url: None,
+            docs_overrides: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn schema_gate() {
+        metadata(CURRENT_SCHEMA_VERSION)
+            .check_schema_compatibility()
+            .unwrap();
+        let error = metadata(CURRENT_SCHEMA_VERSION + 1)
+            .check_schema_compatibility()
+            .unwrap_err();
+        assert!(error.to_string().contains("update Zed"));
+        if MIN_SUPPORTED_SCHEMA_VERSION > 0 {
+            let error = metadata(MIN_SUPPORTED_SCHEMA_VERSION - 1)
+                .check_schema_compatibility()
+                .unwrap_err();
+            assert!(error.to_string().contains("no longer supported"));
+        }
+    }
+
+    #[test]
+    fn font_hints_are_absent_unless_declared() {
+        let mut metadata = metadata(CURRENT_SCHEMA_VERSION);
+        assert_eq!(metadata.font_hints(), None);
+
+        metadata.requires_cjk_fallback = true;
+        assert_eq!(
+            metadata.font_hints(),
+            Some(FontHints {
+                preferred_fonts: Vec::new(),
+                requires_cjk_fallback: true,
+            })
+        );
+    }
+
+    #[test]
+    fn docs_links_resolve_overrides_before_the_base_url() {
+        let mut metadata = metadata(CURRENT_SCHEMA_VERSION);
+        assert_eq!(metadata.docs_links(), None);
+
+        metadata.docs_base_url = Some("https://zed.dev/zh/docs/".to_string());
+        metadata.docs_overrides.insert(
+            "vim".to_string(),
+            "https://example.com/zh/vim-mode".to_string(),
+        );
+        let links = metadata.docs_links().unwrap();
+        assert_eq!(links.resolve("").as_deref(), Some("https://zed.dev/zh/docs"));
+        assert_eq!(
+            links.resolve("repl#changing-kernels").as_deref(),
+            Some("https://zed.dev/zh/docs/repl#changing-kernels")
+        );
+        assert_eq!(
+            links.resolve("vim").as_deref(),
+            Some("https://example.com/zh/vim-mode")
+        );
+
+        // Overrides alone are enough for a pack without a full translated
+        // site; everything else stays unrouted.
+        let links = DocsLinks {
+            base_url: None,
+            overrides: links.overrides,
+        };
+        assert_eq!(
+            links.resolve("vim").as_deref(),
+            Some("https://example.com/zh/vim-mode")
+        );
+        assert_eq!(links.resolve("repl"), None);
+    }
+
+    #[test]
+    fn review_metadata_round_trips_and_tolerates_comments() {
+        let metadata = ReviewMetadata::parse(
+            r#"{
+                // carried over from the machine-translation pass
+                "i18n.menu.file.save": {"status": "machine-translated"},
+                "i18n.menu.file.title": {"status": "reviewed", "reviewer": "lin"},
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.entry("i18n.menu.file.save").map(|entry| entry.status),
+            Some(ReviewStatus::MachineTranslated)
+        );
+        let reviewed = metadata.entry("i18n.menu.file.title").unwrap();
+        assert_eq!(reviewed.status, ReviewStatus::Reviewed);
+        assert_eq!(reviewed.reviewer.as_deref(), Some("lin"));
+        assert!(metadata.entry("i18n.menu.edit.copy").is_none());
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let reparsed = ReviewMetadata::parse(&json).unwrap();
+        assert_eq!(reparsed.entries, metadata.entries);
+    }
+
+    #[test]
+    fn migrations_step_forward_without_clobbering() {
+        let mut entries = serde_json::Map::new();
+        entries.insert("i18n.menu.other.settings".to_string(), "设置".into());
+        entries.insert("i18n.menu.file.save".to_string(), "保存".into());
+        assert_eq!(migrate_entries(&mut entries, 1), 1);
+        assert_eq!(
+            entries.get("i18n.menu.zed.open_settings").and_then(|v| v.as_str()),
+            Some("设置")
+        );
+        assert!(!entries.contains_key("i18n.menu.other.settings"));
+
+        // An up-to-date file is untouched.
+        let before = entries.clone();
+        assert_eq!(migrate_entries(&mut entries, CURRENT_SCHEMA_VERSION), 0);
+        assert_eq!(entries, before);
+
+        // A rename whose target exists keeps the newer entry.
+        let mut entries = serde_json::Map::new();
+        entries.insert("i18n.menu.other.settings".to_string(), "old".into());
+        entries.insert("i18n.menu.zed.open_settings".to_string(), "new".into());
+        assert_eq!(migrate_entries(&mut entries, 1), 1);
+        assert_eq!(
+            entries.get("i18n.menu.zed.open_settings").and_then(|v| v.as_str()),
+            Some("new")
+        );
+    }
+}
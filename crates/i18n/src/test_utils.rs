@@ -0,0 +1,412 @@
+//! Helpers for i18n regression tests in feature crates.
+//!
+//! Enable with the `test-support` feature in dev-dependencies:
+//!
+//! ```toml
+//! i18n = { workspace = true, features = ["test-support"] }
+//! ```
+
+use crate::manager::{DEFAULT_LANGUAGE, I18nManager};
+use std::path::Path;
+
+/// Serializes tests that mutate the process-wide [`I18nManager`], so
+/// parallel tests in one process never observe each other's languages.
+/// [`with_locale`] takes it automatically; tests that mutate the manager
+/// directly should hold it themselves.
+pub static GLOBAL_MANAGER_LOCK: parking_lot::Mutex<()> = parking_lot::Mutex::new(());
+
+/// Asserts that every `i18n.*` key the invoking crate's sources reference
+/// has an English default in the reference table.
+///
+/// Keys in the extension namespace (`i18n.ext.*`) and keys introduced
+/// through [`crate::t_default!`] carry their text elsewhere, so they are
+/// exempt. Scans the same key literals `zed-i18n scan` does, which covers
+/// every `t!`-style call site.
+#[macro_export]
+macro_rules! assert_all_t_keys_have_defaults {
+    () => {
+        $crate::test_utils::assert_source_keys_have_defaults(std::path::Path::new(env!(
+            "CARGO_MANIFEST_DIR"
+        )));
+    };
+}
+
+/// The implementation behind [`crate::assert_all_t_keys_have_defaults!`];
+/// call the macro instead so the invoking crate's manifest directory is
+/// picked up automatically.
+pub fn assert_source_keys_have_defaults(crate_root: &Path) {
+    let missing = keys_without_defaults(crate_root);
+    assert!(
+        missing.is_empty(),
+        "keys referenced in {} without an English default in the reference \
+         table: {missing:?}\nAdd them to i18n/src/defaults.rs, or use \
+         t_default! while they stabilize.",
+        crate_root.display()
+    );
+}
+
+/// The `i18n.*` keys referenced under `crate_root` that have no entry in
+/// the reference table, sorted. Empty for a crate that passes
+/// [`crate::assert_all_t_keys_have_defaults!`].
+pub fn keys_without_defaults(crate_root: &Path) -> Vec<String> {
+    let mut referenced = std::collections::BTreeSet::new();
+    let mut inline_defaults = std::collections::BTreeSet::new();
+    for entry in walkdir::WalkDir::new(crate_root.join("src")) {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_file()
+            || entry.path().extension().is_none_or(|ext| ext != "rs")
+            // The reference set itself would otherwise reference every key.
+            || entry.path().ends_with("i18n/src/defaults.rs")
+        {
+            continue;
+        }
+        let Ok(source) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        referenced.extend(extract_key_literals(&source));
+        referenced.extend(extract_a11y_call_sites(&source));
+        inline_defaults.extend(extract_inline_default_keys(&source));
+    }
+    referenced
+        .into_iter()
+        .filter(|key| {
+            !key.starts_with("i18n.ext.")
+                && !inline_defaults.contains(key)
+                && crate::defaults::default_text(key).is_none()
+        })
+        .collect()
+}
+
+/// The conforming `i18n.*` keys referenced by non-test Rust code under
+/// `root`, with platform suffixes stripped, deprecation aliases resolved,
+/// and extension-namespace keys excluded since their text lives in packs.
+/// `#[cfg(test)]` modules, fuzz targets, and this file don't count, so
+/// deliberately bogus fixture keys neither satisfy nor fail the workspace
+/// consistency test.
+pub fn keys_referenced_outside_tests(root: &Path) -> std::collections::BTreeSet<String> {
+    let mut referenced = std::collections::BTreeSet::new();
+    for entry in walkdir::WalkDir::new(root) {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        if !entry.file_type().is_file()
+            || path.extension().is_none_or(|ext| ext != "rs")
+            // The reference set itself would otherwise reference every key.
+            || path.ends_with("i18n/src/defaults.rs")
+            || path.ends_with("i18n/src/test_utils.rs")
+            || path
+                .components()
+                .any(|component| component.as_os_str() == "fuzz")
+        {
+            continue;
+        }
+        let Ok(source) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        // Tests are the trailing module of a file in this codebase, so
+        // everything from the first `#[cfg(test)]` down is fixture code.
+        let source = match source.find("#[cfg(test)]") {
+            Some(index) => &source[..index],
+            None => source.as_str(),
+        };
+        for key in extract_key_literals(source)
+            .into_iter()
+            .chain(extract_a11y_call_sites(source))
+        {
+            if key.starts_with("i18n.ext.") {
+                continue;
+            }
+            let (base, _) = crate::keys::split_platform(&key);
+            referenced.insert(crate::defaults::canonical_key(base).to_string());
+        }
+    }
+    referenced
+}
+
+/// Extracts the full keys of `a11y_label!("…")` call sites, which name only
+/// the path under `i18n.a11y.` and assemble the key with `concat!`, so
+/// [`extract_key_literals`] can't see them.
+fn extract_a11y_call_sites(source: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("a11y_label!") {
+        rest = &rest[start + "a11y_label!".len()..];
+        let Some(open) = rest.find('"') else {
+            break;
+        };
+        if !rest[..open]
+            .chars()
+            .all(|c| c.is_whitespace() || c == '(')
+        {
+            continue;
+        }
+        rest = &rest[open + 1..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        let candidate = format!("i18n.a11y.{}", &rest[..end]);
+        rest = &rest[end + 1..];
+        if crate::keys::check_key(&candidate).is_ok() {
+            keys.push(candidate);
+        }
+    }
+    keys
+}
+
+/// Extracts `i18n.`-prefixed string literals that conform to the key naming
+/// scheme, like `zed-i18n scan` does.
+fn extract_key_literals(source: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("\"i18n.") {
+        rest = &rest[start + 1..];
+        if let Some(end) = rest.find('"') {
+            let candidate = &rest[..end];
+            if crate::keys::check_key(candidate).is_ok() {
+                keys.push(candidate.to_string());
+            }
+            rest = &rest[end + 1..];
+        } else {
+            break;
+        }
+    }
+    keys
+}
+
+/// Extracts the keys of `t_default!("…", "…")` call sites, whose English
+/// text lives at the call site rather than the reference table.
+fn extract_inline_default_keys(source: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("t_default!") {
+        rest = &rest[start + "t_default!".len()..];
+        let Some(open) = rest.find('"') else {
+            break;
+        };
+        rest = &rest[open + 1..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        let candidate = &rest[..end];
+        if crate::keys::check_key(candidate).is_ok() {
+            keys.push(candidate.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    keys
+}
+
+/// Runs `test` with the process-wide manager switched to `language`, with
+/// the given translations registered for it. The previous state is restored
+/// afterwards, even when the test panics, and concurrent `with_locale`
+/// callers are serialized, so tests stay independent.
+pub fn with_locale<R>(
+    language: &str,
+    translations: &[(&str, &str)],
+    test: impl FnOnce() -> R,
+) -> R {
+    let _guard = GLOBAL_MANAGER_LOCK.lock();
+    let manager = I18nManager::global();
+    manager.register_translations(
+        "test-fixture",
+        language,
+        translations
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    manager.set_current_language(language);
+    // Restore through a drop guard so a panicking test doesn't leak its
+    // language into tests that run later in the same process.
+    struct Restore;
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let manager = I18nManager::global();
+            manager.unregister_source("test-fixture");
+            manager.set_current_language(DEFAULT_LANGUAGE);
+            manager.clear_missing_keys();
+        }
+    }
+    let _restore = Restore;
+    test()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_locale_installs_and_restores_the_language() {
+        with_locale(
+            "zz-fixture-test",
+            &[("i18n.menu.file.save", "fixture save")],
+            || {
+                let manager = I18nManager::global();
+                assert_eq!(manager.current_language(), "zz-fixture-test");
+                assert_eq!(manager.get_text("i18n.menu.file.save"), "fixture save");
+            },
+        );
+        // Re-lock before asserting, so a concurrent test can't be mid-run.
+        let _guard = GLOBAL_MANAGER_LOCK.lock();
+        let manager = I18nManager::global();
+        assert_eq!(manager.current_language(), DEFAULT_LANGUAGE);
+        assert_eq!(manager.get_text("i18n.menu.file.save"), "Save");
+    }
+
+    #[test]
+    fn with_locale_restores_after_a_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_locale("zz-panic-test", &[], || panic!("boom"));
+        });
+        assert!(result.is_err());
+        let _guard = GLOBAL_MANAGER_LOCK.lock();
+        assert_eq!(
+            I18nManager::global().current_language(),
+            DEFAULT_LANGUAGE
+        );
+    }
+
+    #[test]
+    fn workspace_usage_and_reference_table_agree() {
+        // Reference keys no non-test code refers to yet. The UI adopts
+        // translation keys incrementally: entries leave this list as call
+        // sites appear, and a new default must either ship with a call
+        // site or be added here deliberately.
+        static PENDING_ADOPTION: &[&str] = &[
+            "i18n.a11y.close_button",
+            "i18n.a11y.language_picker",
+            "i18n.a11y.menu_bar",
+            "i18n.command.copy_missing_keys_report",
+            "i18n.command.open_override_file",
+            "i18n.command.reload_translations",
+            "i18n.command.report_missing_translation",
+            "i18n.command.switch_language",
+            "i18n.dialog.cancel",
+            "i18n.dialog.dont_save",
+            "i18n.dialog.ok",
+            "i18n.dialog.overwrite",
+            "i18n.dialog.save",
+            "i18n.dialog.unsaved_changes",
+            "i18n.menu.edit.copy",
+            "i18n.menu.edit.cut",
+            "i18n.menu.edit.find",
+            "i18n.menu.edit.find_in_project",
+            "i18n.menu.edit.paste",
+            "i18n.menu.edit.redo",
+            "i18n.menu.edit.title",
+            "i18n.menu.edit.undo",
+            "i18n.menu.file.close_editor",
+            "i18n.menu.file.close_window",
+            "i18n.menu.file.new",
+            "i18n.menu.file.new_window",
+            "i18n.menu.file.open",
+            "i18n.menu.file.open_recent",
+            "i18n.menu.file.save_all",
+            "i18n.menu.file.save_as",
+            "i18n.menu.file.title",
+            "i18n.menu.go.back",
+            "i18n.menu.go.forward",
+            "i18n.menu.go.go_to_definition",
+            "i18n.menu.go.go_to_file",
+            "i18n.menu.go.go_to_line_column",
+            "i18n.menu.go.go_to_symbol_in_project",
+            "i18n.menu.go.title",
+            "i18n.menu.help.documentation",
+            "i18n.menu.help.keyboard_shortcuts",
+            "i18n.menu.help.report_issue",
+            "i18n.menu.help.title",
+            "i18n.menu.selection.select_all",
+            "i18n.menu.selection.title",
+            "i18n.menu.view.close_all_docks",
+            "i18n.menu.view.project_panel",
+            "i18n.menu.view.terminal_panel",
+            "i18n.menu.view.title",
+            "i18n.menu.view.zoom_in",
+            "i18n.menu.view.zoom_out",
+            "i18n.menu.window.minimize",
+            "i18n.menu.window.title",
+            "i18n.menu.window.zoom",
+            "i18n.menu.zed.about",
+            "i18n.menu.zed.check_for_updates",
+            "i18n.menu.zed.extensions",
+            "i18n.menu.zed.open_settings",
+            "i18n.menu.zed.quit",
+            "i18n.menu.zed.select_language",
+            "i18n.menu.zed.title",
+            "i18n.status.cursor_position",
+            "i18n.status.language_changed",
+            "i18n.status.language_pack_installed",
+            "i18n.welcome.no_project",
+            "i18n.welcome.tip_command_palette",
+        ];
+
+        let crates_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../crates");
+        let referenced = keys_referenced_outside_tests(&crates_dir);
+
+        let unknown: Vec<_> = referenced
+            .iter()
+            .filter(|key| crate::defaults::default_text(key).is_none())
+            .collect();
+        assert!(
+            unknown.is_empty(),
+            "keys referenced outside tests with no English default: {unknown:?}"
+        );
+
+        let pending: std::collections::BTreeSet<&str> =
+            PENDING_ADOPTION.iter().copied().collect();
+        for key in &pending {
+            assert!(
+                crate::defaults::default_text(key).is_some(),
+                "pending-adoption entry {key} is not a reference key"
+            );
+        }
+        let drifted: Vec<&str> = crate::defaults::DEFAULT_TEXTS
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|key| !referenced.contains(*key) && !pending.contains(key))
+            .collect();
+        assert!(
+            drifted.is_empty(),
+            "reference keys neither used by any call site nor listed as \
+             pending adoption: {drifted:?}"
+        );
+        // The ratchet's other direction: once a key gains a call site, its
+        // pending entry must go.
+        let adopted: Vec<&str> = pending
+            .iter()
+            .copied()
+            .filter(|key| referenced.contains(*key))
+            .collect();
+        assert!(
+            adopted.is_empty(),
+            "pending-adoption entries for keys that now have call sites: {adopted:?}"
+        );
+    }
+
+    #[test]
+    fn scanning_reports_keys_the_reference_table_lacks() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            concat!(
+                "fn render() {\n",
+                "    t!(\"i18n.menu.file.save\");\n",
+                "    t!(\"i18n.test_utils.not_a_real_key\");\n",
+                "    t!(\"i18n.ext.some_ext.hello\");\n",
+                "    t_default!(\"i18n.test_utils.inline_key\", \"Inline\");\n",
+                "}\n",
+            ),
+        )
+        .unwrap();
+
+        // The unknown key is reported; the reference key, the extension
+        // key, and the t_default! key are not.
+        assert_eq!(
+            keys_without_defaults(dir.path()),
+            vec!["i18n.test_utils.not_a_real_key".to_string()]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Locale-aware helpers for UI code that shortens or shapes displayed text.
+
+use crate::pack::{DigitGrouping, NumberFormat};
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation as _;
+
+/// Shortens `text` to at most `max_graphemes` grapheme clusters, appending
+/// the current language's ellipsis when anything was cut. Cutting on
+/// grapheme-cluster boundaries rather than bytes or chars keeps CJK
+/// characters, combining sequences, and emoji intact, and the result —
+/// ellipsis included — never exceeds `max_graphemes` clusters.
+///
+/// The ellipsis comes from the `i18n.text.ellipsis` reference key, so
+/// language packs can substitute their locale's convention.
+pub fn truncate(text: &str, max_graphemes: usize) -> Cow<'_, str> {
+    let ellipsis = crate::I18nManager::global().get_text("i18n.text.ellipsis");
+    truncate_with(text, max_graphemes, &ellipsis)
+}
+
+/// [`truncate`] with an explicit ellipsis, for callers that already know the
+/// suffix they want (or want none). When `max_graphemes` is too small to
+/// hold even the ellipsis, the ellipsis alone is returned rather than
+/// nothing, since a bare cut would look like the full text.
+pub fn truncate_with<'a>(text: &'a str, max_graphemes: usize, ellipsis: &str) -> Cow<'a, str> {
+    if max_graphemes == 0 {
+        return Cow::Borrowed("");
+    }
+    if text.graphemes(true).nth(max_graphemes).is_none() {
+        return Cow::Borrowed(text);
+    }
+    let keep = max_graphemes.saturating_sub(ellipsis.graphemes(true).count());
+    let boundary = text
+        .grapheme_indices(true)
+        .nth(keep)
+        .map(|(index, _)| index)
+        .unwrap_or(text.len());
+    let mut result = String::with_capacity(boundary + ellipsis.len());
+    result.push_str(&text[..boundary]);
+    result.push_str(ellipsis);
+    Cow::Owned(result)
+}
+
+/// Renders an integer the way `format` says its locale writes numbers: the
+/// locale's digit characters and grouping. With no declaration the result
+/// is plain ASCII with no grouping, matching what `format!` would produce.
+pub fn format_number(value: i64, format: Option<&NumberFormat>) -> String {
+    let Some(format) = format else {
+        return value.to_string();
+    };
+    let digit_table: Option<Vec<char>> = format
+        .digits
+        .as_deref()
+        .map(|digits| digits.chars().collect::<Vec<char>>())
+        // A malformed table would shift every digit; [`NumberFormat::check`]
+        // reports it, this just refuses to misread.
+        .filter(|table| table.len() == 10);
+    let magnitude = value.unsigned_abs().to_string();
+    let mut result = String::new();
+    if value < 0 {
+        result.push('-');
+    }
+    for (index, digit) in magnitude.bytes().enumerate() {
+        let remaining = magnitude.len() - index;
+        if index > 0 && group_boundary(remaining, format.grouping) {
+            result.push_str(&format.group_separator);
+        }
+        match &digit_table {
+            Some(table) => result.push(
+                table
+                    .get(usize::from(digit.wrapping_sub(b'0')))
+                    .copied()
+                    .unwrap_or(char::from(digit)),
+            ),
+            None => result.push(char::from(digit)),
+        }
+    }
+    result
+}
+
+/// Whether a group separator belongs before the digit with `remaining`
+/// digits (itself included) still to write.
+fn group_boundary(remaining: usize, grouping: DigitGrouping) -> bool {
+    match grouping {
+        DigitGrouping::Western => remaining % 3 == 0,
+        DigitGrouping::Indian => remaining >= 3 && (remaining - 3) % 2 == 0,
+        DigitGrouping::None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn text_that_fits_is_returned_unchanged() {
+        assert!(matches!(truncate_with("tab", 3, "…"), Cow::Borrowed("tab")));
+        assert!(matches!(truncate_with("", 1, "…"), Cow::Borrowed("")));
+        // Each CJK character is one cluster, regardless of byte length.
+        assert!(matches!(
+            truncate_with("设置", 2, "…"),
+            Cow::Borrowed("设置")
+        ));
+    }
+
+    #[test]
+    fn truncation_counts_grapheme_clusters_not_bytes_or_chars() {
+        assert_eq!(truncate_with("translation", 8, "…"), "translat…");
+        assert_eq!(truncate_with("设置语言选择", 4, "…"), "设置语…");
+        // The family emoji is seven chars but a single cluster; it is kept
+        // or dropped whole, never split mid-sequence.
+        assert_eq!(truncate_with("a👨‍👩‍👧‍👦bcd", 3, "…"), "a👨‍👩‍👧‍👦…");
+        assert_eq!(truncate_with("👨‍👩‍👧‍👦abcd", 2, "…"), "👨‍👩‍👧‍👦…");
+    }
+
+    #[test]
+    fn the_ellipsis_fits_within_the_budget() {
+        // A three-character ellipsis leaves one cluster of a four-cluster
+        // budget for the text itself.
+        assert_eq!(truncate_with("abcdefgh", 4, "..."), "a...");
+        // Too tight for the ellipsis: the ellipsis alone is returned.
+        assert_eq!(truncate_with("abcdefgh", 2, "..."), "...");
+        assert_eq!(truncate_with("abcdefgh", 0, "..."), "");
+        // No ellipsis: a plain grapheme-boundary cut.
+        assert_eq!(truncate_with("abcdefgh", 4, ""), "abcd");
+    }
+
+    #[test]
+    fn numbers_format_plainly_without_a_declaration() {
+        assert_eq!(format_number(0, None), "0");
+        assert_eq!(format_number(1234567, None), "1234567");
+        assert_eq!(format_number(-42, None), "-42");
+    }
+
+    #[test]
+    fn grouping_follows_the_declared_system() {
+        let western = NumberFormat {
+            digits: None,
+            grouping: DigitGrouping::Western,
+            group_separator: ",".to_string(),
+        };
+        assert_eq!(format_number(1234567, Some(&western)), "1,234,567");
+        assert_eq!(format_number(123, Some(&western)), "123");
+        assert_eq!(format_number(-1234, Some(&western)), "-1,234");
+
+        let indian = NumberFormat {
+            group_separator: ",".to_string(),
+            grouping: DigitGrouping::Indian,
+            digits: None,
+        };
+        assert_eq!(format_number(1234567, Some(&indian)), "12,34,567");
+        assert_eq!(format_number(123456, Some(&indian)), "1,23,456");
+        assert_eq!(format_number(123, Some(&indian)), "123");
+
+        let ungrouped = NumberFormat {
+            digits: None,
+            grouping: DigitGrouping::None,
+            group_separator: ",".to_string(),
+        };
+        assert_eq!(format_number(1234567, Some(&ungrouped)), "1234567");
+    }
+
+    #[test]
+    fn digits_map_through_the_declared_table() {
+        let eastern_arabic = NumberFormat {
+            digits: Some("٠١٢٣٤٥٦٧٨٩".to_string()),
+            grouping: DigitGrouping::Western,
+            group_separator: "٬".to_string(),
+        };
+        assert_eq!(format_number(1234567, Some(&eastern_arabic)), "١٬٢٣٤٬٥٦٧");
+        assert_eq!(format_number(-80, Some(&eastern_arabic)), "-٨٠");
+
+        // A malformed digit table (checked at load, but belt and braces)
+        // falls back to ASCII rather than shifting every digit.
+        let malformed = NumberFormat {
+            digits: Some("٠١٢".to_string()),
+            grouping: DigitGrouping::None,
+            group_separator: ",".to_string(),
+        };
+        assert_eq!(format_number(120, Some(&malformed)), "120");
+    }
+
+    #[test]
+    fn the_locale_s_ellipsis_is_used() {
+        crate::test_utils::with_locale(
+            "zz-ellipsis-test",
+            &[("i18n.text.ellipsis", "⋯")],
+            || {
+                assert_eq!(truncate("language pack", 5), "lang⋯");
+            },
+        );
+    }
+}
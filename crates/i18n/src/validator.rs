@@ -0,0 +1,1194 @@
+//! Validation of translation files against the English reference keys.
+
+use crate::TranslationFile;
+use crate::defaults::{self, SHARED_TERMS};
+use crate::keys;
+use crate::pack;
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// Which stylistic lints [`I18NValidator`] applies on top of the structural
+/// checks. All lints are on by default; tooling can switch individual lints
+/// off for languages where they don't apply.
+#[derive(Debug, Clone)]
+pub struct ValidatorOptions {
+    /// Flag values with leading or trailing whitespace.
+    pub lint_trailing_whitespace: bool,
+    /// Flag values containing two or more consecutive spaces.
+    pub lint_double_spaces: bool,
+    /// Flag values whose trailing ellipsis or colon disagrees with the
+    /// English default.
+    pub lint_trailing_punctuation: bool,
+    /// Flag ASCII `...` where `…` is expected, and half-width `?`/`!`/`:` at
+    /// the end of CJK translations where full-width forms are conventional.
+    pub lint_punctuation_width: bool,
+    /// Flag keys that share identical English text but carry different
+    /// recorded contexts, yet were given the same translation.
+    pub lint_shared_translations: bool,
+    /// Flag values carrying the double-encoding signature of UTF-8 text
+    /// read as Latin-1 (`Ã©` for `é`). Skipped for CJK languages, whose
+    /// scripts can't produce the signature by accident anyway.
+    pub lint_mojibake: bool,
+    /// For keys in [`defaults::MARKDOWN_KEYS`], flag translations whose
+    /// Markdown structure (link targets, code spans, list markers) doesn't
+    /// match the English default's.
+    pub lint_markdown_structure: bool,
+    /// When review metadata is supplied, flag entries whose recorded status
+    /// is anything other than `reviewed`.
+    pub warn_on_unreviewed: bool,
+}
+
+impl Default for ValidatorOptions {
+    fn default() -> Self {
+        Self {
+            lint_trailing_whitespace: true,
+            lint_double_spaces: true,
+            lint_trailing_punctuation: true,
+            lint_punctuation_width: true,
+            lint_shared_translations: true,
+            lint_mojibake: true,
+            lint_markdown_structure: true,
+            warn_on_unreviewed: true,
+        }
+    }
+}
+
+/// Validates a [`TranslationFile`] against the reference key set in
+/// [`crate::defaults`].
+pub struct I18NValidator {
+    options: ValidatorOptions,
+}
+
+impl Default for I18NValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl I18NValidator {
+    pub fn new() -> Self {
+        Self::with_options(ValidatorOptions::default())
+    }
+
+    pub fn with_options(options: ValidatorOptions) -> Self {
+        Self { options }
+    }
+
+    /// Like [`Self::validate`], but additionally checks the pack's review
+    /// sidecar when it has one: entries the sidecar records as not yet
+    /// reviewed are warned about, so a team's CI surfaces strings that
+    /// shipped straight from machine translation.
+    pub fn validate_with_review(
+        &self,
+        file: &TranslationFile,
+        review: Option<&pack::ReviewMetadata>,
+    ) -> ValidationReport {
+        let mut report = self.validate(file);
+        let Some(review) = review else {
+            return report;
+        };
+        if !self.options.warn_on_unreviewed {
+            return report;
+        }
+        for key in file.entries.keys() {
+            let Some(entry) = review.entry(key) else {
+                continue;
+            };
+            if entry.status == pack::ReviewStatus::Reviewed {
+                continue;
+            }
+            let mut message = format!("entry is marked {}", entry.status.label());
+            if let Some(reviewer) = &entry.reviewer {
+                message.push_str(&format!(" (assigned to {reviewer})"));
+            }
+            report.push(ValidationIssue::new(IssueCode::Unreviewed, key).with_message(message));
+        }
+        report
+    }
+
+    /// Like [`Self::validate`], but for a sparse pack that declares a
+    /// parent through `extends` in its metadata: keys the parent's resolved
+    /// translation file provides aren't reported as missing here, since the
+    /// runtime resolves them through the parent chain.
+    pub fn validate_with_parent(
+        &self,
+        file: &TranslationFile,
+        parent: &TranslationFile,
+    ) -> ValidationReport {
+        let mut report = self.validate(file);
+        report.issues.retain(|issue| {
+            !matches!(issue.code, IssueCode::MissingKey | IssueCode::MissingA11yKey)
+                || parent.get(&issue.key).is_none()
+        });
+        report
+    }
+
+    pub fn validate(&self, file: &TranslationFile) -> ValidationReport {
+        let reference = defaults::default_texts();
+        let mut report = ValidationReport::new(file.language.clone());
+
+        for (key, default) in defaults::DEFAULT_TEXTS {
+            // A file from before a rename may carry the entry under a
+            // deprecated alias; the runtime resolves that, so it counts as
+            // provided here (and is flagged as DeprecatedKey below).
+            let entry_key = if file.entries.contains_key(*key) {
+                *key
+            } else {
+                match defaults::KEY_ALIASES.iter().find(|(old, _)| {
+                    defaults::canonical_key(old) == *key && file.entries.contains_key(*old)
+                }) {
+                    Some((old, _)) => *old,
+                    None => {
+                        let code = if keys::is_accessibility_key(key) {
+                            IssueCode::MissingA11yKey
+                        } else {
+                            IssueCode::MissingKey
+                        };
+                        report.push(
+                            ValidationIssue::new(code, *key)
+                                .with_message(format!(
+                                    "no entry for reference key (English: {default:?})"
+                                ))
+                                .with_suggested_fix((*default).to_string()),
+                        );
+                        continue;
+                    }
+                }
+            };
+            let Some(translation) = file.get(entry_key) else {
+                report.push(
+                    ValidationIssue::new(IssueCode::NotAString, entry_key)
+                        .with_message("value must be a JSON string"),
+                );
+                continue;
+            };
+            if let Some(issue) = untranslated_issue(entry_key, default, translation, &file.language)
+            {
+                report.push(issue);
+            }
+            if placeholders(default) != placeholders(translation) {
+                report.push(
+                    ValidationIssue::new(IssueCode::PlaceholderMismatch, entry_key).with_message(
+                        format!(
+                            "placeholders {:?} don't match the English default's {:?}",
+                            placeholders(translation),
+                            placeholders(default),
+                        ),
+                    ),
+                );
+            }
+            for issue in self.lint_issues(entry_key, default, translation, &file.language) {
+                report.push(issue);
+            }
+        }
+
+        if self.options.lint_shared_translations {
+            for issue in shared_translation_issues(file) {
+                report.push(issue);
+            }
+        }
+
+        for key in file.entries.keys() {
+            let (base, platform) = keys::split_platform(key);
+            let canonical = defaults::canonical_key(base);
+            if canonical != base {
+                let suggested = match platform {
+                    Some(platform) => format!("{canonical}@{platform}"),
+                    None => canonical.to_string(),
+                };
+                report.push(
+                    ValidationIssue::new(IssueCode::DeprecatedKey, key)
+                        .with_message(
+                            "key was renamed and only works through a deprecation alias",
+                        )
+                        .with_suggested_fix(suggested),
+                );
+            } else if !reference.contains_key(base) {
+                // The settings-schema namespace has no reference entries by
+                // design: its English text lives in the settings' own doc
+                // comments, and packs translate whichever subset they cover.
+                if !keys::is_settings_schema_key(base) {
+                    let message = match platform {
+                        Some(_) => "platform variant of a key that is not in the reference set",
+                        None => "key is not in the reference set",
+                    };
+                    report
+                        .push(ValidationIssue::new(IssueCode::ExtraKey, key).with_message(message));
+                }
+            } else if platform.is_some() {
+                // Platform variants aren't visited by the reference loop
+                // above, so check their placeholders here against the base
+                // key's English default.
+                if let (Some(default), Some(translation)) = (reference.get(base), file.get(key)) {
+                    if placeholders(default) != placeholders(translation) {
+                        report.push(
+                            ValidationIssue::new(IssueCode::PlaceholderMismatch, key)
+                                .with_message(format!(
+                                    "placeholders {:?} don't match the English default's {:?}",
+                                    placeholders(translation),
+                                    placeholders(default),
+                                )),
+                        );
+                    }
+                }
+            }
+            if let Err(violation) = keys::check_key(key) {
+                let mut issue = ValidationIssue::new(IssueCode::InvalidKey, key)
+                    .with_message(format!("key violates the naming scheme: {violation:?}"));
+                if let Some(normalized) = normalized_key(key) {
+                    issue = issue.with_suggested_fix(normalized);
+                }
+                report.push(issue);
+            }
+        }
+
+        report
+    }
+
+    fn lint_issues(
+        &self,
+        key: &str,
+        default: &str,
+        translation: &str,
+        language: &str,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        if self.options.lint_trailing_whitespace
+            && translation != translation.trim()
+            && !translation.trim().is_empty()
+        {
+            issues.push(
+                ValidationIssue::new(IssueCode::TrailingWhitespace, key)
+                    .with_message("value has leading or trailing whitespace")
+                    .with_suggested_fix(translation.trim().to_string()),
+            );
+        }
+        if self.options.lint_double_spaces && translation.contains("  ") {
+            issues.push(
+                ValidationIssue::new(IssueCode::DoubleSpaces, key)
+                    .with_message("value contains consecutive spaces")
+                    .with_suggested_fix(collapse_spaces(translation)),
+            );
+        }
+        if self.options.lint_trailing_punctuation {
+            if has_trailing_ellipsis(default) != has_trailing_ellipsis(translation) {
+                issues.push(
+                    ValidationIssue::new(IssueCode::MismatchedEllipsis, key).with_message(
+                        "trailing ellipsis disagrees with the English default",
+                    ),
+                );
+            }
+            let default_colon = default.ends_with(':') || default.ends_with('：');
+            let translation_colon = translation.ends_with(':') || translation.ends_with('：');
+            if default_colon != translation_colon {
+                issues.push(
+                    ValidationIssue::new(IssueCode::MismatchedColon, key)
+                        .with_message("trailing colon disagrees with the English default"),
+                );
+            }
+        }
+        if self.options.lint_mojibake
+            && !is_cjk_language(language)
+            && looks_like_mojibake(translation)
+        {
+            issues.push(
+                ValidationIssue::new(IssueCode::Mojibake, key)
+                    .with_message("value looks like UTF-8 text read as Latin-1 (e.g. `Ã©` for `é`)"),
+            );
+        }
+        if self.options.lint_markdown_structure
+            && defaults::MARKDOWN_KEYS.contains(&key)
+            && let Some(issue) = markdown_structure_issue(key, default, translation)
+        {
+            issues.push(issue);
+        }
+        if self.options.lint_punctuation_width {
+            if translation.contains("...") {
+                issues.push(
+                    ValidationIssue::new(IssueCode::AsciiEllipsis, key)
+                        .with_message("use the single-character ellipsis `…` instead of `...`")
+                        .with_suggested_fix(translation.replace("...", "…")),
+                );
+            }
+            if is_cjk_language(language) {
+                if let Some(full_width) = full_width_ending(translation) {
+                    issues.push(
+                        ValidationIssue::new(IssueCode::HalfWidthPunctuation, key)
+                            .with_message(
+                                "CJK translations conventionally end with full-width punctuation",
+                            )
+                            .with_suggested_fix(full_width),
+                    );
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// The outcome of validating a single translation file: a flat list of typed
+/// issues, serializable to JSON for downstream tooling.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub language: String,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    fn new(language: String) -> Self {
+        Self {
+            language,
+            issues: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// A single problem found in a translation file.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub code: IssueCode,
+    /// The translation key the issue applies to.
+    pub key: String,
+    pub message: String,
+    /// A mechanical replacement value (or replacement key, for
+    /// [`IssueCode::InvalidKey`]), when one can be derived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_fix: Option<String>,
+}
+
+impl ValidationIssue {
+    fn new(code: IssueCode, key: impl Into<String>) -> Self {
+        Self {
+            severity: code.severity(),
+            code,
+            key: key.into(),
+            message: String::new(),
+            suggested_fix: None,
+        }
+    }
+
+    fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    fn with_suggested_fix(mut self, fix: impl Into<String>) -> Self {
+        self.suggested_fix = Some(fix.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Machine-readable issue codes, stable across releases so downstream tooling
+/// can filter on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCode {
+    MissingKey,
+    /// A missing `i18n.a11y.*` entry. Screen readers fall back to speaking
+    /// the English default, which degrades gracefully in a way a hole in
+    /// the visible UI doesn't, so packs aren't failed over it.
+    MissingA11yKey,
+    ExtraKey,
+    InvalidKey,
+    NotAString,
+    PlaceholderMismatch,
+    EmptyValue,
+    ValueEqualsKey,
+    ValueEqualsDefault,
+    TrailingWhitespace,
+    DoubleSpaces,
+    MismatchedEllipsis,
+    MismatchedColon,
+    AsciiEllipsis,
+    HalfWidthPunctuation,
+    SharedTranslation,
+    DeprecatedKey,
+    Mojibake,
+    MarkdownMismatch,
+    Unreviewed,
+}
+
+impl IssueCode {
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::MissingKey
+            | Self::InvalidKey
+            | Self::NotAString
+            | Self::PlaceholderMismatch => Severity::Error,
+            Self::MissingA11yKey
+            | Self::ExtraKey
+            | Self::EmptyValue
+            | Self::ValueEqualsKey
+            | Self::ValueEqualsDefault
+            | Self::TrailingWhitespace
+            | Self::DoubleSpaces
+            | Self::MismatchedEllipsis
+            | Self::MismatchedColon
+            | Self::AsciiEllipsis
+            | Self::HalfWidthPunctuation
+            | Self::SharedTranslation
+            | Self::DeprecatedKey
+            | Self::Mojibake
+            | Self::MarkdownMismatch
+            | Self::Unreviewed => Severity::Warning,
+        }
+    }
+}
+
+/// Warns when keys that share identical English text but were recorded (in
+/// [`defaults::KEY_CONTEXTS`]) as meaning different things have been given
+/// the same translation — the problem gettext solves with `msgctxt`, caught
+/// after the fact here. Keys without a recorded context are left alone.
+fn shared_translation_issues(file: &TranslationFile) -> Vec<ValidationIssue> {
+    let mut keys_by_text: collections::HashMap<&str, Vec<&str>> = collections::HashMap::default();
+    for (key, text) in defaults::DEFAULT_TEXTS {
+        keys_by_text.entry(text).or_default().push(key);
+    }
+
+    let mut issues = Vec::new();
+    for keys in keys_by_text.values() {
+        if keys.len() < 2 {
+            continue;
+        }
+        for (index, key) in keys.iter().enumerate() {
+            let Some(context) = defaults::key_context(key) else {
+                continue;
+            };
+            let Some(translation) = file.get(key) else {
+                continue;
+            };
+            // Identical to the default is a different problem, reported as
+            // ValueEqualsDefault.
+            if Some(translation) == defaults::default_text(key) {
+                continue;
+            }
+            for other in &keys[..index] {
+                let Some(other_context) = defaults::key_context(other) else {
+                    continue;
+                };
+                if other_context != context && file.get(other) == Some(translation) {
+                    issues.push(
+                        ValidationIssue::new(IssueCode::SharedTranslation, *key).with_message(
+                            format!(
+                                "translated identically to {other}, but their meanings differ: \
+                                 {context:?} vs {other_context:?}"
+                            ),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    issues.sort_by(|a, b| a.key.cmp(&b.key));
+    issues
+}
+
+fn untranslated_issue(
+    key: &str,
+    default: &str,
+    translation: &str,
+    language: &str,
+) -> Option<ValidationIssue> {
+    if translation.trim().is_empty() {
+        return Some(
+            ValidationIssue::new(IssueCode::EmptyValue, key)
+                .with_message("value is empty or only whitespace"),
+        );
+    }
+    if translation == key {
+        return Some(
+            ValidationIssue::new(IssueCode::ValueEqualsKey, key)
+                .with_message("value is the key itself, typically a template artifact"),
+        );
+    }
+    if translation == default
+        && !shares_english_strings(language)
+        // Values with no letters (numbers, punctuation) and well-known shared
+        // terms legitimately match the English default in any language.
+        && default.chars().any(|c| c.is_alphabetic())
+        && !SHARED_TERMS.contains(&default)
+    {
+        return Some(
+            ValidationIssue::new(IssueCode::ValueEqualsDefault, key)
+                .with_message("value is byte-identical to the English default"),
+        );
+    }
+    None
+}
+
+/// The Markdown structure of a value, reduced to the parts a translation
+/// must carry over verbatim: link targets, code-span contents, and list
+/// shape. Link text and everything outside these is free to change.
+#[derive(Debug, PartialEq, Eq)]
+struct MarkdownSignature {
+    link_targets: Vec<String>,
+    code_spans: Vec<String>,
+    bullet_items: usize,
+    ordered_items: usize,
+}
+
+fn markdown_signature(text: &str) -> MarkdownSignature {
+    let mut link_targets = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        match after.find(')') {
+            Some(end) => {
+                link_targets.push(after[..end].to_string());
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+
+    let parts: Vec<&str> = text.split('`').collect();
+    let mut code_spans = Vec::new();
+    for (index, part) in parts.iter().enumerate() {
+        // Odd parts sit between backticks; the last part after an unpaired
+        // backtick is not a span.
+        if index % 2 == 1 && index + 1 < parts.len() {
+            code_spans.push((*part).to_string());
+        }
+    }
+
+    let mut bullet_items = 0;
+    let mut ordered_items = 0;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
+            bullet_items += 1;
+        } else if trimmed
+            .split_once(". ")
+            .is_some_and(|(number, _)| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        {
+            ordered_items += 1;
+        }
+    }
+
+    MarkdownSignature {
+        link_targets,
+        code_spans,
+        bullet_items,
+        ordered_items,
+    }
+}
+
+fn markdown_structure_issue(key: &str, default: &str, translation: &str) -> Option<ValidationIssue> {
+    let expected = markdown_signature(default);
+    let actual = markdown_signature(translation);
+    if expected == actual {
+        return None;
+    }
+    let message = if expected.link_targets != actual.link_targets {
+        format!(
+            "link targets {:?} don't match the English default's {:?}",
+            actual.link_targets, expected.link_targets,
+        )
+    } else if expected.code_spans != actual.code_spans {
+        format!(
+            "code spans {:?} don't match the English default's {:?}",
+            actual.code_spans, expected.code_spans,
+        )
+    } else {
+        format!(
+            "{} bullet and {} numbered list item(s) where the English default has {} and {}",
+            actual.bullet_items,
+            actual.ordered_items,
+            expected.bullet_items,
+            expected.ordered_items,
+        )
+    };
+    Some(ValidationIssue::new(IssueCode::MarkdownMismatch, key).with_message(message))
+}
+
+/// Checks the raw bytes of a translation file before parsing. UTF-16 and
+/// BOM-prefixed files are rejected outright rather than transcoded — every
+/// tool in the pipeline writes plain UTF-8 — and invalid UTF-8 is reported
+/// with the byte offset of the first bad sequence.
+pub fn check_encoding(bytes: &[u8]) -> Result<&str> {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => {
+            bail!("file starts with a UTF-8 byte order mark; save it as plain UTF-8 without a BOM")
+        }
+        [0xFF, 0xFE, ..] | [0xFE, 0xFF, ..] => {
+            bail!("file is UTF-16 encoded; translation files must be plain UTF-8")
+        }
+        _ => {}
+    }
+    std::str::from_utf8(bytes).map_err(|error| {
+        anyhow::anyhow!(
+            "invalid UTF-8 sequence at byte offset {}",
+            error.valid_up_to()
+        )
+    })
+}
+
+/// Detects the double-encoding signature of UTF-8 text read as Latin-1:
+/// `Ã` or `Â` followed by a character in U+0080–U+00BF (`Ã©` for `é`), or
+/// `â` followed by `€` (the mangled form of curly punctuation). Correctly
+/// encoded accented text never contains these pairs.
+fn looks_like_mojibake(text: &str) -> bool {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            'Ã' | 'Â' => {
+                if chars
+                    .peek()
+                    .is_some_and(|next| ('\u{80}'..='\u{BF}').contains(next))
+                {
+                    return true;
+                }
+            }
+            'â' => {
+                if chars.peek() == Some(&'€') {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Whether a language is expected to keep many UI strings identical to the
+/// English defaults, making equals-default detection too noisy to be useful.
+fn shares_english_strings(language: &str) -> bool {
+    let primary = language.split(['-', '_']).next().unwrap_or(language);
+    primary.eq_ignore_ascii_case("en")
+}
+
+/// Whether a language conventionally uses full-width punctuation.
+fn is_cjk_language(language: &str) -> bool {
+    let primary = language.split(['-', '_']).next().unwrap_or(language);
+    matches!(
+        primary.to_ascii_lowercase().as_str(),
+        "zh" | "ja" | "ko" | "yue"
+    )
+}
+
+fn has_trailing_ellipsis(text: &str) -> bool {
+    let trimmed = text.trim_end();
+    trimmed.ends_with('…') || trimmed.ends_with("...")
+}
+
+/// If `text` ends with half-width punctuation that has a conventional
+/// full-width counterpart, returns the text with that ending replaced.
+fn full_width_ending(text: &str) -> Option<String> {
+    let last = text.chars().last()?;
+    let full_width = match last {
+        '?' => '？',
+        '!' => '！',
+        ':' => '：',
+        ',' => '，',
+        _ => return None,
+    };
+    let mut fixed = text.to_string();
+    fixed.pop();
+    fixed.push(full_width);
+    Some(fixed)
+}
+
+fn collapse_spaces(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c == ' ' {
+            if !last_was_space {
+                result.push(c);
+            }
+            last_was_space = true;
+        } else {
+            last_was_space = false;
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Rewrites a malformed key into a conforming one by normalizing every
+/// segment after the `i18n.` prefix, if a conforming form exists.
+fn normalized_key(key: &str) -> Option<String> {
+    let rest = key.strip_prefix("i18n.")?;
+    let mut segments = vec!["i18n".to_string()];
+    for segment in rest.split('.') {
+        let normalized = keys::normalize_segment(segment);
+        if normalized.is_empty() {
+            return None;
+        }
+        segments.push(normalized);
+    }
+    if segments.len() < 3 {
+        return None;
+    }
+    Some(segments.join("."))
+}
+
+/// Extracts the `{placeholder}` tokens of a string, in order. Public so
+/// tooling can check a single candidate value against its English default
+/// without building a whole [`TranslationFile`].
+pub fn placeholders(text: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        if let Some(end) = after.find('}') {
+            result.push(&after[..end]);
+            rest = &after[end + 1..];
+        } else {
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file(language: &str, json: &str) -> TranslationFile {
+        TranslationFile::parse(language, json).unwrap()
+    }
+
+    fn full_file(language: &str, value_for: impl Fn(&str, &str) -> String) -> TranslationFile {
+        let mut entries = serde_json::Map::new();
+        for (key, default) in crate::defaults::DEFAULT_TEXTS {
+            entries.insert((*key).to_string(), value_for(key, default).into());
+        }
+        TranslationFile {
+            language: language.to_string(),
+            schema_version: crate::pack::CURRENT_SCHEMA_VERSION,
+            translators: Vec::new(),
+            entries,
+        }
+    }
+
+    fn codes_for<'a>(report: &'a ValidationReport, key: &str) -> Vec<IssueCode> {
+        report
+            .issues
+            .iter()
+            .filter(|issue| issue.key == key)
+            .map(|issue| issue.code)
+            .collect()
+    }
+
+    #[test]
+    fn detects_missing_and_extra_keys() {
+        let report = I18NValidator::new().validate(&file(
+            "zh-CN",
+            r#"{"i18n.menu.file.save": "保存", "i18n.menu.file.bogus": "?"}"#,
+        ));
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::MissingKey
+                    && issue.key == "i18n.menu.file.title")
+        );
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.bogus"),
+            vec![IssueCode::ExtraKey]
+        );
+    }
+
+    #[test]
+    fn settings_schema_keys_are_not_extra() {
+        let report = I18NValidator::new().validate(&file(
+            "zh-CN",
+            r#"{
+                "i18n.menu.file.save": "保存",
+                "i18n.settings_schema.theme": "界面主题。",
+                "i18n.settings_schema.Bad Key": "?"
+            }"#,
+        ));
+        assert_eq!(codes_for(&report, "i18n.settings_schema.theme"), vec![]);
+        // The namespace is open-ended, but its keys still follow the naming
+        // scheme.
+        assert_eq!(
+            codes_for(&report, "i18n.settings_schema.Bad Key"),
+            vec![IssueCode::InvalidKey]
+        );
+    }
+
+    #[test]
+    fn missing_accessibility_keys_warn_in_their_own_category() {
+        let report = I18NValidator::new().validate(&file(
+            "zh-CN",
+            r#"{"i18n.menu.file.save": "保存"}"#,
+        ));
+        let a11y = report
+            .issues
+            .iter()
+            .find(|issue| issue.key == "i18n.a11y.close_button")
+            .unwrap();
+        assert_eq!(a11y.code, IssueCode::MissingA11yKey);
+        assert_eq!(a11y.severity, Severity::Warning);
+        // Holes in the visible UI still fail the pack outright.
+        assert!(report.errors().any(|issue| issue.code == IssueCode::MissingKey));
+
+        // A parent that provides the label clears the warning, like any
+        // other missing key.
+        let parent = file("zh", r#"{"i18n.a11y.close_button": "关闭"}"#);
+        let report = I18NValidator::new().validate_with_parent(
+            &file("zh-CN", r#"{"i18n.menu.file.save": "保存"}"#),
+            &parent,
+        );
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.key == "i18n.a11y.close_button")
+        );
+    }
+
+    #[test]
+    fn sparse_packs_inherit_their_parents_keys() {
+        let parent = file("en-US", r#"{"i18n.menu.file.save": "Save"}"#);
+        let report = I18NValidator::new()
+            .validate_with_parent(&file("en-GB", r#"{"i18n.dialog.cancel": "Cancel"}"#), &parent);
+        // Keys the parent provides aren't missing…
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::MissingKey
+                    && issue.key == "i18n.menu.file.save")
+        );
+        // …but keys neither file provides still are.
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::MissingKey
+                    && issue.key == "i18n.menu.file.title")
+        );
+    }
+
+    #[test]
+    fn platform_variants_of_reference_keys_validate_like_their_base() {
+        let report = I18NValidator::new().validate(&file(
+            "zh-CN",
+            r#"{
+                "i18n.menu.file.open@macos": "打开…",
+                "i18n.status.language_changed@linux": "换了",
+                "i18n.menu.file.bogus@windows": "?",
+                "i18n.menu.file.open@amiga": "?"
+            }"#,
+        ));
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.open@macos"),
+            Vec::<IssueCode>::new()
+        );
+        // The base key's placeholders apply to its variants.
+        assert_eq!(
+            codes_for(&report, "i18n.status.language_changed@linux"),
+            vec![IssueCode::PlaceholderMismatch]
+        );
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.bogus@windows"),
+            vec![IssueCode::ExtraKey]
+        );
+        // An unknown platform suffix is a naming violation, not a variant.
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.open@amiga"),
+            vec![IssueCode::ExtraKey, IssueCode::InvalidKey]
+        );
+    }
+
+    #[test]
+    fn detects_untranslated_values() {
+        let report = I18NValidator::new().validate(&full_file("zh-CN", |key, default| {
+            match *key {
+                "i18n.menu.file.save" => String::new(),
+                "i18n.menu.file.title" => key.to_string(),
+                "i18n.menu.edit.copy" => default.to_string(),
+                _ => format!("{default}-zh"),
+            }
+        }));
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.save"),
+            vec![IssueCode::EmptyValue]
+        );
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.title"),
+            vec![IssueCode::ValueEqualsKey]
+        );
+        assert_eq!(
+            codes_for(&report, "i18n.menu.edit.copy"),
+            vec![IssueCode::ValueEqualsDefault]
+        );
+    }
+
+    #[test]
+    fn aliased_keys_count_as_provided_but_warn() {
+        let mut file = full_file("zh-CN", |_, default| format!("{default}-zh"));
+        let value = file
+            .entries
+            .remove("i18n.menu.zed.open_settings")
+            .unwrap();
+        file.entries
+            .insert("i18n.menu.other.settings".to_string(), value);
+
+        let report = I18NValidator::new().validate(&file);
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::MissingKey
+                    && issue.key == "i18n.menu.zed.open_settings")
+        );
+        let deprecated = report
+            .issues
+            .iter()
+            .find(|issue| issue.code == IssueCode::DeprecatedKey)
+            .unwrap();
+        assert_eq!(deprecated.key, "i18n.menu.other.settings");
+        assert_eq!(
+            deprecated.suggested_fix.as_deref(),
+            Some("i18n.menu.zed.open_settings")
+        );
+    }
+
+    #[test]
+    fn unreviewed_entries_warn_when_review_metadata_is_present() {
+        let file = full_file("zh-CN", |_, default| format!("{default}-zh"));
+        let review = pack::ReviewMetadata::parse(
+            r#"{
+                "i18n.menu.file.save": {"status": "machine-translated"},
+                "i18n.menu.file.title": {"status": "needs-review", "reviewer": "lin"},
+                "i18n.menu.edit.copy": {"status": "reviewed", "reviewer": "lin"}
+            }"#,
+        )
+        .unwrap();
+
+        let report = I18NValidator::new().validate_with_review(&file, Some(&review));
+        assert_eq!(
+            codes_for(&report, "i18n.menu.file.save"),
+            vec![IssueCode::Unreviewed]
+        );
+        let assigned = report
+            .issues
+            .iter()
+            .find(|issue| issue.key == "i18n.menu.file.title")
+            .unwrap();
+        assert!(assigned.message.contains("needs-review"));
+        assert!(assigned.message.contains("lin"));
+        assert_eq!(codes_for(&report, "i18n.menu.edit.copy"), vec![]);
+
+        // No sidecar means no review tracking, and the warning is switchable.
+        let no_unreviewed = |report: &ValidationReport| {
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::Unreviewed)
+        };
+        assert!(no_unreviewed(
+            &I18NValidator::new().validate_with_review(&file, None)
+        ));
+        let validator = I18NValidator::with_options(ValidatorOptions {
+            warn_on_unreviewed: false,
+            ..ValidatorOptions::default()
+        });
+        assert!(no_unreviewed(
+            &validator.validate_with_review(&file, Some(&review))
+        ));
+    }
+
+    #[test]
+    fn equals_default_is_not_reported_for_english_variants() {
+        let report =
+            I18NValidator::new().validate(&full_file("en-GB", |_, default| default.to_string()));
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::ValueEqualsDefault)
+        );
+    }
+
+    #[test]
+    fn lints_whitespace_and_punctuation() {
+        let validator = I18NValidator::new();
+        let codes = |default: &str, translation: &str, language: &str| -> Vec<IssueCode> {
+            validator
+                .lint_issues("i18n.test.key", default, translation, language)
+                .into_iter()
+                .map(|issue| issue.code)
+                .collect()
+        };
+        assert_eq!(codes("Save", "保存 ", "zh-CN"), vec![IssueCode::TrailingWhitespace]);
+        assert_eq!(
+            codes("Save All", "Tout  enregistrer", "fr"),
+            vec![IssueCode::DoubleSpaces]
+        );
+        assert_eq!(codes("Open…", "打开", "zh-CN"), vec![IssueCode::MismatchedEllipsis]);
+        assert_eq!(codes("Open…", "打开...", "zh-CN"), vec![IssueCode::AsciiEllipsis]);
+        assert_eq!(
+            codes("Save changes?", "保存更改?", "zh-CN"),
+            vec![IssueCode::HalfWidthPunctuation]
+        );
+        assert_eq!(codes("Save changes?", "保存更改？", "zh-CN"), vec![]);
+        assert_eq!(codes("Café", "CafÃ©", "fr"), vec![IssueCode::Mojibake]);
+        assert_eq!(codes("Café", "Café", "fr"), vec![]);
+    }
+
+    #[test]
+    fn markdown_keys_must_preserve_links_code_spans_and_lists() {
+        let validator = I18NValidator::new();
+        let default = "Open a folder to get started, or read the \
+                       [docs](https://zed.dev/docs) to learn the basics.";
+        // Link text translates freely as long as the target survives.
+        assert_eq!(
+            validator.lint_issues(
+                "i18n.welcome.no_project",
+                default,
+                "打开一个文件夹开始使用，或阅读[文档](https://zed.dev/docs)了解基础知识。",
+                "zh-CN",
+            ),
+            vec![]
+        );
+        let issues = validator.lint_issues(
+            "i18n.welcome.no_project",
+            default,
+            "打开一个文件夹开始使用。",
+            "zh-CN",
+        );
+        assert_eq!(issues[0].code, IssueCode::MarkdownMismatch);
+        assert!(issues[0].message.contains("link targets"));
+
+        // A reworded keystroke inside a code span is flagged too.
+        let issues = validator.lint_issues(
+            "i18n.welcome.tip_command_palette",
+            "Every command lives in the command palette; open it with `cmd-shift-p`.",
+            "所有命令都在命令面板中；用 `cmd+shift+p` 打开。",
+            "zh-CN",
+        );
+        assert!(issues[0].message.contains("code spans"));
+
+        // A dropped list item changes the list shape and is flagged.
+        let issues = validator.lint_issues(
+            "i18n.welcome.no_project",
+            "- one\n- two",
+            "- un",
+            "fr",
+        );
+        assert!(issues[0].message.contains("bullet"));
+
+        // Keys outside MARKDOWN_KEYS aren't held to Markdown rules.
+        assert_eq!(
+            validator.lint_issues("i18n.menu.file.save", "Save", "[保存](x)", "zh-CN"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn rejects_non_utf8_encodings_with_the_offending_offset() {
+        check_encoding("{}".as_bytes()).unwrap();
+        let bom = check_encoding(&[0xEF, 0xBB, 0xBF, b'{', b'}']).unwrap_err();
+        assert!(bom.to_string().contains("byte order mark"));
+        let utf16 = check_encoding(&[0xFF, 0xFE, b'{', 0x00]).unwrap_err();
+        assert!(utf16.to_string().contains("UTF-16"));
+        let invalid = check_encoding(&[b'{', b'"', 0xC3, b'"', b'}']).unwrap_err();
+        assert!(invalid.to_string().contains("byte offset 2"));
+    }
+
+    #[test]
+    fn lints_can_be_disabled() {
+        let validator = I18NValidator::with_options(ValidatorOptions {
+            lint_punctuation_width: false,
+            ..ValidatorOptions::default()
+        });
+        assert_eq!(
+            validator.lint_issues("i18n.test.key", "Open…", "打开...", "zh-CN"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn detects_placeholder_mismatches() {
+        let report = I18NValidator::new().validate(&full_file("zh-CN", |key, default| {
+            if *key == "i18n.status.language_changed" {
+                "语言已切换".to_string()
+            } else {
+                format!("{default}-zh")
+            }
+        }));
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|issue| issue.code == IssueCode::PlaceholderMismatch
+                    && issue.key == "i18n.status.language_changed"
+                    && issue.severity == Severity::Error)
+        );
+    }
+
+    #[test]
+    fn suggests_fixes() {
+        let validator = I18NValidator::new();
+        let issues = validator.lint_issues("i18n.test.key", "Open…", "打开...", "zh-CN");
+        assert_eq!(issues[0].suggested_fix.as_deref(), Some("打开…"));
+
+        let report = I18NValidator::new().validate(&file(
+            "zh-CN",
+            r#"{"i18n.menu.go.go_to_line/column": "转到行/列…"}"#,
+        ));
+        let invalid = report
+            .issues
+            .iter()
+            .find(|issue| issue.code == IssueCode::InvalidKey)
+            .unwrap();
+        assert_eq!(
+            invalid.suggested_fix.as_deref(),
+            Some("i18n.menu.go.go_to_line_column")
+        );
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let report = I18NValidator::new().validate(&file(
+            "zh-CN",
+            r#"{"i18n.menu.file.save": "保存"}"#,
+        ));
+        let json = report.to_json().unwrap();
+        let parsed: ValidationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.language, report.language);
+        assert_eq!(parsed.issues, report.issues);
+    }
+}
@@ -0,0 +1,283 @@
+//! A comment-preserving document model for translation files.
+//!
+//! Translation files are flat JSON objects, optionally with `//` comments
+//! between entries (category headers, translator notes). Reading through
+//! `serde_json` would drop the comments, so the rewriting commands parse
+//! into this model instead and render back out with comments intact.
+
+use anyhow::{Context as _, Result, bail};
+use std::collections::HashMap;
+
+/// One key/value entry, together with the comment lines written above it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentEntry {
+    /// Comment lines (including the `//`) that precede this entry and move
+    /// with it when entries are reordered.
+    pub leading_comments: Vec<String>,
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+/// A parsed translation file that can be rendered back without losing
+/// comments.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Document {
+    pub entries: Vec<DocumentEntry>,
+    /// Comment lines after the last entry.
+    pub trailing_comments: Vec<String>,
+}
+
+impl Document {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut scanner = Scanner::new(source);
+        let mut document = Document::default();
+
+        let mut pending_comments = scanner.skip_trivia();
+        scanner.expect('{')?;
+        loop {
+            pending_comments.extend(scanner.skip_trivia());
+            match scanner.peek() {
+                Some('}') => {
+                    scanner.advance();
+                    document.trailing_comments = pending_comments;
+                    break;
+                }
+                Some('"') => {
+                    let key = scanner.string()?;
+                    scanner.skip_trivia();
+                    scanner.expect(':')?;
+                    scanner.skip_trivia();
+                    let value = scanner.value()?;
+                    document.entries.push(DocumentEntry {
+                        leading_comments: std::mem::take(&mut pending_comments),
+                        key,
+                        value,
+                    });
+                    pending_comments.extend(scanner.skip_trivia());
+                    if scanner.peek() == Some(',') {
+                        scanner.advance();
+                    }
+                }
+                Some(other) => bail!("unexpected character {other:?} in translation file"),
+                None => bail!("translation file ends before the closing brace"),
+            }
+        }
+        Ok(document)
+    }
+
+    /// Renders the document as JSONC: two-space indentation, comments
+    /// preserved, a blank line before each commented entry, and a trailing
+    /// newline.
+    pub fn render(&self) -> String {
+        let mut output = String::from("{\n");
+        for (index, entry) in self.entries.iter().enumerate() {
+            if index > 0 && !entry.leading_comments.is_empty() {
+                output.push('\n');
+            }
+            for comment in &entry.leading_comments {
+                output.push_str("  ");
+                output.push_str(comment);
+                output.push('\n');
+            }
+            let comma = if index + 1 < self.entries.len() { "," } else { "" };
+            output.push_str(&format!(
+                "  {}: {}{comma}\n",
+                serde_json::Value::from(entry.key.as_str()),
+                entry.value
+            ));
+        }
+        for comment in &self.trailing_comments {
+            output.push_str("  ");
+            output.push_str(comment);
+            output.push('\n');
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Reorders entries to match the reference key order, so translated
+    /// files mirror the structure of the defaults source and diffs between
+    /// releases stay minimal. Keys absent from the reference keep their
+    /// relative order after the recognized ones; each entry's leading
+    /// comments move with it.
+    pub fn sort_by_reference(&mut self, reference: &[(&str, &str)]) {
+        let index: HashMap<&str, usize> = reference
+            .iter()
+            .enumerate()
+            .map(|(index, (key, _))| (*key, index))
+            .collect();
+        self.entries.sort_by_key(|entry| {
+            index
+                .get(entry.key.as_str())
+                .copied()
+                .unwrap_or(usize::MAX)
+        });
+    }
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, offset: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.offset..].chars().next()
+    }
+
+    fn advance(&mut self) {
+        if let Some(c) = self.peek() {
+            self.offset += c.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.advance();
+                Ok(())
+            }
+            Some(c) => bail!("expected {expected:?}, found {c:?}"),
+            None => bail!("expected {expected:?}, found end of file"),
+        }
+    }
+
+    /// Skips whitespace and collects any comments encountered along the way.
+    fn skip_trivia(&mut self) -> Vec<String> {
+        let mut comments = Vec::new();
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.advance(),
+                Some('/') => {
+                    let rest = &self.source[self.offset..];
+                    if let Some(rest) = rest.strip_prefix("//") {
+                        let text = rest.lines().next().unwrap_or("").trim_end();
+                        comments.push(format!("//{text}"));
+                        self.offset += 2 + text.len();
+                    } else if let Some(rest) = rest.strip_prefix("/*") {
+                        let end = rest.find("*/").map_or(rest.len(), |end| end + 2);
+                        comments.push(format!("/*{}", &rest[..end]));
+                        self.offset += 2 + end;
+                    } else {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        comments
+    }
+
+    /// Consumes a JSON string literal, returning its decoded contents.
+    fn string(&mut self) -> Result<String> {
+        let start = self.offset;
+        self.expect('"')?;
+        let mut escaped = false;
+        loop {
+            match self.peek() {
+                Some('\\') if !escaped => escaped = true,
+                Some('"') if !escaped => {
+                    self.advance();
+                    return serde_json::from_str(&self.source[start..self.offset])
+                        .context("invalid string literal in translation file");
+                }
+                Some(_) => escaped = false,
+                None => bail!("unterminated string in translation file"),
+            }
+            self.advance();
+        }
+    }
+
+    /// Consumes a scalar JSON value. Translation files only contain strings,
+    /// but numbers, booleans, and null are tolerated so a malformed value
+    /// surfaces as a validation issue rather than a parse failure.
+    fn value(&mut self) -> Result<serde_json::Value> {
+        if self.peek() == Some('"') {
+            return Ok(serde_json::Value::from(self.string()?));
+        }
+        let start = self.offset;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, ',' | '}' | '/') {
+                break;
+            }
+            self.advance();
+        }
+        serde_json::from_str(&self.source[start..self.offset]).with_context(|| {
+            format!(
+                "invalid value {:?} in translation file",
+                &self.source[start..self.offset]
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trips_comments() {
+        let source = "{\n  // menu\n  \"i18n.menu.file.save\": \"Save\",\n\n  // dialog\n  \"i18n.dialog.ok\": \"OK\"\n}\n";
+        let document = Document::parse(source).unwrap();
+        assert_eq!(document.entries.len(), 2);
+        assert_eq!(
+            document.entries[1].leading_comments,
+            vec!["// dialog".to_string()]
+        );
+        assert_eq!(document.render(), source);
+    }
+
+    #[test]
+    fn sorting_follows_the_reference_and_carries_comments_along() {
+        let source = "{\n  // status\n  \"i18n.status.ready\": \"Ready\",\n  \"i18n.custom.key\": \"Custom\",\n\n  // dialog\n  \"i18n.dialog.ok\": \"OK\"\n}\n";
+        let mut document = Document::parse(source).unwrap();
+        document.sort_by_reference(&[
+            ("i18n.dialog.ok", "OK"),
+            ("i18n.status.ready", "Ready"),
+        ]);
+        // Reference keys come in reference order; unknown keys trail.
+        assert_eq!(
+            document.render(),
+            "{\n  // dialog\n  \"i18n.dialog.ok\": \"OK\",\n\n  // status\n  \"i18n.status.ready\": \"Ready\",\n  \"i18n.custom.key\": \"Custom\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn parses_compact_json_and_trailing_commas() {
+        let document =
+            Document::parse(r#"{"i18n.b.b.b": "2", "i18n.a.a.a": "1",}"#).unwrap();
+        assert_eq!(document.entries.len(), 2);
+        assert_eq!(document.entries[0].key, "i18n.b.b.b");
+    }
+
+    #[test]
+    fn escapes_values_correctly_when_rendering() {
+        let document = Document {
+            entries: vec![DocumentEntry {
+                leading_comments: Vec::new(),
+                key: "i18n.a.a.a".to_string(),
+                value: serde_json::Value::from("a \"quoted\" \\ multi\nline\ttext"),
+            }],
+            trailing_comments: Vec::new(),
+        };
+        let rendered = document.render();
+        let reparsed = Document::parse(&rendered).unwrap();
+        assert_eq!(reparsed, document);
+        // Strict JSON parsers accept the output too.
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&rendered).unwrap();
+    }
+
+    #[test]
+    fn rendered_output_stays_loadable() {
+        let source = "{\n  // escapes\n  \"i18n.a.a.a\": \"say \\\"hi\\\"\"\n}\n";
+        let document = Document::parse(source).unwrap();
+        assert_eq!(document.entries[0].value, "say \"hi\"");
+        let file = i18n::TranslationFile::parse("en", &document.render()).unwrap();
+        assert_eq!(file.get("i18n.a.a.a"), Some("say \"hi\""));
+    }
+}
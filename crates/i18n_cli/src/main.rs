@@ -0,0 +1,3071 @@
+//! `zed-i18n`: the command-line entry point for Zed's translation tooling.
+//!
+//! Every workflow — scanning sources for keys, validating language packs,
+//! scaffolding new packs, and reorganizing translation files — lives behind
+//! one binary with shared flags and consistent exit codes.
+
+mod jsonc;
+mod sync;
+mod template;
+
+use anyhow::{Context as _, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use i18n::TranslationFile;
+use i18n::defaults::default_texts;
+use i18n::importer::CHECKSUM_MANIFEST_NAME;
+use i18n::keys::TranslationCategory;
+use i18n::pack::{PackMetadata, ReviewMetadata, ReviewStatus};
+use i18n::validator::{I18NValidator, placeholders};
+use serde::Serialize;
+use sha2::Digest as _;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use template::I18NTemplate;
+
+const EXIT_CODES_HELP: &str = "Exit codes:
+  0  success, nothing to report
+  1  the command ran but found problems (validation errors, unknown keys)
+  2  the command itself failed (bad arguments, unreadable files)";
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "zed-i18n",
+    about = "Tooling for Zed's UI translations and language packs.",
+    after_help = EXIT_CODES_HELP
+)]
+struct ZedI18n {
+    /// Output format for reports.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Suppress informational output; only problems are printed.
+    #[arg(long, global = true)]
+    quiet: bool,
+    /// Directory that relative paths are resolved against.
+    #[arg(long, global = true, default_value = ".")]
+    base_dir: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Scan Rust sources for translation key literals and report keys that
+    /// are missing from the reference set, plus reference keys no source
+    /// file mentions.
+    Scan {
+        /// Files or directories to scan. Defaults to the base directory.
+        paths: Vec<PathBuf>,
+    },
+    /// Scan the application menu definitions and report menu labels that
+    /// have no reference key yet.
+    ScanAppMenus {
+        /// Path to the menu definitions, relative to the base directory.
+        #[arg(long, default_value = "crates/zed/src/zed/app_menus.rs")]
+        menus: PathBuf,
+    },
+    /// Collect the inline English defaults written at `t_default!` call
+    /// sites, deduplicated, and report defaults missing from the reference
+    /// set, call sites that disagree with each other, and call sites that
+    /// disagree with the reference set.
+    ExtractDefaults {
+        /// Files or directories to scan. Defaults to the base directory.
+        paths: Vec<PathBuf>,
+    },
+    /// Validate a language pack or a single translation file.
+    Validate {
+        /// A pack directory (containing metadata.json) or a translation
+        /// file.
+        pack: PathBuf,
+        /// The language the file provides. Inferred from the pack metadata
+        /// or the file name when omitted.
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// Three-way merge of translation files at key granularity. Keys changed
+    /// on only one side merge cleanly; keys changed on both sides get
+    /// conflict markers inside the value.
+    Merge {
+        /// The common ancestor version.
+        base: PathBuf,
+        /// Our version. Also the output path unless --output is given, which
+        /// matches how git invokes merge drivers.
+        ours: PathBuf,
+        /// Their version.
+        theirs: PathBuf,
+        /// Write the merged result here instead of overwriting `ours`.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Print translated/fuzzy/untranslated counts for a pack, broken down by
+    /// category, plus the longest and shortest translations.
+    Stats {
+        /// A pack directory (containing metadata.json) or a translation
+        /// file.
+        pack: PathBuf,
+        /// The language the file provides. Inferred from the pack metadata
+        /// or the file name when omitted.
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// List reference keys added, removed, or reworded since a previous
+    /// revision, with old and new English text for translators.
+    Changed {
+        /// The git revision to compare the current reference set against,
+        /// e.g. a release tag.
+        #[arg(long)]
+        since: String,
+    },
+    /// Create a new language pack skeleton with template translation files.
+    New {
+        /// The IETF language tag the pack will provide, e.g. `zh-CN`.
+        /// Prompted for interactively when omitted.
+        language: Option<String>,
+        /// Human-readable pack name. Defaults to the language's native name
+        /// when known, otherwise the language tag.
+        #[arg(long)]
+        name: Option<String>,
+        /// Where to create the pack. Defaults to `<base-dir>/<language>`.
+        #[arg(long, alias = "output-dir")]
+        output: Option<PathBuf>,
+        /// Pre-fill values from the installed pack for a related language
+        /// (e.g. seed zh-TW from zh-CN) instead of English.
+        #[arg(long)]
+        seed: Option<String>,
+        /// Where installed packs live when --seed is used. Defaults to Zed's
+        /// language pack directory.
+        #[arg(long)]
+        packs_dir: Option<PathBuf>,
+        /// Generate the data-only extension layout (extension.toml plus
+        /// resources/translations/) instead of a standalone pack.
+        #[arg(long)]
+        data_only: bool,
+        /// Generate one translation file per key area (menu.json,
+        /// editor.json, …) instead of a single file.
+        #[arg(long)]
+        split: bool,
+        /// Generate a sparse pack extending the given parent language (e.g.
+        /// en-GB extending en-US), containing only the keys that differ.
+        #[arg(long, value_name = "LANGUAGE")]
+        extends: Option<String>,
+    },
+    /// Validate, normalize, and package a pack into a distributable archive
+    /// with an embedded checksum manifest, ready for `I18nImporter`.
+    Package {
+        /// The pack directory to package.
+        pack_dir: PathBuf,
+        /// Where to write the archive. Defaults to
+        /// `<pack-dir>-<version>.tar.gz`.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Validate every i18n-* pack in an extensions directory in one pass:
+    /// manifests, translations, and duplicate language ids across packs.
+    CheckExtensions {
+        /// The directory containing installed extensions.
+        extensions_dir: PathBuf,
+    },
+    /// Build a key-usage index mapping every translation key to the source
+    /// files and lines that use it, for translators and review tooling.
+    UsageIndex {
+        /// Files or directories to scan. Defaults to the base directory.
+        paths: Vec<PathBuf>,
+        /// Where to write the index. Defaults to `key-usage.json` in the
+        /// base directory.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Export a pack as a gettext PO file, with each key as `msgctxt` and
+    /// recorded translator context as `#.` comments.
+    ExportPo {
+        /// A pack directory (containing metadata.json) or a translation
+        /// file.
+        pack: PathBuf,
+        /// The language the file provides. Inferred from the pack metadata
+        /// or the file name when omitted.
+        #[arg(long)]
+        language: Option<String>,
+        /// Where to write the PO file. Defaults to stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Emit the fully resolved translation table for a language — installed
+    /// pack values overlaid on the English defaults — exactly as users will
+    /// see it, for docs tooling, screenshots, and QA.
+    ExportEffective {
+        /// The language to resolve, e.g. `zh-CN`. Negotiated against the
+        /// installed packs the same way the runtime does.
+        language: String,
+        /// Where installed packs live. Defaults to Zed's language pack
+        /// directory.
+        #[arg(long)]
+        packs_dir: Option<PathBuf>,
+        /// Overlay personal overrides from this `translations.json` file.
+        #[arg(long)]
+        user_overrides: Option<PathBuf>,
+        /// Where to write the table. Defaults to stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Export only the entries a translator still has work on — keys the
+    /// pack is missing, keys copied verbatim from English, and entries the
+    /// review sidecar flags as needing review — with the English text and
+    /// recorded translator context alongside each one.
+    ExportMissing {
+        /// A pack directory (containing metadata.json) or a translation
+        /// file.
+        pack: PathBuf,
+        /// The language the file provides. Inferred from the pack metadata
+        /// or the file name when omitted.
+        #[arg(long)]
+        language: Option<String>,
+        /// The file format to emit. (The global `--format` selects how
+        /// reports are printed, so the export format has its own flag.)
+        #[arg(long = "as", value_enum, default_value_t = ExportFormat::Json)]
+        export_format: ExportFormat,
+        /// Where to write the export. Defaults to stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge a completed `export-missing` file back into a pack, checking
+    /// each entry's `{placeholder}` tokens against the English default and
+    /// skipping entries that don't match.
+    ImportPartial {
+        /// The pack directory to merge into. Must keep its translations in
+        /// a single `translation.json`.
+        pack: PathBuf,
+        /// The completed export: a JSON, PO, or CSV file as produced by
+        /// `export-missing`, recognized by its extension.
+        input: PathBuf,
+    },
+    /// Synchronize with a hosted translation platform (Weblate or Crowdin):
+    /// push new reference keys upstream, pull approved translations back
+    /// into each configured pack.
+    Sync {
+        /// The sync configuration file.
+        #[arg(long, default_value = "sync.toml")]
+        config: PathBuf,
+        /// Push reference keys and English text upstream.
+        #[arg(long)]
+        push: bool,
+        /// Pull translations back into the configured packs.
+        #[arg(long)]
+        pull: bool,
+    },
+    /// Rewrite translation files into canonical form: keys in defaults
+    /// order, two-space indentation, trailing newline.
+    Reorganize {
+        /// The translation files to rewrite in place.
+        files: Vec<PathBuf>,
+        /// Print the would-be result instead of writing, and exit with
+        /// status 1 if any file would change.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Upgrade translation files to the current key schema, applying the
+    /// registered migrations and stamping the new schema version.
+    Migrate {
+        /// The translation files to migrate in place.
+        files: Vec<PathBuf>,
+    },
+    /// Rename a translation key everywhere at once: the reference set, call
+    /// sites in Rust sources, and every translation file under the base
+    /// directory.
+    RenameKey {
+        /// The key to rename.
+        old: String,
+        /// The new name for the key.
+        new: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// The file formats `export-missing` can emit and `import-partial` can read
+/// back.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ExportFormat {
+    Json,
+    Po,
+    Csv,
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+    let args = ZedI18n::parse();
+    match run(args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(error) => {
+            eprintln!("zed-i18n: {error:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Runs the selected subcommand. `Ok(false)` means the command completed but
+/// found problems worth a nonzero exit.
+fn run(args: ZedI18n) -> Result<bool> {
+    match args.command {
+        Command::Scan { paths } => {
+            let paths = if paths.is_empty() {
+                vec![args.base_dir.clone()]
+            } else {
+                paths
+                    .into_iter()
+                    .map(|path| resolve(&args.base_dir, path))
+                    .collect()
+            };
+            scan(&paths, args.format, args.quiet)
+        }
+        Command::ScanAppMenus { menus } => {
+            scan_app_menus(&resolve(&args.base_dir, menus), args.format, args.quiet)
+        }
+        Command::ExtractDefaults { paths } => {
+            let paths = if paths.is_empty() {
+                vec![args.base_dir.clone()]
+            } else {
+                paths
+                    .into_iter()
+                    .map(|path| resolve(&args.base_dir, path))
+                    .collect()
+            };
+            extract_defaults(&paths, args.format, args.quiet)
+        }
+        Command::Validate { pack, language } => validate(
+            &resolve(&args.base_dir, pack),
+            language,
+            args.format,
+            args.quiet,
+        ),
+        Command::Stats { pack, language } => {
+            stats(&resolve(&args.base_dir, pack), language, args.format)
+        }
+        Command::Merge {
+            base,
+            ours,
+            theirs,
+            output,
+        } => {
+            let ours = resolve(&args.base_dir, ours);
+            let output = output.map_or_else(|| ours.clone(), |output| resolve(&args.base_dir, output));
+            merge(
+                &resolve(&args.base_dir, base),
+                &ours,
+                &resolve(&args.base_dir, theirs),
+                &output,
+                args.quiet,
+            )
+        }
+        Command::Changed { since } => changed(&args.base_dir, &since, args.format, args.quiet),
+        Command::New {
+            language,
+            name,
+            output,
+            seed,
+            packs_dir,
+            data_only,
+            split,
+            extends,
+        } => {
+            let language = match language {
+                Some(language) => language,
+                None => prompt_for_language()?,
+            };
+            let language = i18n::lang_codes::check_language_tag(&language)?;
+            let output = output
+                .map(|output| resolve(&args.base_dir, output))
+                .unwrap_or_else(|| args.base_dir.join(&language));
+            let name = name
+                .or_else(|| i18n::lang_codes::native_name(&language).map(String::from))
+                .unwrap_or_else(|| language.clone());
+            let mut template = I18NTemplate::new(&language, &name);
+            if data_only {
+                template = template.data_only();
+            }
+            if split {
+                template = template.split();
+            }
+            if let Some(seed) = seed {
+                template = template.with_seed_language(seed);
+            }
+            if let Some(extends) = extends {
+                template = template.extends(extends);
+            }
+            if let Some(packs_dir) = packs_dir {
+                template = template.with_packs_dir(resolve(&args.base_dir, packs_dir));
+            }
+            template.generate_translation_files(&output)?;
+            if !args.quiet {
+                println!("created language pack skeleton at {}", output.display());
+            }
+            Ok(true)
+        }
+        Command::CheckExtensions { extensions_dir } => {
+            check_extensions(&resolve(&args.base_dir, extensions_dir), args.quiet)
+        }
+        Command::UsageIndex { paths, output } => {
+            let paths = if paths.is_empty() {
+                vec![args.base_dir.clone()]
+            } else {
+                paths
+                    .into_iter()
+                    .map(|path| resolve(&args.base_dir, path))
+                    .collect()
+            };
+            let output = output.map_or_else(
+                || args.base_dir.join("key-usage.json"),
+                |output| resolve(&args.base_dir, output),
+            );
+            usage_index(&args.base_dir, &paths, &output, args.quiet)
+        }
+        Command::ExportPo {
+            pack,
+            language,
+            output,
+        } => {
+            let file = load_translation_file(&resolve(&args.base_dir, pack), language)?;
+            let po = render_po(&file);
+            match output {
+                Some(output) => {
+                    let output = resolve(&args.base_dir, output);
+                    std::fs::write(&output, po)
+                        .with_context(|| format!("failed to write {}", output.display()))?;
+                    if !args.quiet {
+                        println!("exported {} to {}", file.language, output.display());
+                    }
+                }
+                None => print!("{po}"),
+            }
+            Ok(true)
+        }
+        Command::ExportEffective {
+            language,
+            packs_dir,
+            user_overrides,
+            output,
+        } => {
+            let packs_dir = packs_dir
+                .map(|dir| resolve(&args.base_dir, dir))
+                .unwrap_or_else(|| paths::language_packs_dir().clone());
+            let user_overrides = user_overrides.map(|path| resolve(&args.base_dir, path));
+            let output = output.map(|path| resolve(&args.base_dir, path));
+            export_effective(
+                &language,
+                &packs_dir,
+                user_overrides.as_deref(),
+                output.as_deref(),
+                args.format,
+                args.quiet,
+            )
+        }
+        Command::ExportMissing {
+            pack,
+            language,
+            export_format,
+            output,
+        } => {
+            let pack = resolve(&args.base_dir, pack);
+            let output = output.map(|path| resolve(&args.base_dir, path));
+            export_missing(
+                &pack,
+                language,
+                export_format,
+                output.as_deref(),
+                args.quiet,
+            )
+        }
+        Command::ImportPartial { pack, input } => import_partial(
+            &resolve(&args.base_dir, pack),
+            &resolve(&args.base_dir, input),
+            args.quiet,
+        ),
+        Command::Sync { config, push, pull } => {
+            if !push && !pull {
+                bail!("pass --push, --pull, or both");
+            }
+            run_sync(&resolve(&args.base_dir, config), push, pull, args.quiet)
+        }
+        Command::Package { pack_dir, output } => {
+            let pack_dir = resolve(&args.base_dir, pack_dir);
+            let output = output.map(|output| resolve(&args.base_dir, output));
+            package(&pack_dir, output, args.quiet)
+        }
+        Command::Reorganize { files, dry_run } => {
+            if files.is_empty() {
+                bail!("no translation files given");
+            }
+            let files = expand_translation_paths(&args.base_dir, files)?;
+            let mut changed = 0;
+            for file in &files {
+                if reorganize(file, dry_run)? {
+                    changed += 1;
+                }
+            }
+            if !args.quiet {
+                if dry_run {
+                    println!("{changed} of {} file(s) would change", files.len());
+                } else {
+                    println!("reorganized {changed} of {} file(s)", files.len());
+                }
+            }
+            Ok(!dry_run || changed == 0)
+        }
+        Command::Migrate { files } => {
+            if files.is_empty() {
+                bail!("no translation files given");
+            }
+            let files = expand_translation_paths(&args.base_dir, files)?;
+            let mut migrated = 0;
+            for file in &files {
+                if migrate_translation_file(file)? {
+                    migrated += 1;
+                }
+            }
+            if !args.quiet {
+                println!("migrated {migrated} of {} file(s)", files.len());
+            }
+            Ok(true)
+        }
+        Command::RenameKey { old, new } => {
+            rename_key(&args.base_dir, &old, &new, args.format, args.quiet)
+        }
+    }
+}
+
+/// Asks for a language tag on the terminal, listing the well-known tags
+/// with their native names first.
+fn prompt_for_language() -> Result<String> {
+    use std::io::{BufRead as _, IsTerminal as _, Write as _};
+
+    anyhow::ensure!(
+        std::io::stdin().is_terminal(),
+        "no language tag given; pass one as an argument, e.g. `zed-i18n new zh-CN`"
+    );
+    println!("Well-known language tags (any IETF tag is accepted):");
+    for (tag, native, english) in i18n::lang_codes::KNOWN_LANGUAGES {
+        println!("  {tag:<8} {native} ({english})");
+    }
+    print!("Language tag: ");
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    let line = line.trim();
+    anyhow::ensure!(!line.is_empty(), "no language tag given");
+    Ok(line.to_string())
+}
+
+fn resolve(base_dir: &Path, path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        path
+    } else {
+        base_dir.join(path)
+    }
+}
+
+/// Expands directory arguments into the translation files they contain: a
+/// pack's `translation.json` and any per-namespace files under its
+/// `translations/` directory. Plain file paths pass through unchanged.
+fn expand_translation_paths(base_dir: &Path, paths: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut expanded = Vec::new();
+    for path in paths {
+        let path = resolve(base_dir, path);
+        if !path.is_dir() {
+            expanded.push(path);
+            continue;
+        }
+        let before = expanded.len();
+        let translation_path = path.join("translation.json");
+        if translation_path.exists() {
+            expanded.push(translation_path);
+        }
+        let split_dir = path.join(i18n::pack::SPLIT_TRANSLATIONS_DIR_NAME);
+        if split_dir.is_dir() {
+            let mut parts = Vec::new();
+            for entry in std::fs::read_dir(&split_dir)
+                .with_context(|| format!("failed to read {}", split_dir.display()))?
+            {
+                let part = entry?.path();
+                if part.extension().is_some_and(|extension| extension == "json") {
+                    parts.push(part);
+                }
+            }
+            parts.sort();
+            expanded.extend(parts);
+        }
+        if expanded.len() == before {
+            bail!("no translation files in {}", path.display());
+        }
+    }
+    Ok(expanded)
+}
+
+#[derive(Serialize)]
+struct ScanReport {
+    /// Keys referenced in source that the reference set doesn't define.
+    unknown_keys: BTreeSet<String>,
+    /// Reference keys no scanned source file mentions.
+    unreferenced_keys: BTreeSet<String>,
+}
+
+fn scan(paths: &[PathBuf], format: OutputFormat, quiet: bool) -> Result<bool> {
+    let mut referenced = BTreeSet::new();
+    for path in paths {
+        for entry in walkdir::WalkDir::new(path) {
+            let entry = entry?;
+            if entry.file_type().is_file()
+                && entry.path().extension().is_some_and(|ext| ext == "rs")
+                // The reference set itself would otherwise mark every key as
+                // referenced.
+                && !entry.path().ends_with("i18n/src/defaults.rs")
+            {
+                let source = std::fs::read_to_string(entry.path())
+                    .with_context(|| format!("failed to read {}", entry.path().display()))?;
+                referenced.extend(extract_key_literals(&source));
+                referenced.extend(extract_a11y_call_sites(&source));
+            }
+        }
+    }
+
+    let defaults = default_texts();
+    let report = ScanReport {
+        unknown_keys: referenced
+            .iter()
+            .filter(|key| !defaults.contains_key(key.as_str()))
+            .cloned()
+            .collect(),
+        unreferenced_keys: defaults
+            .keys()
+            .filter(|key| !referenced.contains(*key))
+            .map(|key| key.to_string())
+            .collect(),
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => {
+            for key in &report.unknown_keys {
+                println!("unknown key: {key}");
+            }
+            if !quiet {
+                for key in &report.unreferenced_keys {
+                    println!("unreferenced key: {key}");
+                }
+                println!(
+                    "{} key(s) referenced, {} unknown, {} unreferenced",
+                    referenced.len(),
+                    report.unknown_keys.len(),
+                    report.unreferenced_keys.len()
+                );
+            }
+        }
+    }
+    Ok(report.unknown_keys.is_empty())
+}
+
+/// Extracts `i18n.`-prefixed string literals that conform to the key naming
+/// scheme.
+fn extract_key_literals(source: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("\"i18n.") {
+        rest = &rest[start + 1..];
+        if let Some(end) = rest.find('"') {
+            let candidate = &rest[..end];
+            if i18n::keys::check_key(candidate).is_ok() {
+                keys.push(candidate.to_string());
+            }
+            rest = &rest[end + 1..];
+        } else {
+            break;
+        }
+    }
+    keys
+}
+
+/// Extracts the full keys of `a11y_label!("…")` call sites, which name only
+/// the path under `i18n.a11y.` and assemble the key with `concat!`, so the
+/// literal scan above can't see them.
+fn extract_a11y_call_sites(source: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("a11y_label!") {
+        rest = &rest[start + "a11y_label!".len()..];
+        let Some((path, after)) = leading_string_literal(rest) else {
+            continue;
+        };
+        let candidate = format!("i18n.a11y.{path}");
+        if i18n::keys::check_key(&candidate).is_ok() {
+            keys.push(candidate);
+        }
+        rest = after;
+    }
+    keys
+}
+
+#[derive(Serialize)]
+struct ReferenceMismatch {
+    key: String,
+    call_site: String,
+    reference: String,
+}
+
+#[derive(Serialize)]
+struct ExtractDefaultsReport {
+    /// Deduplicated call-site defaults the reference set doesn't define
+    /// yet, ready to fold into `DEFAULT_TEXTS`.
+    new_defaults: BTreeMap<String, String>,
+    /// Keys whose call sites disagree about the English default.
+    conflicts: BTreeMap<String, BTreeSet<String>>,
+    /// Call-site defaults that disagree with the reference set's text.
+    reference_mismatches: Vec<ReferenceMismatch>,
+}
+
+fn extract_defaults(paths: &[PathBuf], format: OutputFormat, quiet: bool) -> Result<bool> {
+    let mut collected: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for path in paths {
+        for entry in walkdir::WalkDir::new(path) {
+            let entry = entry?;
+            if entry.file_type().is_file()
+                && entry.path().extension().is_some_and(|ext| ext == "rs")
+            {
+                let source = std::fs::read_to_string(entry.path())
+                    .with_context(|| format!("failed to read {}", entry.path().display()))?;
+                for (key, default) in extract_inline_defaults(&source) {
+                    collected.entry(key).or_default().insert(default);
+                }
+            }
+        }
+    }
+
+    let defaults = default_texts();
+    let mut report = ExtractDefaultsReport {
+        new_defaults: BTreeMap::new(),
+        conflicts: BTreeMap::new(),
+        reference_mismatches: Vec::new(),
+    };
+    for (key, texts) in collected {
+        if texts.len() > 1 {
+            report.conflicts.insert(key, texts);
+            continue;
+        }
+        let Some(text) = texts.into_iter().next() else {
+            continue;
+        };
+        match defaults.get(key.as_str()) {
+            Some(reference) if *reference != text => {
+                report.reference_mismatches.push(ReferenceMismatch {
+                    key,
+                    call_site: text,
+                    reference: (*reference).to_string(),
+                });
+            }
+            Some(_) => {}
+            None => {
+                report.new_defaults.insert(key, text);
+            }
+        }
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => {
+            for (key, texts) in &report.conflicts {
+                let texts: Vec<String> = texts.iter().map(|text| format!("{text:?}")).collect();
+                println!("conflicting defaults for {key}: {}", texts.join(", "));
+            }
+            for mismatch in &report.reference_mismatches {
+                println!(
+                    "call site for {} says {:?}, reference set says {:?}",
+                    mismatch.key, mismatch.call_site, mismatch.reference
+                );
+            }
+            if !report.new_defaults.is_empty() {
+                println!("not in the reference set yet (paste into DEFAULT_TEXTS):");
+                for (key, text) in &report.new_defaults {
+                    println!("    ({key:?}, {text:?}),");
+                }
+            }
+            if !quiet {
+                println!(
+                    "{} new default(s), {} conflict(s), {} reference mismatch(es)",
+                    report.new_defaults.len(),
+                    report.conflicts.len(),
+                    report.reference_mismatches.len()
+                );
+            }
+        }
+    }
+    Ok(report.conflicts.is_empty() && report.reference_mismatches.is_empty())
+}
+
+/// Extracts `(key, default)` pairs from `t_default!("…", "…")` call sites.
+fn extract_inline_defaults(source: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("t_default!") {
+        rest = &rest[start + "t_default!".len()..];
+        let Some((key, after_key)) = leading_string_literal(rest) else {
+            continue;
+        };
+        let Some((default, after_default)) = leading_string_literal(after_key) else {
+            continue;
+        };
+        if i18n::keys::check_key(&key).is_ok() {
+            pairs.push((key, default));
+        }
+        rest = after_default;
+    }
+    pairs
+}
+
+/// Parses the next string literal, requiring that only call punctuation and
+/// whitespace precede it so the scan can't wander into unrelated strings.
+fn leading_string_literal(text: &str) -> Option<(String, &str)> {
+    let start = text.find('"')?;
+    if !text[..start]
+        .chars()
+        .all(|c| c.is_whitespace() || c == '(' || c == ',')
+    {
+        return None;
+    }
+    let mut value = String::new();
+    let mut chars = text[start + 1..].char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, escaped)) => value.push(escaped),
+                None => return None,
+            },
+            '"' => {
+                let end = start + 1 + index + 1;
+                return Some((value, text.get(end..)?));
+            }
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+#[derive(Serialize)]
+struct MenuLabel {
+    menu: String,
+    label: String,
+    expected_key: String,
+    covered: bool,
+}
+
+fn scan_app_menus(menus_path: &Path, format: OutputFormat, quiet: bool) -> Result<bool> {
+    let source = std::fs::read_to_string(menus_path)
+        .with_context(|| format!("failed to read {}", menus_path.display()))?;
+    let labels = scan_menu_labels(&source);
+    let missing = labels.iter().filter(|label| !label.covered).count();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&labels)?),
+        OutputFormat::Text => {
+            for label in &labels {
+                if !label.covered {
+                    println!(
+                        "missing key for \"{}\" in menu \"{}\" (expected {})",
+                        label.label, label.menu, label.expected_key
+                    );
+                }
+            }
+            if !quiet {
+                println!("{} menu label(s), {} missing keys", labels.len(), missing);
+            }
+        }
+    }
+    Ok(missing == 0)
+}
+
+/// Extracts menu item labels from a `Vec<Menu>`-building source file by
+/// following the `Menu` / `MenuItem::submenu` structure itself: a stack of
+/// open `Menu { ... }` literals tracks real nesting, so arbitrarily nested
+/// submenus produce hierarchical keys without naming any menu in code.
+fn scan_menu_labels(source: &str) -> Vec<MenuLabel> {
+    struct Frame {
+        depth: usize,
+        name: Option<String>,
+    }
+
+    let defaults = default_texts();
+    let mut labels = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut depth = 0_usize;
+    let mut pending_menu = false;
+    let mut pending_name = false;
+    let mut pending_label = false;
+
+    let bytes = source.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        // `Menu` only opens a frame when its brace follows directly;
+        // mentions in type positions like `Vec<Menu>` don't.
+        if pending_menu && !bytes[i].is_ascii_whitespace() && bytes[i] != b'{' {
+            pending_menu = false;
+        }
+        match bytes[i] {
+            b'"' => {
+                let (literal, end) = read_string_literal(source, i);
+                if pending_name {
+                    if let Some(frame) = stack.last_mut() {
+                        if frame.name.is_none() {
+                            frame.name = Some(literal);
+                        }
+                    }
+                    pending_name = false;
+                } else if pending_label {
+                    // The label is the first string literal after the call;
+                    // multi-line calls put it on a following line.
+                    let path: Vec<&str> = stack
+                        .iter()
+                        .filter_map(|frame| frame.name.as_deref())
+                        .collect();
+                    let mut expected_key = String::from("i18n.menu");
+                    for segment in &path {
+                        expected_key.push('.');
+                        expected_key.push_str(&i18n::keys::normalize_segment(segment));
+                    }
+                    expected_key.push('.');
+                    expected_key.push_str(&i18n::keys::normalize_segment(&literal));
+                    let covered = defaults.contains_key(expected_key.as_str())
+                        || defaults.values().any(|text| *text == literal);
+                    labels.push(MenuLabel {
+                        menu: path.join(" > "),
+                        label: literal,
+                        expected_key,
+                        covered,
+                    });
+                    pending_label = false;
+                }
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'{' => {
+                depth += 1;
+                if pending_menu {
+                    stack.push(Frame { depth, name: None });
+                    pending_menu = false;
+                }
+                i += 1;
+            }
+            b'}' => {
+                while stack.last().is_some_and(|frame| frame.depth == depth) {
+                    stack.pop();
+                }
+                depth = depth.saturating_sub(1);
+                i += 1;
+            }
+            _ => {
+                let boundary = i == 0 || !is_ident_byte(bytes[i - 1]);
+                let rest = &source[i..];
+                if boundary
+                    && rest.starts_with("Menu")
+                    && !rest[4..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+                {
+                    pending_menu = true;
+                    i += "Menu".len();
+                } else if boundary && rest.starts_with("name:") {
+                    pending_name = true;
+                    i += "name:".len();
+                } else if boundary
+                    && (rest.starts_with("MenuItem::action(")
+                        || rest.starts_with("MenuItem::os_action("))
+                {
+                    pending_label = true;
+                    i += "MenuItem::".len();
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    }
+    labels
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Reads the Rust string literal starting at the quote at `start`, returning
+/// its contents and the index just past the closing quote.
+fn read_string_literal(source: &str, start: usize) -> (String, usize) {
+    let mut literal = String::new();
+    let mut chars = source[start + 1..].char_indices();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    literal.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                }
+            }
+            '"' => return (literal, start + 1 + offset + 1),
+            other => literal.push(other),
+        }
+    }
+    (literal, source.len())
+}
+
+/// Loads the translation file for a pack directory or a bare translation
+/// file, determining the language from pack metadata or the file name when
+/// none is given.
+fn load_translation_file(pack: &Path, language: Option<String>) -> Result<TranslationFile> {
+    let (language, file_path) = if pack.is_dir() {
+        let metadata = PackMetadata::load(pack)?;
+        metadata.check_schema_compatibility()?;
+        let language = language.unwrap_or(metadata.language);
+        let translation_path = pack.join("translation.json");
+        if !translation_path.exists() {
+            let split_dir = pack.join(i18n::pack::SPLIT_TRANSLATIONS_DIR_NAME);
+            if split_dir.is_dir() {
+                return TranslationFile::load_merged(language, &split_dir);
+            }
+        }
+        (language, translation_path)
+    } else {
+        let language = match language {
+            Some(language) => language,
+            None => {
+                let stem = pack
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or_default();
+                if stem.is_empty() || stem == "translation" {
+                    bail!("cannot infer the language from the file name; pass --language");
+                }
+                stem.to_string()
+            }
+        };
+        (language, pack.to_path_buf())
+    };
+    TranslationFile::load(language, &file_path)
+}
+
+fn validate(
+    pack: &Path,
+    language: Option<String>,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<bool> {
+    let file = load_translation_file(pack, language)?;
+    let review = if pack.is_dir() {
+        ReviewMetadata::load(pack)?
+    } else {
+        None
+    };
+    let report = I18NValidator::new().validate_with_review(&file, review.as_ref());
+
+    match format {
+        OutputFormat::Json => println!("{}", report.to_json()?),
+        OutputFormat::Text => {
+            for issue in &report.issues {
+                let severity = match issue.severity {
+                    i18n::validator::Severity::Error => "error",
+                    i18n::validator::Severity::Warning => "warning",
+                };
+                let mut line = format!("{severity}: {} — {}", issue.key, issue.message);
+                if let Some(fix) = &issue.suggested_fix {
+                    line.push_str(&format!(" (suggested: {fix})"));
+                }
+                println!("{line}");
+            }
+            if !quiet {
+                println!(
+                    "{}: {} error(s), {} warning(s)",
+                    report.language,
+                    report.errors().count(),
+                    report.warnings().count()
+                );
+            }
+        }
+    }
+    Ok(!report.has_errors())
+}
+
+/// The repo-relative location of the reference string table, used to read
+/// historical versions out of git.
+const DEFAULTS_SOURCE_PATH: &str = "crates/i18n/src/defaults.rs";
+
+#[derive(Serialize)]
+struct ChangedReport {
+    since: String,
+    added: BTreeMap<String, String>,
+    removed: BTreeMap<String, String>,
+    /// Keys whose English text changed: key → (old text, new text).
+    reworded: BTreeMap<String, (String, String)>,
+}
+
+fn changed(base_dir: &Path, since: &str, format: OutputFormat, quiet: bool) -> Result<bool> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(base_dir)
+        .args(["show", &format!("{since}:{DEFAULTS_SOURCE_PATH}")])
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git show {since}:{DEFAULTS_SOURCE_PATH} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let old_source = String::from_utf8(output.stdout).context("git output was not UTF-8")?;
+    let old_texts = parse_default_texts(&old_source);
+    let new_texts = default_texts();
+
+    let mut report = ChangedReport {
+        since: since.to_string(),
+        added: BTreeMap::new(),
+        removed: BTreeMap::new(),
+        reworded: BTreeMap::new(),
+    };
+    for (key, new_text) in new_texts {
+        match old_texts.get(*key) {
+            None => {
+                report.added.insert(key.to_string(), new_text.to_string());
+            }
+            Some(old_text) if old_text != new_text => {
+                report.reworded.insert(
+                    key.to_string(),
+                    (old_text.to_string(), new_text.to_string()),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, old_text) in &old_texts {
+        if !new_texts.contains_key(key.as_str()) {
+            report.removed.insert(key.clone(), old_text.clone());
+        }
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => {
+            for (key, text) in &report.added {
+                println!("added: {key} = {text:?}");
+            }
+            for (key, text) in &report.removed {
+                println!("removed: {key} (was {text:?})");
+            }
+            for (key, (old_text, new_text)) in &report.reworded {
+                println!("reworded: {key}: {old_text:?} → {new_text:?}");
+            }
+            if !quiet {
+                println!(
+                    "{} added, {} removed, {} reworded since {since}",
+                    report.added.len(),
+                    report.removed.len(),
+                    report.reworded.len()
+                );
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Parses the `DEFAULT_TEXTS` table out of a historical `defaults.rs`: pairs
+/// of string literals of the form `("key", "text"),`.
+fn parse_default_texts(source: &str) -> BTreeMap<String, String> {
+    let mut texts = BTreeMap::new();
+    for line in source.lines() {
+        let line = line.trim();
+        let Some(rest) = line.strip_prefix("(\"i18n.") else {
+            continue;
+        };
+        let mut literals = Vec::new();
+        let mut rest = format!("\"i18n.{rest}");
+        while let Some(start) = rest.find('"') {
+            let mut value = String::new();
+            let mut chars = rest[start + 1..].char_indices();
+            let mut end = None;
+            while let Some((index, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        end = Some(start + 1 + index + 1);
+                        break;
+                    }
+                    '\\' => match chars.next() {
+                        Some((_, 'n')) => value.push('\n'),
+                        Some((_, 't')) => value.push('\t'),
+                        Some((_, escaped)) => value.push(escaped),
+                        None => break,
+                    },
+                    c => value.push(c),
+                }
+            }
+            let Some(end) = end else { break };
+            literals.push(value);
+            rest = rest[end..].to_string();
+        }
+        if literals.len() >= 2 {
+            let mut literals = literals.into_iter();
+            if let (Some(key), Some(text)) = (literals.next(), literals.next()) {
+                texts.insert(key, text);
+            }
+        }
+    }
+    texts
+}
+
+fn merge(base: &Path, ours: &Path, theirs: &Path, output: &Path, quiet: bool) -> Result<bool> {
+    let read = |path: &Path| -> Result<serde_json::Map<String, serde_json::Value>> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    };
+    let (merged, conflicts) = merge_translation_maps(&read(base)?, &read(ours)?, &read(theirs)?);
+
+    let mut contents = serde_json::to_string_pretty(&merged)?;
+    contents.push('\n');
+    std::fs::write(output, contents)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+
+    for key in &conflicts {
+        println!("conflict: {key}");
+    }
+    if !quiet {
+        println!("merged {} key(s), {} conflict(s)", merged.len(), conflicts.len());
+    }
+    Ok(conflicts.is_empty())
+}
+
+/// Merges `ours` and `theirs` against their common ancestor `base`, key by
+/// key. Returns the merged map (ours' key order, then theirs' additions) and
+/// the keys that conflicted.
+fn merge_translation_maps(
+    base: &serde_json::Map<String, serde_json::Value>,
+    ours: &serde_json::Map<String, serde_json::Value>,
+    theirs: &serde_json::Map<String, serde_json::Value>,
+) -> (serde_json::Map<String, serde_json::Value>, Vec<String>) {
+    let mut merged = serde_json::Map::new();
+    let mut conflicts = Vec::new();
+
+    let mut keys: Vec<&String> = ours.keys().collect();
+    keys.extend(theirs.keys().filter(|key| !ours.contains_key(*key)));
+
+    for key in keys {
+        let in_base = base.get(key);
+        let in_ours = ours.get(key);
+        let in_theirs = theirs.get(key);
+
+        let resolved = if in_ours == in_theirs {
+            in_ours.cloned()
+        } else if in_ours == in_base {
+            in_theirs.cloned()
+        } else if in_theirs == in_base {
+            in_ours.cloned()
+        } else {
+            conflicts.push(key.clone());
+            let render = |value: Option<&serde_json::Value>| match value {
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(other) => other.to_string(),
+                None => "(deleted)".to_string(),
+            };
+            Some(serde_json::Value::String(format!(
+                "<<<<<<< ours\n{}\n=======\n{}\n>>>>>>> theirs",
+                render(in_ours),
+                render(in_theirs)
+            )))
+        };
+        if let Some(value) = resolved {
+            merged.insert(key.clone(), value);
+        }
+    }
+
+    (merged, conflicts)
+}
+
+#[derive(Default, Serialize)]
+struct CategoryStats {
+    translated: usize,
+    /// Entries whose value still equals the English default — usually a
+    /// pre-filled template line the translator hasn't reached yet.
+    fuzzy: usize,
+    untranslated: usize,
+}
+
+#[derive(Serialize)]
+struct ExtremeEntry {
+    key: String,
+    value: String,
+    chars: usize,
+}
+
+#[derive(Default, Serialize)]
+struct ReviewStats {
+    reviewed: usize,
+    needs_review: usize,
+    machine_translated: usize,
+    /// Entries present in the translation file but absent from the sidecar.
+    untracked: usize,
+}
+
+#[derive(Serialize)]
+struct StatsReport {
+    language: String,
+    categories: BTreeMap<String, CategoryStats>,
+    totals: CategoryStats,
+    /// Present only when the pack ships a `translation.meta.json` sidecar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    review: Option<ReviewStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    longest: Option<ExtremeEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shortest: Option<ExtremeEntry>,
+}
+
+fn stats(pack: &Path, language: Option<String>, format: OutputFormat) -> Result<bool> {
+    let file = load_translation_file(pack, language)?;
+    let defaults = default_texts();
+
+    let mut categories: BTreeMap<String, CategoryStats> = BTreeMap::new();
+    let mut totals = CategoryStats::default();
+    let mut translated_entries: Vec<(&str, &str)> = Vec::new();
+
+    let mut record = |key: &str, state: fn(&mut CategoryStats) -> &mut usize| {
+        let category = TranslationCategory::for_key(key).to_string();
+        *state(categories.entry(category).or_default()) += 1;
+        *state(&mut totals) += 1;
+    };
+
+    for (key, default) in defaults {
+        match file.get(key) {
+            Some(value) if value == *default => record(key, |stats| &mut stats.fuzzy),
+            Some(value) => {
+                translated_entries.push((key, value));
+                record(key, |stats| &mut stats.translated);
+            }
+            None => record(key, |stats| &mut stats.untranslated),
+        }
+    }
+    // Entries beyond the reference set (extension namespaces, stale keys)
+    // have no English default to compare against.
+    for (key, value) in &file.entries {
+        if !defaults.contains_key(key.as_str()) {
+            if let Some(value) = value.as_str() {
+                translated_entries.push((key, value));
+                record(key, |stats| &mut stats.translated);
+            }
+        }
+    }
+
+    let extreme = |entry: Option<&(&str, &str)>| {
+        entry.map(|(key, value)| ExtremeEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+            chars: value.chars().count(),
+        })
+    };
+    let longest = extreme(
+        translated_entries
+            .iter()
+            .max_by_key(|(_, value)| value.chars().count()),
+    );
+    let shortest = extreme(
+        translated_entries
+            .iter()
+            .min_by_key(|(_, value)| value.chars().count()),
+    );
+
+    let review = if pack.is_dir() {
+        ReviewMetadata::load(pack)?.map(|metadata| {
+            let mut stats = ReviewStats::default();
+            for key in file.entries.keys() {
+                match metadata.entry(key).map(|entry| entry.status) {
+                    Some(ReviewStatus::Reviewed) => stats.reviewed += 1,
+                    Some(ReviewStatus::NeedsReview) => stats.needs_review += 1,
+                    Some(ReviewStatus::MachineTranslated) => stats.machine_translated += 1,
+                    None => stats.untracked += 1,
+                }
+            }
+            stats
+        })
+    } else {
+        None
+    };
+
+    let report = StatsReport {
+        language: file.language.clone(),
+        categories,
+        totals,
+        review,
+        longest,
+        shortest,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => {
+            println!("{}:", report.language);
+            for (category, stats) in &report.categories {
+                println!(
+                    "  {category}: {} translated, {} fuzzy, {} untranslated",
+                    stats.translated, stats.fuzzy, stats.untranslated
+                );
+            }
+            println!(
+                "  total: {} translated, {} fuzzy, {} untranslated",
+                report.totals.translated, report.totals.fuzzy, report.totals.untranslated
+            );
+            if let Some(review) = &report.review {
+                println!(
+                    "  review: {} reviewed, {} needs review, {} machine-translated, {} untracked",
+                    review.reviewed,
+                    review.needs_review,
+                    review.machine_translated,
+                    review.untracked
+                );
+            }
+            if let Some(longest) = &report.longest {
+                println!(
+                    "  longest: {} ({} chars): {:?}",
+                    longest.key, longest.chars, longest.value
+                );
+            }
+            if let Some(shortest) = &report.shortest {
+                println!(
+                    "  shortest: {} ({} chars): {:?}",
+                    shortest.key, shortest.chars, shortest.value
+                );
+            }
+        }
+    }
+    Ok(true)
+}
+
+#[derive(Serialize)]
+struct EffectiveReport {
+    /// The language the table was resolved for: the installed pack language
+    /// the requested tag negotiated to, or the tag itself when no pack
+    /// matched.
+    language: String,
+    entries: BTreeMap<String, String>,
+}
+
+fn export_effective(
+    language: &str,
+    packs_dir: &Path,
+    user_overrides: Option<&Path>,
+    output: Option<&Path>,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<bool> {
+    let mut packs = Vec::new();
+    if packs_dir.is_dir() {
+        for entry in std::fs::read_dir(packs_dir)
+            .with_context(|| format!("failed to read {}", packs_dir.display()))?
+        {
+            let entry = entry?;
+            if let Ok(metadata) = PackMetadata::load(&entry.path()) {
+                packs.push((metadata.language, entry.path()));
+            }
+        }
+    }
+    let languages: Vec<String> = packs.iter().map(|(language, _)| language.clone()).collect();
+    let resolved = i18n::lang_codes::negotiate([language], &languages).cloned();
+
+    // The table comes from the same manager the runtime resolves through,
+    // with the matched pack registered under a scoped source id.
+    const SOURCE_ID: &str = "export-effective";
+    let manager = i18n::I18nManager::global();
+    if let Some(resolved) = &resolved {
+        let (_, pack_dir) = packs
+            .iter()
+            .find(|(language, _)| language == resolved)
+            .context("negotiated a language with no backing pack")?;
+        let file = load_translation_file(pack_dir, Some(resolved.clone()))?;
+        manager.register_translations(
+            SOURCE_ID,
+            resolved,
+            file.entries.iter().filter_map(|(key, value)| {
+                value.as_str().map(|value| (key.clone(), value.to_string()))
+            }),
+        );
+    }
+    if let Some(user_overrides) = user_overrides {
+        manager.load_user_overrides(user_overrides)?;
+    }
+    let effective_language = resolved.unwrap_or_else(|| language.to_string());
+    let report = EffectiveReport {
+        entries: manager.effective_translations(&effective_language),
+        language: effective_language,
+    };
+    manager.unregister_source(SOURCE_ID);
+
+    let mut rendered = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
+        OutputFormat::Text => {
+            let mut lines = String::new();
+            for (key, value) in &report.entries {
+                lines.push_str(&format!("{key} = {value:?}\n"));
+            }
+            lines.pop();
+            lines
+        }
+    };
+    rendered.push('\n');
+    match output {
+        Some(output) => {
+            std::fs::write(output, rendered)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            if !quiet {
+                println!(
+                    "exported the effective {} table to {}",
+                    report.language,
+                    output.display()
+                );
+            }
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(true)
+}
+
+fn check_extensions(extensions_dir: &Path, quiet: bool) -> Result<bool> {
+    let mut clean = true;
+    let mut packs_checked = 0;
+    // Language → extension ids providing it, in load (sorted) order.
+    let mut providers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    let mut entries: Vec<_> = std::fs::read_dir(extensions_dir)
+        .with_context(|| format!("failed to read {}", extensions_dir.display()))?
+        .collect::<std::io::Result<_>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        if !entry.path().is_dir() || !dir_name.starts_with("i18n-") {
+            continue;
+        }
+        packs_checked += 1;
+
+        let manifest_path = entry.path().join("extension.toml");
+        let manifest: toml::Value = match std::fs::read_to_string(&manifest_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| Ok(toml::from_str(&contents)?))
+        {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                println!("{dir_name}: unreadable extension.toml: {error:#}");
+                clean = false;
+                continue;
+            }
+        };
+        let id = manifest.get("id").and_then(|id| id.as_str()).unwrap_or("");
+        if id != dir_name {
+            println!("{dir_name}: manifest id {id:?} doesn't match the directory name");
+            clean = false;
+        }
+        if id
+            .strip_prefix("i18n-")
+            .is_none_or(|rest| rest.is_empty() || rest.chars().any(|c| c.is_ascii_uppercase()))
+        {
+            println!("{dir_name}: id must have the form i18n-<language>, lowercase");
+            clean = false;
+        }
+        if manifest.get("version").and_then(|version| version.as_str()).is_none() {
+            println!("{dir_name}: manifest is missing a version");
+            clean = false;
+        }
+
+        let translations_dir = entry.path().join("resources").join("translations");
+        if !translations_dir.is_dir() {
+            println!("{dir_name}: no resources/translations directory");
+            clean = false;
+            continue;
+        }
+        let mut translation_files: Vec<_> = std::fs::read_dir(&translations_dir)?
+            .collect::<std::io::Result<_>>()?;
+        translation_files.sort_by_key(|entry| entry.file_name());
+        for file_entry in translation_files {
+            let path = file_entry.path();
+            if path.extension() != Some("json".as_ref()) {
+                continue;
+            }
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            providers
+                .entry(language.to_string())
+                .or_default()
+                .push(dir_name.clone());
+
+            match TranslationFile::load(language, &path) {
+                Ok(file) => {
+                    let report = I18NValidator::new().validate(&file);
+                    if report.has_errors() {
+                        for issue in report.errors() {
+                            println!("{dir_name}/{language}: {} — {}", issue.key, issue.message);
+                        }
+                        clean = false;
+                    }
+                }
+                Err(error) => {
+                    println!("{dir_name}/{language}: {error:#}");
+                    clean = false;
+                }
+            }
+        }
+    }
+
+    for (language, packs) in &providers {
+        if packs.len() > 1 {
+            // Extensions register in sorted order and later registrations
+            // win, so the last pack's strings take effect.
+            if let Some(winner) = packs.last() {
+                println!(
+                    "duplicate language {language}: provided by {}; {winner} wins at runtime",
+                    packs.join(", ")
+                );
+            }
+        }
+    }
+
+    if !quiet {
+        println!("checked {packs_checked} pack(s)");
+    }
+    Ok(clean)
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug)]
+struct KeyUsage {
+    file: String,
+    line: usize,
+}
+
+fn usage_index(base_dir: &Path, paths: &[PathBuf], output: &Path, quiet: bool) -> Result<bool> {
+    let mut index: BTreeMap<String, Vec<KeyUsage>> = BTreeMap::new();
+    for path in paths {
+        for entry in walkdir::WalkDir::new(path) {
+            let entry = entry?;
+            if !entry.file_type().is_file()
+                || entry.path().extension().is_none_or(|ext| ext != "rs")
+                || entry.path().ends_with("i18n/src/defaults.rs")
+            {
+                continue;
+            }
+            let source = std::fs::read_to_string(entry.path())
+                .with_context(|| format!("failed to read {}", entry.path().display()))?;
+            let file = entry
+                .path()
+                .strip_prefix(base_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            for (key, line) in extract_key_usages(&source) {
+                index.entry(key).or_default().push(KeyUsage {
+                    file: file.clone(),
+                    line,
+                });
+            }
+        }
+    }
+
+    let mut contents = serde_json::to_string_pretty(&index)?;
+    contents.push('\n');
+    std::fs::write(output, contents)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+    if !quiet {
+        println!(
+            "indexed {} key(s) into {}",
+            index.len(),
+            output.display()
+        );
+    }
+    Ok(true)
+}
+
+/// Extracts conforming key literals along with their 1-based line numbers.
+/// Covers both `t!("…")` call sites and bare key literals.
+fn extract_key_usages(source: &str) -> Vec<(String, usize)> {
+    let mut usages = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        for key in extract_key_literals(line) {
+            usages.push((key, index + 1));
+        }
+    }
+    usages
+}
+
+/// Renders a pack as a gettext PO file. The translation key becomes
+/// `msgctxt`, which is what disambiguates entries whose English text is
+/// identical; recorded translator context becomes `#.` extracted comments.
+/// Platform variants (`key@macos` and friends) follow their base entry with
+/// the suffixed key as `msgctxt` and the base key's English as `msgid`.
+fn render_po(file: &TranslationFile) -> String {
+    let mut output = format!(
+        "msgid \"\"\nmsgstr \"\"\n\"Language: {}\\n\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n",
+        file.language
+    );
+    for (key, default) in i18n::defaults::DEFAULT_TEXTS {
+        output.push('\n');
+        if let Some(context) = i18n::defaults::key_context(key) {
+            output.push_str(&format!("#. {context}\n"));
+        }
+        output.push_str(&format!("msgctxt \"{}\"\n", po_escape(key)));
+        output.push_str(&format!("msgid \"{}\"\n", po_escape(default)));
+        let translation = file.get(key).unwrap_or("");
+        output.push_str(&format!("msgstr \"{}\"\n", po_escape(translation)));
+        for platform in i18n::keys::PLATFORM_SUFFIXES {
+            let variant = format!("{key}@{platform}");
+            let Some(translation) = file.get(&variant) else {
+                continue;
+            };
+            output.push('\n');
+            output.push_str(&format!("#. Only on {platform}\n"));
+            output.push_str(&format!("msgctxt \"{}\"\n", po_escape(&variant)));
+            output.push_str(&format!("msgid \"{}\"\n", po_escape(default)));
+            output.push_str(&format!("msgstr \"{}\"\n", po_escape(translation)));
+        }
+    }
+    output
+}
+
+fn po_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[derive(Serialize)]
+struct TodoEntry {
+    key: String,
+    english: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+    /// The current draft, present when the entry exists but still needs
+    /// work. This is the field a translator fills in or revises.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translation: Option<String>,
+    status: String,
+}
+
+/// Collects the reference entries a translator still has work on: keys the
+/// file doesn't translate, keys whose value is the English text verbatim,
+/// and keys the review sidecar flags as machine-translated or needing
+/// review. Reference order, like the other exports.
+fn todo_entries(file: &TranslationFile, review: Option<&ReviewMetadata>) -> Vec<TodoEntry> {
+    let mut entries = Vec::new();
+    for (key, default) in i18n::defaults::DEFAULT_TEXTS {
+        let current = file.get(key);
+        let status = match current {
+            None => "missing",
+            Some(value) if value == *default => "fuzzy",
+            Some(_) => match review.and_then(|review| review.entry(key)) {
+                Some(entry) if entry.status != ReviewStatus::Reviewed => entry.status.label(),
+                _ => continue,
+            },
+        };
+        entries.push(TodoEntry {
+            key: (*key).to_string(),
+            english: (*default).to_string(),
+            context: i18n::defaults::key_context(key).map(String::from),
+            translation: current.map(String::from),
+            status: status.to_string(),
+        });
+    }
+    entries
+}
+
+fn render_todo_po(language: &str, entries: &[TodoEntry]) -> String {
+    let mut output = format!(
+        "msgid \"\"\nmsgstr \"\"\n\"Language: {language}\\n\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n"
+    );
+    for entry in entries {
+        output.push('\n');
+        if let Some(context) = &entry.context {
+            output.push_str(&format!("#. {context}\n"));
+        }
+        // Gettext tools read the fuzzy flag as "present, but needs a human
+        // pass" — exactly what a draft awaiting review is.
+        if entry.translation.is_some() {
+            output.push_str("#, fuzzy\n");
+        }
+        output.push_str(&format!("msgctxt \"{}\"\n", po_escape(&entry.key)));
+        output.push_str(&format!("msgid \"{}\"\n", po_escape(&entry.english)));
+        let translation = entry.translation.as_deref().unwrap_or("");
+        output.push_str(&format!("msgstr \"{}\"\n", po_escape(translation)));
+    }
+    output
+}
+
+fn render_todo_csv(entries: &[TodoEntry]) -> String {
+    let mut output = String::from("key,english,context,translation,status\n");
+    for entry in entries {
+        output.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_escape(&entry.key),
+            csv_escape(&entry.english),
+            csv_escape(entry.context.as_deref().unwrap_or("")),
+            csv_escape(entry.translation.as_deref().unwrap_or("")),
+            csv_escape(&entry.status),
+        ));
+    }
+    output
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn export_missing(
+    pack: &Path,
+    language: Option<String>,
+    format: ExportFormat,
+    output: Option<&Path>,
+    quiet: bool,
+) -> Result<bool> {
+    let file = load_translation_file(pack, language)?;
+    let review = if pack.is_dir() {
+        ReviewMetadata::load(pack)?
+    } else {
+        None
+    };
+    let entries = todo_entries(&file, review.as_ref());
+    let rendered = match format {
+        ExportFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&entries)?;
+            json.push('\n');
+            json
+        }
+        ExportFormat::Po => render_todo_po(&file.language, &entries),
+        ExportFormat::Csv => render_todo_csv(&entries),
+    };
+    match output {
+        Some(output) => {
+            std::fs::write(output, rendered)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            if !quiet {
+                println!(
+                    "exported {} {} entries needing work to {}",
+                    entries.len(),
+                    file.language,
+                    output.display()
+                );
+            }
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(true)
+}
+
+fn import_partial(pack: &Path, input: &Path, quiet: bool) -> Result<bool> {
+    let translation_path = pack.join("translation.json");
+    anyhow::ensure!(
+        translation_path.exists(),
+        "{} keeps no single translation.json; merging into split packs isn't supported",
+        pack.display()
+    );
+    let contents = std::fs::read_to_string(input)
+        .with_context(|| format!("failed to read {}", input.display()))?;
+    let imported = match input.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => parse_json_translations(&contents)?,
+        Some("po") => parse_po_translations(&contents),
+        Some("csv") => parse_csv_translations(&contents)?,
+        _ => bail!(
+            "unrecognized import format for {}; expected a .json, .po, or .csv file",
+            input.display()
+        ),
+    };
+
+    let defaults = default_texts();
+    let mut accepted: Vec<(String, String)> = Vec::new();
+    let mut skipped: Vec<(String, String)> = Vec::new();
+    for (key, value) in imported {
+        // Untouched entries come back empty; they're not a merge, and not
+        // an error either.
+        if value.is_empty() {
+            continue;
+        }
+        // Platform variants are checked against their base key's default.
+        let (base, _) = i18n::keys::split_platform(&key);
+        if let Some(default) = defaults.get(base) {
+            let expected = placeholders(default);
+            let found = placeholders(&value);
+            if expected != found {
+                skipped.push((
+                    key,
+                    format!("placeholders {found:?} don't match the English text's {expected:?}"),
+                ));
+                continue;
+            }
+        }
+        accepted.push((key, value));
+    }
+
+    if !accepted.is_empty() {
+        let source = std::fs::read_to_string(&translation_path)
+            .with_context(|| format!("failed to read {}", translation_path.display()))?;
+        let mut document = jsonc::Document::parse(&source)
+            .with_context(|| format!("failed to parse {}", translation_path.display()))?;
+        for (key, value) in &accepted {
+            let value = serde_json::Value::from(value.as_str());
+            match document.entries.iter_mut().find(|entry| entry.key == *key) {
+                Some(entry) => entry.value = value,
+                None => document.entries.push(jsonc::DocumentEntry {
+                    leading_comments: Vec::new(),
+                    key: key.clone(),
+                    value,
+                }),
+            }
+        }
+        back_up(&translation_path)?;
+        std::fs::write(&translation_path, document.render())
+            .with_context(|| format!("failed to write {}", translation_path.display()))?;
+    }
+
+    for (key, reason) in &skipped {
+        eprintln!("skipped {key}: {reason}");
+    }
+    if !quiet {
+        println!(
+            "merged {} entries into {}, skipped {}",
+            accepted.len(),
+            translation_path.display(),
+            skipped.len()
+        );
+    }
+    Ok(skipped.is_empty())
+}
+
+/// Reads translations back from a JSON export: either the `export-missing`
+/// array of entry objects, taking each one's `translation` field, or a
+/// plain `{"key": "value"}` map.
+fn parse_json_translations(contents: &str) -> Result<Vec<(String, String)>> {
+    let value: serde_json::Value =
+        serde_json::from_str(contents).context("failed to parse JSON")?;
+    match value {
+        serde_json::Value::Object(map) => Ok(map
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value.as_str()?.to_string())))
+            .collect()),
+        serde_json::Value::Array(entries) => Ok(entries
+            .into_iter()
+            .filter_map(|entry| {
+                let key = entry.get("key")?.as_str()?.to_string();
+                let translation = entry.get("translation")?.as_str()?.to_string();
+                Some((key, translation))
+            })
+            .collect()),
+        _ => bail!("expected a JSON object or an array of export entries"),
+    }
+}
+
+/// Extracts `(msgctxt, msgstr)` pairs from a PO file, tolerating the
+/// multi-line string wrapping translation editors emit. Entries without a
+/// `msgctxt`, such as the header, carry no key and are skipped.
+fn parse_po_translations(contents: &str) -> Vec<(String, String)> {
+    enum Field {
+        None,
+        Ctxt,
+        Id,
+        Str,
+    }
+    let mut entries = Vec::new();
+    let mut key: Option<String> = None;
+    let mut translation = String::new();
+    let mut field = Field::None;
+    // The sentinel line flushes the final entry.
+    for line in contents.lines().chain(["msgctxt \"\""]) {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("msgctxt ") {
+            if let Some(previous) = key.take()
+                && !previous.is_empty()
+            {
+                entries.push((previous, std::mem::take(&mut translation)));
+            }
+            translation.clear();
+            key = Some(po_unescape(rest));
+            field = Field::Ctxt;
+        } else if line.starts_with("msgid ") {
+            field = Field::Id;
+        } else if let Some(rest) = line.strip_prefix("msgstr ") {
+            translation = po_unescape(rest);
+            field = Field::Str;
+        } else if line.starts_with('"') {
+            match field {
+                Field::Ctxt => {
+                    if let Some(key) = &mut key {
+                        key.push_str(&po_unescape(line));
+                    }
+                }
+                Field::Str => translation.push_str(&po_unescape(line)),
+                Field::Id | Field::None => {}
+            }
+        }
+    }
+    entries
+}
+
+/// Undoes [`po_escape`] on one quoted PO string segment.
+fn po_unescape(literal: &str) -> String {
+    let literal = literal.trim();
+    let inner = literal
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(literal);
+    let mut result = String::with_capacity(inner.len());
+    let mut characters = inner.chars();
+    while let Some(character) = characters.next() {
+        if character == '\\' {
+            match characters.next() {
+                Some('n') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(character);
+        }
+    }
+    result
+}
+
+/// Splits CSV text into rows of fields, honoring quoted fields containing
+/// commas, doubled quotes, and newlines.
+fn parse_csv(contents: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut characters = contents.chars().peekable();
+    while let Some(character) = characters.next() {
+        if in_quotes {
+            if character == '"' {
+                if characters.peek() == Some(&'"') {
+                    characters.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(character);
+            }
+        } else {
+            match character {
+                '"' if field.is_empty() => in_quotes = true,
+                ',' => row.push(std::mem::take(&mut field)),
+                '\r' => {}
+                '\n' => {
+                    row.push(std::mem::take(&mut field));
+                    if row.iter().any(|field| !field.is_empty()) {
+                        rows.push(std::mem::take(&mut row));
+                    } else {
+                        row.clear();
+                    }
+                }
+                _ => field.push(character),
+            }
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Reads the `key` and `translation` columns back from a CSV export,
+/// located by the header row so reordered or trimmed columns still work.
+fn parse_csv_translations(contents: &str) -> Result<Vec<(String, String)>> {
+    let mut rows = parse_csv(contents).into_iter();
+    let header = rows.next().context("the CSV file is empty")?;
+    let key_column = header
+        .iter()
+        .position(|name| name == "key")
+        .context("the CSV file has no `key` column")?;
+    let translation_column = header
+        .iter()
+        .position(|name| name == "translation")
+        .context("the CSV file has no `translation` column")?;
+    Ok(rows
+        .filter_map(|row| {
+            Some((
+                row.get(key_column)?.clone(),
+                row.get(translation_column)?.clone(),
+            ))
+        })
+        .collect())
+}
+
+fn run_sync(config_path: &Path, push: bool, pull: bool, quiet: bool) -> Result<bool> {
+    let config = sync::SyncConfig::load(config_path)?;
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+    let user_agent = format!("zed-i18n/{}", env!("CARGO_PKG_VERSION"));
+    let http: std::sync::Arc<dyn http_client::HttpClient> =
+        std::sync::Arc::new(reqwest_client::ReqwestClient::user_agent(&user_agent)?);
+    let backend = config.backend(http)?;
+
+    smol::block_on(async {
+        if push {
+            backend
+                .push_reference_keys(i18n::defaults::DEFAULT_TEXTS)
+                .await?;
+            if !quiet {
+                println!(
+                    "pushed {} reference key(s) to {}",
+                    i18n::defaults::DEFAULT_TEXTS.len(),
+                    backend.name()
+                );
+            }
+        }
+        if pull {
+            for pack in &config.packs {
+                let pack_dir = resolve(config_dir, pack.path.clone());
+                let translation_path = pack_dir.join("translation.json");
+                let contents = std::fs::read_to_string(&translation_path).with_context(|| {
+                    format!("failed to read {}", translation_path.display())
+                })?;
+                let mut entries: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(&contents).with_context(|| {
+                        format!("failed to parse {}", translation_path.display())
+                    })?;
+
+                let units = backend.pull_translations(&pack.language).await?;
+                let stats = sync::apply_remote_units(&mut entries, &units);
+
+                let mut output = serde_json::to_string_pretty(&entries)?;
+                output.push('\n');
+                std::fs::write(&translation_path, output).with_context(|| {
+                    format!("failed to write {}", translation_path.display())
+                })?;
+                if !quiet {
+                    println!(
+                        "{}: applied {} translation(s), {} still under review",
+                        pack.language, stats.applied, stats.needs_review
+                    );
+                }
+            }
+        }
+        Ok(true)
+    })
+}
+
+fn package(pack_dir: &Path, output: Option<PathBuf>, quiet: bool) -> Result<bool> {
+    let mut metadata = PackMetadata::load(pack_dir)?;
+    metadata.check_schema_compatibility()?;
+
+    // extension.toml is authoritative for the version when the pack ships as
+    // an extension; metadata.json is stamped to match.
+    let extension_toml = pack_dir.join("extension.toml");
+    if extension_toml.exists() {
+        let contents = std::fs::read_to_string(&extension_toml)
+            .with_context(|| format!("failed to read {}", extension_toml.display()))?;
+        let manifest: toml::Value =
+            toml::from_str(&contents).context("failed to parse extension.toml")?;
+        if let Some(version) = manifest.get("version").and_then(|version| version.as_str()) {
+            metadata.version = version.to_string();
+        }
+    }
+
+    let file = load_translation_file(pack_dir, None)?;
+    let report = I18NValidator::new().validate(&file);
+    if report.has_errors() {
+        for issue in report.errors() {
+            println!("error: {} — {}", issue.key, issue.message);
+        }
+        println!("refusing to package a pack with validation errors");
+        return Ok(false);
+    }
+
+    let staging = tempfile::tempdir().context("failed to create staging directory")?;
+    for entry in walkdir::WalkDir::new(pack_dir) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(pack_dir)
+            .context("walked file outside the pack directory")?;
+        if relative.as_os_str().is_empty() || relative == Path::new(CHECKSUM_MANIFEST_NAME) {
+            continue;
+        }
+        let target = staging.path().join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("failed to stage {}", relative.display()))?;
+        }
+    }
+
+    let mut metadata_json = serde_json::to_string_pretty(&metadata)?;
+    metadata_json.push('\n');
+    std::fs::write(staging.path().join(i18n::pack::METADATA_FILE_NAME), metadata_json)?;
+    let translation_path = staging.path().join("translation.json");
+    let (_, canonical) = canonical_translation_source(&translation_path)?;
+    std::fs::write(&translation_path, canonical)?;
+
+    let mut manifest = String::new();
+    for entry in walkdir::WalkDir::new(staging.path()).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(staging.path())
+            .context("walked file outside the staging directory")?;
+        let contents = std::fs::read(entry.path())?;
+        let digest = hex::encode(sha2::Sha256::digest(&contents));
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        manifest.push_str(&format!("{digest}  {relative}\n"));
+    }
+    std::fs::write(staging.path().join(CHECKSUM_MANIFEST_NAME), manifest)?;
+
+    let output = match output {
+        Some(output) => output,
+        None => {
+            let dir_name = pack_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .context("pack directory has no usable name")?;
+            pack_dir.with_file_name(format!("{dir_name}-{}.tar.gz", metadata.version))
+        }
+    };
+    let tar_output = std::process::Command::new("tar")
+        .arg("-czf")
+        .arg(&output)
+        .arg("-C")
+        .arg(staging.path())
+        .arg(".")
+        .output()
+        .context("failed to run tar")?;
+    if !tar_output.status.success() {
+        bail!(
+            "failed to create {}: {}",
+            output.display(),
+            String::from_utf8_lossy(&tar_output.stderr)
+        );
+    }
+
+    if !quiet {
+        println!("packaged {} {} at {}", metadata.name, metadata.version, output.display());
+    }
+    Ok(true)
+}
+
+#[derive(Serialize)]
+struct RenameReport {
+    old_key: String,
+    new_key: String,
+    defaults_updated: bool,
+    rust_files: Vec<String>,
+    translation_files: Vec<String>,
+}
+
+fn rename_key(
+    base_dir: &Path,
+    old: &str,
+    new: &str,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<bool> {
+    if let Err(violation) = i18n::keys::check_key(new) {
+        bail!("new key violates the naming scheme: {violation:?}");
+    }
+    if old == new {
+        bail!("the old and new keys are identical");
+    }
+    if default_texts().contains_key(new) {
+        bail!("key {new} is already in the reference set");
+    }
+
+    let needle = format!("\"{old}\"");
+    let replacement = format!("\"{new}\"");
+
+    // Stage every edit before writing anything, so a failure part-way
+    // through can't leave the tree half-renamed.
+    let mut staged: Vec<(PathBuf, String)> = Vec::new();
+    let mut report = RenameReport {
+        old_key: old.to_string(),
+        new_key: new.to_string(),
+        defaults_updated: false,
+        rust_files: Vec::new(),
+        translation_files: Vec::new(),
+    };
+
+    for entry in walkdir::WalkDir::new(base_dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let relative = path
+            .strip_prefix(base_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            let source = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            if source.contains(&needle) {
+                let mut updated = source.replace(&needle, &replacement);
+                if path.ends_with("i18n/src/defaults.rs") {
+                    report.defaults_updated = true;
+                    updated = add_key_alias(&updated, old, new);
+                } else {
+                    report.rust_files.push(relative);
+                }
+                staged.push((path.to_path_buf(), updated));
+            }
+        } else if is_translation_json(path) {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let mut document = jsonc::Document::parse(&contents)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            let mut renamed = false;
+            for entry in &mut document.entries {
+                if entry.key == old {
+                    entry.key = new.to_string();
+                    renamed = true;
+                }
+            }
+            if renamed {
+                report.translation_files.push(relative);
+                staged.push((path.to_path_buf(), document.render()));
+            }
+        }
+    }
+
+    if staged.is_empty() {
+        bail!("key {old} was not found anywhere under {}", base_dir.display());
+    }
+    for (path, contents) in &staged {
+        std::fs::write(path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => {
+            if !quiet {
+                if report.defaults_updated {
+                    println!("updated the reference set");
+                }
+                for file in &report.rust_files {
+                    println!("rewrote {file}");
+                }
+                for file in &report.translation_files {
+                    println!("renamed in {file}");
+                }
+                println!("renamed {old} to {new} in {} file(s)", staged.len());
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Appends `old → new` to the `KEY_ALIASES` table in the defaults source,
+/// so packs built against the old name keep working for the deprecation
+/// window. Existing aliases pointing at `old` were already redirected by the
+/// literal replacement.
+fn add_key_alias(source: &str, old: &str, new: &str) -> String {
+    let Some(table) = source.find("KEY_ALIASES") else {
+        return source.to_string();
+    };
+    let Some(close) = source[table..].find("];") else {
+        return source.to_string();
+    };
+    let mut result = source.to_string();
+    result.insert_str(table + close, &format!("    (\"{old}\", \"{new}\"),\n"));
+    result
+}
+
+/// Whether a path looks like a language pack's translation file: either a
+/// pack directory's `translation.json` or a data-only extension's
+/// `resources/translations/<language>.json`.
+fn is_translation_json(path: &Path) -> bool {
+    if path.extension().is_none_or(|ext| ext != "json") {
+        return false;
+    }
+    path.file_name().is_some_and(|name| name == "translation.json")
+        || path
+            .parent()
+            .is_some_and(|dir| dir.ends_with("resources/translations"))
+}
+
+/// Reads a translation file and returns its current contents alongside the
+/// canonical rendering: defaults order, two-space indentation, trailing
+/// newline.
+fn canonical_translation_source(path: &Path) -> Result<(String, String)> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let mut document = jsonc::Document::parse(&contents)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    document.sort_by_reference(i18n::defaults::DEFAULT_TEXTS);
+    // The schema declaration always stays at the top of the file.
+    if let Some(index) = document
+        .entries
+        .iter()
+        .position(|entry| entry.key == i18n::pack::SCHEMA_VERSION_KEY)
+    {
+        let entry = document.entries.remove(index);
+        document.entries.insert(0, entry);
+    }
+    let canonical = document.render();
+    Ok((contents, canonical))
+}
+
+/// Applies the registered key-schema migrations to a translation file in
+/// place, stamping the current schema version. Returns whether the file
+/// changed; a backup is written first when it does.
+fn migrate_translation_file(path: &Path) -> Result<bool> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let mut document = jsonc::Document::parse(&contents)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+
+    let declared_version = document
+        .entries
+        .iter()
+        .find(|entry| entry.key == i18n::pack::SCHEMA_VERSION_KEY)
+        .map(|entry| {
+            entry
+                .value
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .context("schema_version must be an integer")
+        })
+        .transpose()?;
+    let from_version = declared_version.unwrap_or(1);
+    if from_version > i18n::pack::CURRENT_SCHEMA_VERSION {
+        bail!(
+            "{} declares schema version {from_version}, newer than this tool supports ({})",
+            path.display(),
+            i18n::pack::CURRENT_SCHEMA_VERSION,
+        );
+    }
+
+    let mut renames: Vec<(&str, &str)> = Vec::new();
+    for migration in i18n::pack::SCHEMA_MIGRATIONS {
+        if migration.from >= from_version {
+            renames.extend(migration.renames.iter().copied());
+        }
+    }
+    let existing: BTreeSet<String> = document
+        .entries
+        .iter()
+        .map(|entry| entry.key.clone())
+        .collect();
+    let applicable = renames
+        .iter()
+        .any(|(old, _)| existing.contains(*old));
+    if !applicable && declared_version == Some(i18n::pack::CURRENT_SCHEMA_VERSION) {
+        return Ok(false);
+    }
+
+    let mut migrated = Vec::with_capacity(document.entries.len() + 1);
+    migrated.push(jsonc::DocumentEntry {
+        leading_comments: Vec::new(),
+        key: i18n::pack::SCHEMA_VERSION_KEY.to_string(),
+        value: serde_json::Value::from(i18n::pack::CURRENT_SCHEMA_VERSION),
+    });
+    for mut entry in std::mem::take(&mut document.entries) {
+        if entry.key == i18n::pack::SCHEMA_VERSION_KEY {
+            continue;
+        }
+        if let Some((_, new)) = renames.iter().find(|(old, _)| *old == entry.key) {
+            // A rename whose target already exists drops the old entry
+            // rather than clobbering the newer one.
+            if existing.contains(*new) {
+                continue;
+            }
+            entry.key = (*new).to_string();
+        }
+        migrated.push(entry);
+    }
+    document.entries = migrated;
+
+    back_up(path)?;
+    std::fs::write(path, document.render())
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+fn reorganize(path: &Path, dry_run: bool) -> Result<bool> {
+    let (contents, output) = canonical_translation_source(path)?;
+    if output == contents {
+        return Ok(false);
+    }
+    if dry_run {
+        print!("{output}");
+    } else {
+        back_up(path)?;
+        std::fs::write(path, output)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(true)
+}
+
+/// Copies the file to a timestamped sibling (`translation.json.<epoch>.bak`)
+/// so an in-place rewrite always leaves a recovery path.
+fn back_up(path: &Path) -> Result<PathBuf> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .context("system clock is before the unix epoch")?
+        .as_secs();
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid file name: {}", path.display()))?;
+    let backup = path.with_file_name(format!("{file_name}.{timestamp}.bak"));
+    std::fs::copy(path, &backup)
+        .with_context(|| format!("failed to back up {}", path.display()))?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn extracts_conforming_key_literals() {
+        let source = r#"
+            let a = t("i18n.menu.file.save");
+            let b = "i18n.not-a-key!";
+            let c = "i18n.status.ready";
+            let d = "plain string";
+        "#;
+        assert_eq!(
+            extract_key_literals(source),
+            vec![
+                "i18n.menu.file.save".to_string(),
+                "i18n.status.ready".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_accessibility_label_call_sites() {
+        let source = r#"
+            let a = a11y_label!("close_button");
+            let b = a11y_label!(
+                "language_picker",
+            );
+            let c = a11y_label!(not_a_literal);
+            let d = a11y_label!("not a path");
+        "#;
+        assert_eq!(
+            extract_a11y_call_sites(source),
+            vec![
+                "i18n.a11y.close_button".to_string(),
+                "i18n.a11y.language_picker".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_inline_defaults_from_call_sites() {
+        let source = r#"
+            let a = t_default!("i18n.editor.new_thing", "New Thing");
+            let b = t_default!(
+                "i18n.status.syncing",
+                "Syncing…",
+            );
+            let c = t_default!(not_a_literal, "ignored");
+            let d = t_default!("i18n.bad key", "also ignored");
+        "#;
+        assert_eq!(
+            extract_inline_defaults(source),
+            vec![
+                (
+                    "i18n.editor.new_thing".to_string(),
+                    "New Thing".to_string()
+                ),
+                ("i18n.status.syncing".to_string(), "Syncing…".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scans_menu_labels_following_the_menu_structure() {
+        let source = r#"
+            pub fn app_menus() -> Vec<Menu> {
+                vec![
+                    Menu {
+                        name: "File".into(),
+                        items: vec![
+                            MenuItem::action("Save", workspace::Save),
+                            MenuItem::submenu(Menu {
+                                name: "Recent".into(),
+                                items: vec![
+                                    MenuItem::action(
+                                        "Clear List",
+                                        workspace::ClearRecent,
+                                    ),
+                                ],
+                            }),
+                            MenuItem::action(
+                                "Save As…",
+                                workspace::SaveAs,
+                            ),
+                        ],
+                    },
+                ]
+            }
+        "#;
+        let labels = scan_menu_labels(source);
+        let summary: Vec<_> = labels
+            .iter()
+            .map(|label| (label.menu.as_str(), label.label.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("File", "Save"),
+                ("File > Recent", "Clear List"),
+                ("File", "Save As…"),
+            ]
+        );
+        assert_eq!(labels[0].expected_key, "i18n.menu.file.save");
+        assert!(labels[0].covered);
+        // Submenu labels get hierarchical keys.
+        assert_eq!(labels[1].expected_key, "i18n.menu.file.recent.clear_list");
+    }
+
+    #[test]
+    fn extracts_key_usages_with_line_numbers() {
+        let source = "fn render() {\n    t!(\"i18n.menu.file.save\");\n    let label = \"i18n.status.ready\";\n}\n";
+        assert_eq!(
+            extract_key_usages(source),
+            vec![
+                ("i18n.menu.file.save".to_string(), 2),
+                ("i18n.status.ready".to_string(), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn po_export_disambiguates_with_msgctxt() {
+        let file = TranslationFile::parse("zh-CN", r#"{"i18n.dialog.save": "保存"}"#).unwrap();
+        let po = render_po(&file);
+        // "Save" appears under several keys; msgctxt keeps them apart.
+        assert!(po.contains("msgctxt \"i18n.dialog.save\"\nmsgid \"Save\"\nmsgstr \"保存\"\n"));
+        assert!(po.contains("msgctxt \"i18n.menu.file.save\"\nmsgid \"Save\"\nmsgstr \"\"\n"));
+        // Recorded context rides along as an extracted comment.
+        assert!(po.contains("#. Dialog button"));
+    }
+
+    #[test]
+    fn po_export_emits_platform_variants_after_their_base_entry() {
+        let file = TranslationFile::parse(
+            "zh-CN",
+            r#"{"i18n.menu.file.open": "打开…", "i18n.menu.file.open@macos": "苹果打开…"}"#,
+        )
+        .unwrap();
+        let po = render_po(&file);
+        assert!(po.contains(
+            "msgctxt \"i18n.menu.file.open\"\nmsgid \"Open…\"\nmsgstr \"打开…\"\n\n#. Only on macos\nmsgctxt \"i18n.menu.file.open@macos\"\nmsgid \"Open…\"\nmsgstr \"苹果打开…\"\n"
+        ));
+    }
+
+    #[test]
+    fn parses_historical_default_texts() {
+        let source = r#"
+            pub static DEFAULT_TEXTS: &[(&str, &str)] = &[
+                ("i18n.dialog.ok", "OK"),
+                ("i18n.dialog.quote", "Say \"hi\"…"),
+                ("i18n.status.ready", "Line one\nLine two"),
+            ];
+        "#;
+        let texts = parse_default_texts(source);
+        assert_eq!(texts.len(), 3);
+        assert_eq!(texts["i18n.dialog.ok"], "OK");
+        assert_eq!(texts["i18n.dialog.quote"], "Say \"hi\"…");
+        assert_eq!(texts["i18n.status.ready"], "Line one\nLine two");
+    }
+
+    fn map(pairs: &[(&str, &str)]) -> serde_json::Map<String, serde_json::Value> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::String(value.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn merges_non_conflicting_changes_and_marks_conflicts() {
+        let base = map(&[
+            ("i18n.a.a.a", "base"),
+            ("i18n.b.b.b", "base"),
+            ("i18n.c.c.c", "base"),
+            ("i18n.d.d.d", "base"),
+        ]);
+        let ours = map(&[
+            ("i18n.a.a.a", "ours"),
+            ("i18n.b.b.b", "base"),
+            ("i18n.c.c.c", "ours"),
+            ("i18n.e.e.e", "ours"),
+        ]);
+        let theirs = map(&[
+            ("i18n.a.a.a", "base"),
+            ("i18n.b.b.b", "theirs"),
+            ("i18n.c.c.c", "theirs"),
+            ("i18n.d.d.d", "base"),
+            ("i18n.f.f.f", "theirs"),
+        ]);
+
+        let (merged, conflicts) = merge_translation_maps(&base, &ours, &theirs);
+
+        // Changed on one side only.
+        assert_eq!(merged["i18n.a.a.a"], "ours");
+        assert_eq!(merged["i18n.b.b.b"], "theirs");
+        // Deleted by us, unchanged by them.
+        assert!(!merged.contains_key("i18n.d.d.d"));
+        // Added on either side.
+        assert_eq!(merged["i18n.e.e.e"], "ours");
+        assert_eq!(merged["i18n.f.f.f"], "theirs");
+        // Changed on both sides.
+        assert_eq!(conflicts, vec!["i18n.c.c.c".to_string()]);
+        assert_eq!(
+            merged["i18n.c.c.c"],
+            "<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs"
+        );
+    }
+
+    #[test]
+    fn reorganize_orders_keys_to_match_the_defaults_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.json");
+        std::fs::write(
+            &path,
+            r#"{"i18n.dialog.ok": "OK", "i18n.x.y.z": "?", "i18n.dialog.cancel": "Cancel"}"#,
+        )
+        .unwrap();
+        assert!(reorganize(&path, false).unwrap());
+        let contents = std::fs::read_to_string(&path).unwrap();
+        // Defaults order, unknown keys last, trailing newline.
+        assert_eq!(
+            contents,
+            "{\n  \"i18n.dialog.cancel\": \"Cancel\",\n  \"i18n.dialog.ok\": \"OK\",\n  \"i18n.x.y.z\": \"?\"\n}\n"
+        );
+        // A second pass finds nothing to do and leaves no extra backup.
+        assert!(!reorganize(&path, false).unwrap());
+        let backups: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_name().to_string_lossy().ends_with(".bak")
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+        let backup = std::fs::read_to_string(backups[0].path()).unwrap();
+        assert!(backup.starts_with(r#"{"i18n.dialog.ok""#));
+    }
+
+    #[test]
+    fn rename_key_rewrites_sources_and_packs_together() {
+        let dir = tempfile::tempdir().unwrap();
+        let defaults_dir = dir.path().join("crates/i18n/src");
+        std::fs::create_dir_all(&defaults_dir).unwrap();
+        std::fs::write(
+            defaults_dir.join("defaults.rs"),
+            "pub static DEFAULT_TEXTS: &[(&str, &str)] = &[\n    (\"i18n.status.old_name\", \"Old\"),\n];\n\npub static KEY_ALIASES: &[(&str, &str)] = &[\n];\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("status_bar.rs"),
+            "let label = t!(\"i18n.status.old_name\");\n",
+        )
+        .unwrap();
+        let pack_dir = dir.path().join("zh-CN");
+        std::fs::create_dir_all(&pack_dir).unwrap();
+        std::fs::write(
+            pack_dir.join("translation.json"),
+            "{\n  // status\n  \"i18n.status.old_name\": \"旧\"\n}\n",
+        )
+        .unwrap();
+
+        assert!(
+            rename_key(
+                dir.path(),
+                "i18n.status.old_name",
+                "i18n.status.new_name",
+                OutputFormat::Text,
+                true,
+            )
+            .unwrap()
+        );
+
+        let defaults = std::fs::read_to_string(defaults_dir.join("defaults.rs")).unwrap();
+        assert!(defaults.contains("(\"i18n.status.new_name\", \"Old\")"));
+        // The old name becomes a deprecation alias.
+        assert!(
+            defaults
+                .contains("    (\"i18n.status.old_name\", \"i18n.status.new_name\"),\n];")
+        );
+        let source = std::fs::read_to_string(dir.path().join("status_bar.rs")).unwrap();
+        assert_eq!(source, "let label = t!(\"i18n.status.new_name\");\n");
+        let pack = std::fs::read_to_string(pack_dir.join("translation.json")).unwrap();
+        assert_eq!(pack, "{\n  // status\n  \"i18n.status.new_name\": \"旧\"\n}\n");
+    }
+
+    #[test]
+    fn effective_export_overlays_pack_values_on_defaults() {
+        let packs_dir = tempfile::tempdir().unwrap();
+        let pack = packs_dir.path().join("test-pack");
+        std::fs::create_dir_all(&pack).unwrap();
+        std::fs::write(
+            pack.join("metadata.json"),
+            r#"{"name": "Test", "language": "zz-effective-cli", "version": "1.0.0", "schema_version": 2}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            pack.join("translation.json"),
+            r#"{"schema_version": 2, "i18n.menu.file.save": "保存"}"#,
+        )
+        .unwrap();
+
+        let output = packs_dir.path().join("effective.json");
+        export_effective(
+            "zz-effective-cli",
+            packs_dir.path(),
+            None,
+            Some(&output),
+            OutputFormat::Json,
+            true,
+        )
+        .unwrap();
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(report["language"], "zz-effective-cli");
+        assert_eq!(report["entries"]["i18n.menu.file.save"], "保存");
+        // Keys the pack doesn't translate resolve to the English default.
+        assert_eq!(report["entries"]["i18n.menu.file.title"], "File");
+    }
+
+    #[test]
+    fn export_missing_selects_only_entries_needing_work() {
+        let file = TranslationFile::parse(
+            "zz",
+            r#"{
+                "schema_version": 2,
+                "i18n.dialog.save": "保存",
+                "i18n.dialog.cancel": "Cancel",
+                "i18n.dialog.ok": "好"
+            }"#,
+        )
+        .unwrap();
+        let review =
+            ReviewMetadata::parse(r#"{"i18n.dialog.save": {"status": "needs-review"}}"#).unwrap();
+
+        let entries = todo_entries(&file, Some(&review));
+        let by_key: BTreeMap<&str, &TodoEntry> = entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry))
+            .collect();
+
+        // Translated and not flagged: nothing left to do, so not exported.
+        assert!(!by_key.contains_key("i18n.dialog.ok"));
+        // English text copied verbatim still needs translating.
+        assert_eq!(by_key["i18n.dialog.cancel"].status, "fuzzy");
+        let flagged = &by_key["i18n.dialog.save"];
+        assert_eq!(flagged.status, "needs-review");
+        assert_eq!(flagged.translation.as_deref(), Some("保存"));
+        assert_eq!(flagged.english, "Save");
+        let missing = &by_key["i18n.dialog.dont_save"];
+        assert_eq!(missing.status, "missing");
+        assert_eq!(missing.translation, None);
+    }
+
+    #[test]
+    fn todo_exports_round_trip_through_the_import_parsers() {
+        let entries = vec![
+            TodoEntry {
+                key: "i18n.status.language_changed".into(),
+                english: "Language changed to {language}".into(),
+                context: None,
+                translation: Some("Langue : {language}, \"avec\" virgule,\net saut".into()),
+                status: "needs-review".into(),
+            },
+            TodoEntry {
+                key: "i18n.dialog.ok".into(),
+                english: "OK".into(),
+                context: Some("Button label".into()),
+                translation: None,
+                status: "missing".into(),
+            },
+        ];
+        let expected: Vec<(String, String)> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key.clone(),
+                    entry.translation.clone().unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            parse_po_translations(&render_todo_po("zz", &entries)),
+            expected
+        );
+        assert_eq!(
+            parse_csv_translations(&render_todo_csv(&entries)).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn import_partial_merges_valid_entries_and_skips_placeholder_mismatches() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = dir.path().join("zz-import");
+        std::fs::create_dir_all(&pack).unwrap();
+        std::fs::write(
+            pack.join("metadata.json"),
+            r#"{"name": "Test", "language": "zz-import-cli", "version": "1.0.0", "schema_version": 2}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            pack.join("translation.json"),
+            "{\n  // dialogs\n  \"i18n.dialog.ok\": \"好\"\n}\n",
+        )
+        .unwrap();
+        let input = dir.path().join("completed.json");
+        std::fs::write(
+            &input,
+            r#"{
+                "i18n.dialog.save": "保存",
+                "i18n.dialog.ok": "",
+                "i18n.status.language_changed": "语言已切换"
+            }"#,
+        )
+        .unwrap();
+
+        // The language_changed draft dropped its {language} placeholder, so
+        // it is skipped and the command reports problems.
+        assert!(!import_partial(&pack, &input, true).unwrap());
+
+        let merged = std::fs::read_to_string(pack.join("translation.json")).unwrap();
+        assert!(merged.contains("// dialogs"));
+        assert!(merged.contains("\"i18n.dialog.save\": \"保存\""));
+        // The untouched (empty) entry didn't clobber the existing value.
+        assert!(merged.contains("\"i18n.dialog.ok\": \"好\""));
+        assert!(!merged.contains("language_changed"));
+    }
+
+    #[test]
+    fn migrate_renames_keys_and_stamps_the_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.json");
+        std::fs::write(
+            &path,
+            "{\n  // menu\n  \"i18n.menu.other.settings\": \"设置\"\n}\n",
+        )
+        .unwrap();
+        assert!(migrate_translation_file(&path).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\n  \"schema_version\": 2,\n\n  // menu\n  \"i18n.menu.zed.open_settings\": \"设置\"\n}\n"
+        );
+        // A second run has nothing left to do.
+        assert!(!migrate_translation_file(&path).unwrap());
+    }
+
+    #[test]
+    fn rename_key_rejects_nonconforming_and_colliding_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            rename_key(dir.path(), "i18n.a.b.c", "i18n.Bad.Key", OutputFormat::Text, true)
+                .is_err()
+        );
+        assert!(
+            rename_key(
+                dir.path(),
+                "i18n.a.b.c",
+                "i18n.dialog.save",
+                OutputFormat::Text,
+                true,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn reorganize_dry_run_leaves_the_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.json");
+        let original = r#"{"i18n.dialog.ok": "OK", "i18n.dialog.cancel": "Cancel"}"#;
+        std::fs::write(&path, original).unwrap();
+        assert!(reorganize(&path, true).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+        assert!(
+            std::fs::read_dir(dir.path())
+                .unwrap()
+                .all(|entry| entry.unwrap().file_name() == "translation.json")
+        );
+    }
+
+    #[test]
+    fn reorganize_preserves_category_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.json");
+        std::fs::write(
+            &path,
+            "{\n  // status\n  \"i18n.status.ready\": \"Ready\",\n  // dialog\n  \"i18n.dialog.ok\": \"OK\"\n}\n",
+        )
+        .unwrap();
+        reorganize(&path, false).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            "{\n  // dialog\n  \"i18n.dialog.ok\": \"OK\",\n\n  // status\n  \"i18n.status.ready\": \"Ready\"\n}\n"
+        );
+    }
+
+    proptest::proptest! {
+        // File IO per case; enough cases to shake out escaping bugs
+        // without slowing the suite down.
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+        #[test]
+        fn reorganizing_never_changes_a_file_s_entries(
+            entries in proptest::collection::btree_map(
+                // A mix of reference-shaped keys (which sort into defaults
+                // order) and arbitrary ones (which keep their relative
+                // order at the end).
+                proptest::prop_oneof![
+                    "i18n\\.[a-z]{1,6}\\.[a-z]{1,6}\\.[a-z]{1,6}",
+                    "\\PC{1,12}",
+                ],
+                "\\PC{0,24}",
+                0..12,
+            ),
+        ) {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("translation.json");
+            std::fs::write(&path, serde_json::to_string(&entries).unwrap()).unwrap();
+
+            let (_, canonical) = canonical_translation_source(&path).unwrap();
+            let document = jsonc::Document::parse(&canonical).unwrap();
+
+            let round_tripped: std::collections::BTreeMap<String, serde_json::Value> = document
+                .entries
+                .into_iter()
+                .map(|entry| (entry.key, entry.value))
+                .collect();
+            let expected: std::collections::BTreeMap<String, serde_json::Value> = entries
+                .into_iter()
+                .map(|(key, value)| (key, serde_json::Value::from(value)))
+                .collect();
+            proptest::prop_assert_eq!(round_tripped, expected);
+        }
+    }
+}
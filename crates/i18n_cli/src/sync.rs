@@ -0,0 +1,420 @@
+//! Synchronization with hosted translation platforms.
+//!
+//! A `sync.toml` config selects a backend (Weblate or Crowdin) and lists the
+//! packs to keep in sync. Pushing uploads new reference keys with their
+//! English text; pulling folds approved translations back into each pack's
+//! `translation.json`, leaving strings still under review untouched.
+
+use anyhow::{Context as _, Result, bail};
+use async_trait::async_trait;
+use futures::AsyncReadExt as _;
+use http_client::{AsyncBody, HttpClient, Request};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The contents of `sync.toml`.
+#[derive(Debug, Deserialize)]
+pub struct SyncConfig {
+    pub backend: BackendKind,
+    /// Base API URL, e.g. `https://hosted.weblate.org/api` or
+    /// `https://api.crowdin.com/api/v2`.
+    pub api_url: String,
+    pub project: String,
+    /// Weblate component name; unused by Crowdin.
+    #[serde(default)]
+    pub component: String,
+    /// The environment variable holding the API token, so tokens never land
+    /// in the config file.
+    pub token_env: String,
+    #[serde(default)]
+    pub packs: Vec<PackSyncConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PackSyncConfig {
+    pub language: String,
+    /// The pack directory, relative to the config file's directory.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    Weblate,
+    Crowdin,
+}
+
+impl SyncConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn backend(&self, http: Arc<dyn HttpClient>) -> Result<Box<dyn SyncBackend>> {
+        let token = std::env::var(&self.token_env)
+            .with_context(|| format!("the {} environment variable is not set", self.token_env))?;
+        Ok(match self.backend {
+            BackendKind::Weblate => Box::new(WeblateBackend {
+                http,
+                api_url: self.api_url.trim_end_matches('/').to_string(),
+                project: self.project.clone(),
+                component: self.component.clone(),
+                token,
+            }),
+            BackendKind::Crowdin => Box::new(CrowdinBackend {
+                http,
+                api_url: self.api_url.trim_end_matches('/').to_string(),
+                project: self.project.clone(),
+                token,
+            }),
+        })
+    }
+}
+
+/// How far along a remote translation is, after mapping each platform's own
+/// state model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteState {
+    NeedsReview,
+    Translated,
+    Approved,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RemoteUnit {
+    pub key: String,
+    pub value: String,
+    pub state: RemoteState,
+}
+
+#[async_trait]
+pub trait SyncBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Uploads reference keys and their English text so translators see new
+    /// strings. Keys the platform already knows are skipped server-side.
+    async fn push_reference_keys(&self, texts: &[(&str, &str)]) -> Result<()>;
+    async fn pull_translations(&self, language: &str) -> Result<Vec<RemoteUnit>>;
+}
+
+/// How pulled units were folded into a pack.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncStats {
+    /// Approved (or newly translated) values written into the pack.
+    pub applied: usize,
+    /// Units still under review whose local value was preserved.
+    pub needs_review: usize,
+}
+
+/// Applies pulled units to a pack's entries. Approved values overwrite;
+/// merely-translated values fill gaps only; needs-review values never
+/// replace an existing local translation.
+pub fn apply_remote_units(
+    entries: &mut serde_json::Map<String, serde_json::Value>,
+    units: &[RemoteUnit],
+) -> SyncStats {
+    let mut stats = SyncStats::default();
+    for unit in units {
+        let existing = entries.get(&unit.key).and_then(|value| value.as_str());
+        let write = match unit.state {
+            RemoteState::Approved => true,
+            RemoteState::Translated => existing.is_none(),
+            RemoteState::NeedsReview => {
+                if existing.is_some() {
+                    stats.needs_review += 1;
+                    false
+                } else {
+                    stats.needs_review += 1;
+                    true
+                }
+            }
+        };
+        if write && existing != Some(unit.value.as_str()) {
+            entries.insert(unit.key.clone(), unit.value.clone().into());
+            stats.applied += 1;
+        }
+    }
+    stats
+}
+
+struct WeblateBackend {
+    http: Arc<dyn HttpClient>,
+    api_url: String,
+    project: String,
+    component: String,
+    token: String,
+}
+
+#[async_trait]
+impl SyncBackend for WeblateBackend {
+    fn name(&self) -> &'static str {
+        "weblate"
+    }
+
+    async fn push_reference_keys(&self, texts: &[(&str, &str)]) -> Result<()> {
+        let url = format!(
+            "{}/translations/{}/{}/en/units/",
+            self.api_url, self.project, self.component
+        );
+        for (key, text) in texts {
+            let body = serde_json::json!({ "context": key, "source": [text] });
+            let request = Request::builder()
+                .method(http_client::Method::POST)
+                .uri(&url)
+                .header("Content-Type", "application/json")
+                .header("Authorization", format!("Token {}", self.token))
+                .body(AsyncBody::from(body.to_string()))?;
+            let response = self.http.send(request).await?;
+            // 400 means the unit already exists, which push treats as done.
+            if !response.status().is_success() && response.status().as_u16() != 400 {
+                bail!("weblate rejected key {key}: HTTP {}", response.status());
+            }
+        }
+        Ok(())
+    }
+
+    async fn pull_translations(&self, language: &str) -> Result<Vec<RemoteUnit>> {
+        let url = format!(
+            "{}/translations/{}/{}/{}/units/",
+            self.api_url, self.project, self.component, language
+        );
+        let request = Request::builder()
+            .uri(&url)
+            .header("Authorization", format!("Token {}", self.token))
+            .body(AsyncBody::default())?;
+        let mut response = self.http.send(request).await?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "weblate returned HTTP {} for {language}",
+            response.status()
+        );
+        let mut body = String::new();
+        response.body_mut().read_to_string(&mut body).await?;
+        parse_weblate_units(&body)
+    }
+}
+
+/// Parses Weblate's unit list response. Weblate states: 10 needs editing,
+/// 20 translated, 30 approved.
+fn parse_weblate_units(body: &str) -> Result<Vec<RemoteUnit>> {
+    #[derive(Deserialize)]
+    struct UnitList {
+        results: Vec<Unit>,
+    }
+    #[derive(Deserialize)]
+    struct Unit {
+        context: String,
+        #[serde(default)]
+        target: Vec<String>,
+        state: u32,
+    }
+
+    let list: UnitList = serde_json::from_str(body).context("unexpected Weblate response")?;
+    Ok(list
+        .results
+        .into_iter()
+        .filter_map(|unit| {
+            let value = unit.target.into_iter().next()?;
+            if value.is_empty() {
+                return None;
+            }
+            let state = match unit.state {
+                30.. => RemoteState::Approved,
+                20..=29 => RemoteState::Translated,
+                _ => RemoteState::NeedsReview,
+            };
+            Some(RemoteUnit {
+                key: unit.context,
+                value,
+                state,
+            })
+        })
+        .collect())
+}
+
+struct CrowdinBackend {
+    http: Arc<dyn HttpClient>,
+    api_url: String,
+    project: String,
+    token: String,
+}
+
+#[async_trait]
+impl SyncBackend for CrowdinBackend {
+    fn name(&self) -> &'static str {
+        "crowdin"
+    }
+
+    async fn push_reference_keys(&self, texts: &[(&str, &str)]) -> Result<()> {
+        let url = format!("{}/projects/{}/strings", self.api_url, self.project);
+        for (key, text) in texts {
+            let body = serde_json::json!({ "identifier": key, "text": text });
+            let request = Request::builder()
+                .method(http_client::Method::POST)
+                .uri(&url)
+                .header("Content-Type", "application/json")
+                .header("Authorization", format!("Bearer {}", self.token))
+                .body(AsyncBody::from(body.to_string()))?;
+            let response = self.http.send(request).await?;
+            if !response.status().is_success() && response.status().as_u16() != 400 {
+                bail!("crowdin rejected key {key}: HTTP {}", response.status());
+            }
+        }
+        Ok(())
+    }
+
+    async fn pull_translations(&self, language: &str) -> Result<Vec<RemoteUnit>> {
+        let url = format!(
+            "{}/projects/{}/languages/{}/translations?limit=500",
+            self.api_url, self.project, language
+        );
+        let request = Request::builder()
+            .uri(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .body(AsyncBody::default())?;
+        let mut response = self.http.send(request).await?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "crowdin returned HTTP {} for {language}",
+            response.status()
+        );
+        let mut body = String::new();
+        response.body_mut().read_to_string(&mut body).await?;
+        parse_crowdin_units(&body)
+    }
+}
+
+fn parse_crowdin_units(body: &str) -> Result<Vec<RemoteUnit>> {
+    #[derive(Deserialize)]
+    struct TranslationList {
+        data: Vec<Entry>,
+    }
+    #[derive(Deserialize)]
+    struct Entry {
+        data: Translation,
+    }
+    #[derive(Deserialize)]
+    struct Translation {
+        identifier: String,
+        text: String,
+        #[serde(default)]
+        status: String,
+    }
+
+    let list: TranslationList = serde_json::from_str(body).context("unexpected Crowdin response")?;
+    Ok(list
+        .data
+        .into_iter()
+        .filter(|entry| !entry.data.text.is_empty())
+        .map(|entry| {
+            let state = match entry.data.status.as_str() {
+                "approved" => RemoteState::Approved,
+                "needsReview" | "needs_review" => RemoteState::NeedsReview,
+                _ => RemoteState::Translated,
+            };
+            RemoteUnit {
+                key: entry.data.identifier,
+                value: entry.data.text,
+                state,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_sync_config() {
+        let config: SyncConfig = toml::from_str(
+            r#"
+            backend = "weblate"
+            api_url = "https://hosted.weblate.org/api"
+            project = "zed"
+            component = "ui"
+            token_env = "WEBLATE_TOKEN"
+
+            [[packs]]
+            language = "zh-CN"
+            path = "packs/zh-CN"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.backend, BackendKind::Weblate);
+        assert_eq!(config.packs.len(), 1);
+        assert_eq!(config.packs[0].language, "zh-CN");
+    }
+
+    #[test]
+    fn maps_weblate_states() {
+        let units = parse_weblate_units(
+            r#"{"results": [
+                {"context": "i18n.dialog.ok", "target": ["好"], "state": 30},
+                {"context": "i18n.dialog.save", "target": ["保存"], "state": 20},
+                {"context": "i18n.dialog.cancel", "target": ["取消"], "state": 10},
+                {"context": "i18n.dialog.overwrite", "target": [], "state": 30}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(units.len(), 3);
+        assert_eq!(units[0].state, RemoteState::Approved);
+        assert_eq!(units[1].state, RemoteState::Translated);
+        assert_eq!(units[2].state, RemoteState::NeedsReview);
+    }
+
+    #[test]
+    fn maps_crowdin_states() {
+        let units = parse_crowdin_units(
+            r#"{"data": [
+                {"data": {"identifier": "i18n.dialog.ok", "text": "好", "status": "approved"}},
+                {"data": {"identifier": "i18n.dialog.save", "text": "保存", "status": "needsReview"}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(units[0].state, RemoteState::Approved);
+        assert_eq!(units[1].state, RemoteState::NeedsReview);
+    }
+
+    #[test]
+    fn approved_overwrites_but_needs_review_is_preserved() {
+        let mut entries = serde_json::Map::new();
+        entries.insert("i18n.dialog.ok".to_string(), "旧".into());
+        entries.insert("i18n.dialog.save".to_string(), "本地".into());
+
+        let stats = apply_remote_units(
+            &mut entries,
+            &[
+                RemoteUnit {
+                    key: "i18n.dialog.ok".to_string(),
+                    value: "好".to_string(),
+                    state: RemoteState::Approved,
+                },
+                RemoteUnit {
+                    key: "i18n.dialog.save".to_string(),
+                    value: "保存".to_string(),
+                    state: RemoteState::NeedsReview,
+                },
+                RemoteUnit {
+                    key: "i18n.dialog.cancel".to_string(),
+                    value: "取消".to_string(),
+                    state: RemoteState::Translated,
+                },
+            ],
+        );
+
+        assert_eq!(entries["i18n.dialog.ok"], "好");
+        // The local value survives until the remote string is approved.
+        assert_eq!(entries["i18n.dialog.save"], "本地");
+        assert_eq!(entries["i18n.dialog.cancel"], "取消");
+        assert_eq!(
+            stats,
+            SyncStats {
+                applied: 2,
+                needs_review: 1,
+            }
+        );
+    }
+}
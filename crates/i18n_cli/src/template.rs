@@ -0,0 +1,563 @@
+//! Scaffolding for new language packs.
+
+use crate::jsonc::{Document, DocumentEntry};
+use anyhow::{Context as _, Result};
+use i18n::TranslationFile;
+use i18n::defaults::DEFAULT_TEXTS;
+use i18n::keys::TranslationCategory;
+use i18n::pack::{
+    CURRENT_SCHEMA_VERSION, METADATA_FILE_NAME, PackMetadata, SCHEMA_VERSION_KEY,
+    SPLIT_TRANSLATIONS_DIR_NAME,
+};
+use std::path::{Path, PathBuf};
+
+/// Generates the files a new language pack starts from: `metadata.json` and
+/// a `translation.json` template containing the complete reference key set,
+/// pre-filled with the English values to replace and grouped under category
+/// comments.
+pub struct I18NTemplate {
+    language: String,
+    name: String,
+    seed_language: Option<String>,
+    packs_dir: Option<PathBuf>,
+    data_only: bool,
+    split: bool,
+    extends: Option<String>,
+}
+
+impl I18NTemplate {
+    pub fn new(language: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            name: name.into(),
+            seed_language: None,
+            packs_dir: None,
+            data_only: false,
+            split: false,
+            extends: None,
+        }
+    }
+
+    /// Generates a sparse pack extending `parent` (e.g. an `en-GB` pack
+    /// extending `en-US`): the metadata declares the parent and the
+    /// translation file starts with no keys, since every key the pack
+    /// doesn't list resolves through the parent's pack at runtime. Takes
+    /// precedence over [`Self::split`].
+    pub fn extends(mut self, parent: impl Into<String>) -> Self {
+        self.extends = Some(parent.into());
+        self
+    }
+
+    /// Generates the data-only extension layout — `extension.toml` plus
+    /// `resources/translations/<language>.json` — instead of a standalone
+    /// pack directory. Data-only packs need no compiled library, so
+    /// translators never touch a Rust toolchain.
+    pub fn data_only(mut self) -> Self {
+        self.data_only = true;
+        self
+    }
+
+    /// Generates one translation file per key area (`menu.json`,
+    /// `editor.json`, …) instead of a single file, for languages with
+    /// enough contributors that one file becomes a merge bottleneck.
+    pub fn split(mut self) -> Self {
+        self.split = true;
+        self
+    }
+
+    /// Pre-fills the template from an installed pack for a related language
+    /// instead of English, e.g. seeding `zh-TW` from `zh-CN` or `pt-BR` from
+    /// `pt`.
+    pub fn with_seed_language(mut self, language: impl Into<String>) -> Self {
+        self.seed_language = Some(language.into());
+        self
+    }
+
+    /// Where to look for installed packs when seeding. Defaults to Zed's
+    /// language pack directory.
+    pub fn with_packs_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.packs_dir = Some(dir.into());
+        self
+    }
+
+    pub fn generate_translation_files(&self, output_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(output_dir)
+            .with_context(|| format!("failed to create {}", output_dir.display()))?;
+
+        if self.data_only {
+            return self.generate_data_only(output_dir);
+        }
+
+        let metadata = PackMetadata {
+            name: self.name.clone(),
+            language: self.language.clone(),
+            version: "0.1.0".to_string(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+            translators: Vec::new(),
+            issue_repository: None,
+            extends: self.extends.clone(),
+            number_format: None,
+            preferred_fonts: Vec::new(),
+            requires_cjk_fallback: false,
+            docs_base_url: None,
+            docs_overrides: Default::default(),
+        };
+        let mut metadata_json = serde_json::to_string_pretty(&metadata)?;
+        metadata_json.push('\n');
+        std::fs::write(output_dir.join(METADATA_FILE_NAME), metadata_json)
+            .context("failed to write metadata.json")?;
+
+        if let Some(parent) = &self.extends {
+            std::fs::write(
+                output_dir.join("translation.json"),
+                render_sparse_template(parent),
+            )
+            .context("failed to write translation.json")?;
+        } else {
+            let seed = self.load_seed()?;
+            if self.split {
+                let split_dir = output_dir.join(SPLIT_TRANSLATIONS_DIR_NAME);
+                std::fs::create_dir_all(&split_dir)
+                    .with_context(|| format!("failed to create {}", split_dir.display()))?;
+                for (file_name, contents) in render_split_templates(seed.as_ref()) {
+                    std::fs::write(split_dir.join(&file_name), contents)
+                        .with_context(|| format!("failed to write {file_name}"))?;
+                }
+            } else {
+                std::fs::write(
+                    output_dir.join("translation.json"),
+                    render_template(seed.as_ref()),
+                )
+                .context("failed to write translation.json")?;
+            }
+        }
+
+        self.generate_test_harness(output_dir)?;
+        self.generate_readme(output_dir)?;
+
+        Ok(())
+    }
+
+    /// Writes a README in the pack's own language when a pre-translated
+    /// template exists, falling back to English.
+    fn generate_readme(&self, output_dir: &Path) -> Result<()> {
+        let languages: Vec<String> = README_TEMPLATES
+            .iter()
+            .map(|(language, _)| language.to_string())
+            .collect();
+        let matched = i18n::lang_codes::negotiate([self.language.as_str()], &languages)
+            .map_or("en", |language| language.as_str());
+        let template = README_TEMPLATES
+            .iter()
+            .find(|(language, _)| *language == matched)
+            .map(|(_, template)| *template)
+            .unwrap_or(README_TEMPLATES[0].1);
+        let readme = template
+            .replace("{name}", &self.name)
+            .replace("{language}", &self.language);
+        std::fs::write(output_dir.join("README.md"), readme)
+            .context("failed to write README.md")
+    }
+
+    /// Emits a `cargo test` harness that validates the pack's own
+    /// translation file, so contributors catch errors locally before
+    /// opening a pull request.
+    fn generate_test_harness(&self, output_dir: &Path) -> Result<()> {
+        let tests_dir = output_dir.join("tests");
+        std::fs::create_dir_all(&tests_dir)
+            .with_context(|| format!("failed to create {}", tests_dir.display()))?;
+        std::fs::write(tests_dir.join("validate.rs"), VALIDATE_TEST_SOURCE)
+            .context("failed to write tests/validate.rs")?;
+
+        let cargo_toml = output_dir.join("Cargo.toml");
+        if !cargo_toml.exists() {
+            let manifest = format!(
+                "[package]\nname = \"i18n-{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\npublish = false\n\n[dev-dependencies]\ni18n = {{ git = \"https://github.com/chatterzhao/zed\" }}\n",
+                self.language.to_lowercase()
+            );
+            std::fs::write(&cargo_toml, manifest).context("failed to write Cargo.toml")?;
+        }
+        Ok(())
+    }
+
+    fn generate_data_only(&self, output_dir: &Path) -> Result<()> {
+        let id = format!("i18n-{}", self.language.to_lowercase());
+        let manifest = format!(
+            "id = {id:?}\nname = {name:?}\nversion = \"0.1.0\"\nschema_version = 1\nauthors = []\ndescription = \"UI translations for {language}\"\nrepository = \"\"\n",
+            name = self.name,
+            language = self.language,
+        );
+        std::fs::write(output_dir.join("extension.toml"), manifest)
+            .context("failed to write extension.toml")?;
+
+        let translations_dir = output_dir.join("resources").join("translations");
+        std::fs::create_dir_all(&translations_dir)
+            .with_context(|| format!("failed to create {}", translations_dir.display()))?;
+        let seed = self.load_seed()?;
+        if self.split {
+            let language_dir = translations_dir.join(&self.language);
+            std::fs::create_dir_all(&language_dir)
+                .with_context(|| format!("failed to create {}", language_dir.display()))?;
+            for (file_name, contents) in render_split_templates(seed.as_ref()) {
+                std::fs::write(language_dir.join(&file_name), contents)
+                    .with_context(|| format!("failed to write {file_name}"))?;
+            }
+        } else {
+            std::fs::write(
+                translations_dir.join(format!("{}.json", self.language)),
+                render_template(seed.as_ref()),
+            )
+            .context("failed to write the translation file")?;
+        }
+        self.generate_readme(output_dir)?;
+        Ok(())
+    }
+
+    /// Finds the installed pack whose language best matches the seed
+    /// language, using the same negotiation as the runtime.
+    fn load_seed(&self) -> Result<Option<TranslationFile>> {
+        let Some(seed_language) = &self.seed_language else {
+            return Ok(None);
+        };
+        let packs_dir = self
+            .packs_dir
+            .clone()
+            .unwrap_or_else(|| paths::language_packs_dir().clone());
+
+        let mut packs = Vec::new();
+        if packs_dir.is_dir() {
+            for entry in std::fs::read_dir(&packs_dir)
+                .with_context(|| format!("failed to read {}", packs_dir.display()))?
+            {
+                let entry = entry?;
+                if let Ok(metadata) = PackMetadata::load(&entry.path()) {
+                    packs.push((metadata.language, entry.path()));
+                }
+            }
+        }
+        let languages: Vec<String> = packs.iter().map(|(language, _)| language.clone()).collect();
+        let matched = i18n::lang_codes::negotiate([seed_language.as_str()], &languages)
+            .with_context(|| {
+                format!("no installed language pack matches seed language {seed_language}")
+            })?;
+        let (language, pack_dir) = packs
+            .iter()
+            .find(|(language, _)| language == matched)
+            .context("negotiated a language with no backing pack")?;
+        let file = TranslationFile::load(language.clone(), &pack_dir.join("translation.json"))?;
+        Ok(Some(file))
+    }
+}
+
+/// Pre-translated README templates, keyed by language. `{name}` and
+/// `{language}` are substituted at generation time; languages without a
+/// template fall back to English.
+const README_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "en",
+        "# {name}\n\nUI translations for Zed ({language}).\n\n## Key naming cheat sheet\n\n\
+         Keys have the form `i18n.<area>.<path>`, with every segment in lowercase\n\
+         snake case — for example `i18n.menu.file.save`. Extension strings live\n\
+         under `i18n.ext.<extension_id>.<path>`.\n\n## Contributing\n\n\
+         1. Edit `translation.json`, replacing the English values.\n\
+         2. Run `zed-i18n validate .` (or `cargo test`) to check your work.\n\
+         3. Open a pull request.\n",
+    ),
+    (
+        "zh-CN",
+        "# {name}\n\nZed 的界面翻译（{language}）。\n\n## 键名速查\n\n\
+         键名的形式为 `i18n.<area>.<path>`，每段均为小写下划线风格，\n\
+         例如 `i18n.menu.file.save`。扩展字符串位于\n\
+         `i18n.ext.<extension_id>.<path>` 命名空间下。\n\n## 参与贡献\n\n\
+         1. 编辑 `translation.json`，将英文值替换为译文。\n\
+         2. 运行 `zed-i18n validate .`（或 `cargo test`）检查翻译。\n\
+         3. 提交 Pull Request。\n",
+    ),
+    (
+        "zh-TW",
+        "# {name}\n\nZed 的介面翻譯（{language}）。\n\n## 鍵名速查\n\n\
+         鍵名的形式為 `i18n.<area>.<path>`，每段均為小寫底線風格，\n\
+         例如 `i18n.menu.file.save`。擴充功能字串位於\n\
+         `i18n.ext.<extension_id>.<path>` 命名空間下。\n\n## 參與貢獻\n\n\
+         1. 編輯 `translation.json`，將英文值替換為譯文。\n\
+         2. 執行 `zed-i18n validate .`（或 `cargo test`）檢查翻譯。\n\
+         3. 發送 Pull Request。\n",
+    ),
+    (
+        "ja",
+        "# {name}\n\nZed の UI 翻訳（{language}）。\n\n## キー命名早見表\n\n\
+         キーは `i18n.<area>.<path>` の形式で、各セグメントは小文字の\n\
+         スネークケースです — 例: `i18n.menu.file.save`。拡張機能の文字列は\n\
+         `i18n.ext.<extension_id>.<path>` 名前空間に置かれます。\n\n## 貢献するには\n\n\
+         1. `translation.json` の英語の値を訳文に置き換えます。\n\
+         2. `zed-i18n validate .`（または `cargo test`）で確認します。\n\
+         3. Pull Request を送ります。\n",
+    ),
+];
+
+/// The validation test emitted into every generated pack.
+const VALIDATE_TEST_SOURCE: &str = r#"use i18n::TranslationFile;
+use i18n::pack::PackMetadata;
+use i18n::validator::{I18NValidator, IssueCode};
+use std::path::Path;
+
+#[test]
+fn translation_file_is_valid() {
+    let pack_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let metadata = PackMetadata::load(pack_dir).expect("failed to load metadata.json");
+    let file = TranslationFile::load(metadata.language, &pack_dir.join("translation.json"))
+        .expect("failed to load translation.json");
+    let report = I18NValidator::new().validate(&file);
+    // A sparse pack inherits the keys it doesn't list from its parent, so
+    // missing keys aren't errors for it.
+    let errors: Vec<_> = report
+        .errors()
+        .filter(|issue| metadata.extends.is_none() || issue.code != IssueCode::MissingKey)
+        .collect();
+    assert!(errors.is_empty(), "validation errors: {errors:#?}");
+}
+"#;
+
+/// Renders the full reference key set as a translation file template, with a
+/// comment line introducing each category. Values come from the seed file
+/// when one is given and it has the key, otherwise from the English
+/// defaults.
+fn render_template(seed: Option<&TranslationFile>) -> String {
+    template_document(seed, DEFAULT_TEXTS).render()
+}
+
+/// Renders the translation file a sparse pack starts from: just the schema
+/// version entry, with a comment pointing translators at the parent.
+fn render_sparse_template(parent: &str) -> String {
+    let mut document = Document::default();
+    document.entries.push(DocumentEntry {
+        leading_comments: vec![
+            format!("// Keys not listed here are inherited from the {parent} pack."),
+            "// Add only the keys whose translation differs.".to_string(),
+        ],
+        key: SCHEMA_VERSION_KEY.to_string(),
+        value: serde_json::Value::from(CURRENT_SCHEMA_VERSION),
+    });
+    document.render()
+}
+
+/// Renders one template file per key area (`menu.json`, `editor.json`, …),
+/// as pairs of file name and contents. Each file carries its own schema
+/// version entry so it stays loadable on its own.
+fn render_split_templates(seed: Option<&TranslationFile>) -> Vec<(String, String)> {
+    let mut areas: Vec<&str> = Vec::new();
+    for (key, _) in DEFAULT_TEXTS {
+        let area = key_area(key);
+        if !areas.contains(&area) {
+            areas.push(area);
+        }
+    }
+    areas
+        .into_iter()
+        .map(|area| {
+            let entries: Vec<&(&str, &str)> = DEFAULT_TEXTS
+                .iter()
+                .filter(|(key, _)| key_area(key) == area)
+                .collect();
+            (
+                format!("{area}.json"),
+                template_document(seed, entries).render(),
+            )
+        })
+        .collect()
+}
+
+/// The `<area>` segment of an `i18n.<area>.<path>` key, which names the
+/// file the key goes to in the split layout.
+fn key_area(key: &str) -> &str {
+    key.split('.').nth(1).unwrap_or("other")
+}
+
+fn template_document<'a>(
+    seed: Option<&TranslationFile>,
+    entries: impl IntoIterator<Item = &'a (&'a str, &'a str)>,
+) -> Document {
+    let mut document = Document::default();
+    document.entries.push(DocumentEntry {
+        leading_comments: Vec::new(),
+        key: SCHEMA_VERSION_KEY.to_string(),
+        value: serde_json::Value::from(CURRENT_SCHEMA_VERSION),
+    });
+    let mut last_category: Option<TranslationCategory> = None;
+    for (key, text) in entries {
+        let mut leading_comments = Vec::new();
+        let category = TranslationCategory::for_key(key);
+        if last_category.as_ref() != Some(&category) {
+            leading_comments.push(format!("// {category}"));
+            last_category = Some(category);
+        }
+        if let Some(context) = i18n::defaults::key_context(key) {
+            leading_comments.push(format!("// {context}"));
+        }
+        let value = seed.and_then(|seed| seed.get(key)).unwrap_or(text);
+        document.entries.push(DocumentEntry {
+            leading_comments,
+            key: (*key).to_string(),
+            value: serde_json::Value::from(value),
+        });
+    }
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_the_complete_reference_key_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = I18NTemplate::new("zh-CN", "简体中文");
+        template.generate_translation_files(dir.path()).unwrap();
+
+        let metadata = PackMetadata::load(dir.path()).unwrap();
+        assert_eq!(metadata.language, "zh-CN");
+        assert_eq!(metadata.schema_version, CURRENT_SCHEMA_VERSION);
+
+        let contents =
+            std::fs::read_to_string(dir.path().join("translation.json")).unwrap();
+        for (key, text) in DEFAULT_TEXTS {
+            let line = format!(
+                "  {}: {}",
+                serde_json::to_string(key).unwrap(),
+                serde_json::to_string(text).unwrap()
+            );
+            assert!(contents.contains(&line), "template is missing {key}");
+        }
+        assert!(contents.contains("  // menu\n"));
+        assert!(contents.contains("  // dialog\n"));
+
+        // The generated harness validates the pack during `cargo test`.
+        let test_source = std::fs::read_to_string(dir.path().join("tests/validate.rs")).unwrap();
+        assert!(test_source.contains("I18NValidator"));
+        assert!(dir.path().join("Cargo.toml").exists());
+    }
+
+    #[test]
+    fn seeds_values_from_a_related_installed_pack() {
+        let packs_dir = tempfile::tempdir().unwrap();
+        let seed_dir = packs_dir.path().join("chinese-simplified");
+        std::fs::create_dir_all(&seed_dir).unwrap();
+        std::fs::write(
+            seed_dir.join(METADATA_FILE_NAME),
+            r#"{"name": "简体中文", "language": "zh-CN", "version": "1.0.0", "schema_version": 1}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            seed_dir.join("translation.json"),
+            r#"{"i18n.menu.file.save": "保存"}"#,
+        )
+        .unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        I18NTemplate::new("zh-TW", "繁體中文")
+            .with_seed_language("zh-TW")
+            .with_packs_dir(packs_dir.path())
+            .generate_translation_files(output.path())
+            .unwrap();
+
+        let contents =
+            std::fs::read_to_string(output.path().join("translation.json")).unwrap();
+        // Seeded keys start from the related language; the rest stay English.
+        assert!(contents.contains(r#""i18n.menu.file.save": "保存""#));
+        assert!(contents.contains(r#""i18n.menu.file.open": "Open…""#));
+    }
+
+    #[test]
+    fn escapes_special_characters_in_seeded_values() {
+        let seed = TranslationFile::parse(
+            "zh-CN",
+            r#"{"i18n.menu.file.save": "\"save\" \\ first\nthen quit"}"#,
+        )
+        .unwrap();
+        let rendered = render_template(Some(&seed));
+        let round_tripped = TranslationFile::parse("zh-CN", &rendered).unwrap();
+        assert_eq!(
+            round_tripped.get("i18n.menu.file.save"),
+            Some("\"save\" \\ first\nthen quit")
+        );
+    }
+
+    #[test]
+    fn localizes_the_readme_when_a_template_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        I18NTemplate::new("zh-Hans-CN", "简体中文")
+            .generate_translation_files(dir.path())
+            .unwrap();
+        let readme = std::fs::read_to_string(dir.path().join("README.md")).unwrap();
+        assert!(readme.contains("# 简体中文"));
+        assert!(readme.contains("键名速查"));
+
+        let dir = tempfile::tempdir().unwrap();
+        I18NTemplate::new("ko", "한국어")
+            .generate_translation_files(dir.path())
+            .unwrap();
+        // No Korean template yet; English is the fallback.
+        let readme = std::fs::read_to_string(dir.path().join("README.md")).unwrap();
+        assert!(readme.contains("Key naming cheat sheet"));
+    }
+
+    #[test]
+    fn generates_the_split_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        I18NTemplate::new("zh-CN", "简体中文")
+            .split()
+            .generate_translation_files(dir.path())
+            .unwrap();
+
+        assert!(!dir.path().join("translation.json").exists());
+        let split_dir = dir.path().join(SPLIT_TRANSLATIONS_DIR_NAME);
+        let menu = std::fs::read_to_string(split_dir.join("menu.json")).unwrap();
+        assert!(menu.contains(r#""schema_version": 2"#));
+        assert!(menu.contains(r#""i18n.menu.file.save": "Save""#));
+        assert!(!menu.contains("i18n.dialog."));
+
+        // The parts merge back into the complete reference key set.
+        let merged = TranslationFile::load_merged("zh-CN", &split_dir).unwrap();
+        for (key, _) in DEFAULT_TEXTS {
+            assert!(merged.get(key).is_some(), "merged set is missing {key}");
+        }
+    }
+
+    #[test]
+    fn generates_a_sparse_pack_extending_a_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        I18NTemplate::new("en-GB", "British English")
+            .extends("en-US")
+            .generate_translation_files(dir.path())
+            .unwrap();
+
+        let metadata = PackMetadata::load(dir.path()).unwrap();
+        assert_eq!(metadata.extends.as_deref(), Some("en-US"));
+
+        let contents = std::fs::read_to_string(dir.path().join("translation.json")).unwrap();
+        assert!(contents.contains("inherited from the en-US pack"));
+        assert!(!contents.contains("i18n.menu.file.save"));
+        let file =
+            TranslationFile::load("en-GB", &dir.path().join("translation.json")).unwrap();
+        assert!(file.get("i18n.menu.file.save").is_none());
+    }
+
+    #[test]
+    fn generates_the_data_only_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        I18NTemplate::new("zh-CN", "简体中文")
+            .data_only()
+            .generate_translation_files(dir.path())
+            .unwrap();
+
+        let manifest = std::fs::read_to_string(dir.path().join("extension.toml")).unwrap();
+        assert!(manifest.contains(r#"id = "i18n-zh-cn""#));
+        let translation = dir
+            .path()
+            .join("resources")
+            .join("translations")
+            .join("zh-CN.json");
+        let contents = std::fs::read_to_string(translation).unwrap();
+        assert!(contents.contains(r#""i18n.menu.file.save": "Save""#));
+    }
+}
@@ -0,0 +1,57 @@
+//! Connects the extension host's i18n interface to the [`i18n`] subsystem.
+
+use std::sync::Arc;
+
+use extension::{ExtensionHostProxy, ExtensionI18nProxy};
+use i18n::I18nManager;
+
+pub fn init(extension_host_proxy: Arc<ExtensionHostProxy>) {
+    extension_host_proxy.register_i18n_proxy(I18nManagerProxy);
+}
+
+struct I18nManagerProxy;
+
+impl ExtensionI18nProxy for I18nManagerProxy {
+    fn register_translations(
+        &self,
+        extension_id: Arc<str>,
+        language: String,
+        translations: Vec<(String, String)>,
+    ) {
+        // Keys outside the core reference set are moved into the extension's
+        // own namespace so that two extensions can't clobber each other's
+        // strings.
+        let translations = translations.into_iter().map(|(key, value)| {
+            (i18n::keys::extension_namespaced_key(&extension_id, &key), value)
+        });
+        I18nManager::global().register_translations(&extension_id, &language, translations);
+    }
+
+    fn register_translators(&self, extension_id: Arc<str>, language: String, names: Vec<String>) {
+        I18nManager::global().set_translators(&extension_id, &language, names);
+    }
+
+    fn unregister_translations(&self, extension_id: Arc<str>) {
+        I18nManager::global().unregister_source(&extension_id);
+    }
+
+    fn current_language(&self) -> String {
+        I18nManager::global().current_language()
+    }
+
+    fn translate(
+        &self,
+        extension_id: Arc<str>,
+        key: String,
+        args: Vec<(String, String)>,
+    ) -> String {
+        // The same namespacing as registration, so an extension resolves its
+        // own strings with the short keys it registered them under.
+        let key = i18n::keys::extension_namespaced_key(&extension_id, &key);
+        let mut translated = i18n::TranslatedString::new(key);
+        for (name, value) in args {
+            translated = translated.with_arg(name, value);
+        }
+        translated.resolve().to_string()
+    }
+}
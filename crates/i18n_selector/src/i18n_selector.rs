@@ -0,0 +1,547 @@
+//! The UI language picker modal.
+
+use fs::Fs;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable, Render, SharedString, WeakEntity,
+    Window,
+};
+use i18n::{I18nManager, I18nSettings, TranslationFile, lang_codes, manager::DEFAULT_LANGUAGE};
+use picker::{Picker, PickerDelegate};
+use settings::{Settings as _, update_settings_file};
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, prelude::*, v_flex};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, ui::HighlightedLabel};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(
+        |workspace: &mut Workspace, _window, _cx: &mut Context<Workspace>| {
+            workspace.register_action(toggle_lang_selector);
+        },
+    )
+    .detach();
+}
+
+fn toggle_lang_selector(
+    workspace: &mut Workspace,
+    _: &i18n::SwitchLanguage,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let fs = workspace.app_state().fs.clone();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        let delegate = I18nLangSelectorDelegate::new(cx.entity().downgrade(), fs, cx);
+        I18nLangSelector::new(delegate, window, cx)
+    });
+}
+
+pub struct I18nLangSelector {
+    picker: Entity<Picker<I18nLangSelectorDelegate>>,
+}
+
+impl ModalView for I18nLangSelector {}
+
+impl EventEmitter<DismissEvent> for I18nLangSelector {}
+
+impl Focusable for I18nLangSelector {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for I18nLangSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl I18nLangSelector {
+    pub fn new(
+        delegate: I18nLangSelectorDelegate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+/// What confirming an entry pins in the settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LanguageChoice {
+    /// Clear `ui_language` and negotiate from the system locales.
+    SystemDefault,
+    /// Pin the built-in English strings.
+    English,
+    /// Pin a specific language tag.
+    Language(String),
+}
+
+#[derive(Clone)]
+struct LanguageEntry {
+    choice: LanguageChoice,
+    /// The section the entry is listed under; `None` for the pinned entries
+    /// at the top.
+    section: Option<SharedString>,
+    native_name: SharedString,
+    english_name: Option<SharedString>,
+    /// Fraction of the reference keys the language's pack translates.
+    coverage: Option<f32>,
+    flag: Option<SharedString>,
+}
+
+impl LanguageEntry {
+    /// The text fuzzy matching runs against.
+    fn match_text(&self) -> String {
+        let mut text = self.native_name.to_string();
+        if let Some(english_name) = &self.english_name {
+            text.push(' ');
+            text.push_str(english_name);
+        }
+        if let LanguageChoice::Language(language) = &self.choice {
+            text.push(' ');
+            text.push_str(language);
+        }
+        text
+    }
+}
+
+enum PickerEntry {
+    Header(SharedString),
+    Language(usize, LanguageEntry),
+}
+
+pub struct I18nLangSelectorDelegate {
+    fs: Arc<dyn Fs>,
+    /// Every selectable entry, pinned ones first, then grouped by section.
+    languages: Vec<LanguageEntry>,
+    entries: Vec<PickerEntry>,
+    matches: Vec<StringMatch>,
+    active_choice: LanguageChoice,
+    selected_index: usize,
+    selector: WeakEntity<I18nLangSelector>,
+}
+
+impl I18nLangSelectorDelegate {
+    fn new(
+        selector: WeakEntity<I18nLangSelector>,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<I18nLangSelector>,
+    ) -> Self {
+        let settings = I18nSettings::get_global(cx);
+        let active_choice = match settings.ui_language.as_deref() {
+            Some(DEFAULT_LANGUAGE) => LanguageChoice::English,
+            Some(language) => LanguageChoice::Language(language.to_string()),
+            None => LanguageChoice::SystemDefault,
+        };
+        let languages = language_entries(&settings.recently_used_languages);
+        let mut this = Self {
+            fs,
+            languages,
+            entries: Vec::new(),
+            matches: Vec::new(),
+            active_choice,
+            selected_index: 0,
+            selector,
+        };
+        this.rebuild_entries(None);
+        this.selected_index = this
+            .entries
+            .iter()
+            .position(|entry| match entry {
+                PickerEntry::Language(_, entry) => entry.choice == this.active_choice,
+                PickerEntry::Header(_) => false,
+            })
+            .unwrap_or(0);
+        this
+    }
+
+    /// Rebuilds the visible list from the match set (or all languages when
+    /// there's no query), inserting a header whenever the section changes.
+    fn rebuild_entries(&mut self, matches: Option<&[StringMatch]>) {
+        let mut matched: Vec<usize> = match matches {
+            Some(matches) => matches.iter().map(|mat| mat.candidate_id).collect(),
+            None => (0..self.languages.len()).collect(),
+        };
+        // Keep the grouped order stable under filtering, so a section's
+        // header never repeats.
+        matched.sort_unstable();
+        self.entries.clear();
+        let mut last_section: Option<SharedString> = None;
+        for index in matched {
+            let Some(entry) = self.languages.get(index) else {
+                continue;
+            };
+            if entry.section != last_section {
+                if let Some(section) = &entry.section {
+                    self.entries.push(PickerEntry::Header(section.clone()));
+                }
+                last_section = entry.section.clone();
+            }
+            self.entries.push(PickerEntry::Language(index, entry.clone()));
+        }
+    }
+}
+
+/// How many picker choices `recently_used_languages` retains.
+const MAX_RECENT_LANGUAGES: usize = 5;
+
+/// Collects the selectable languages: the two always-available pinned
+/// entries, the recently chosen languages, then every language a
+/// registered source or installed pack provides, grouped into sections by
+/// language with the region/script variants listed inside, best-covered
+/// first.
+fn language_entries(recently_used: &[String]) -> Vec<LanguageEntry> {
+    let manager = I18nManager::global();
+    let mut entries = vec![
+        LanguageEntry {
+            choice: LanguageChoice::SystemDefault,
+            section: None,
+            native_name: "System default (auto)".into(),
+            english_name: None,
+            coverage: None,
+            flag: None,
+        },
+        LanguageEntry {
+            choice: LanguageChoice::English,
+            section: None,
+            native_name: "English (built-in)".into(),
+            english_name: None,
+            coverage: None,
+            flag: None,
+        },
+    ];
+
+    let mut languages: Vec<(String, Option<String>)> = Vec::new();
+    for (directory, metadata) in i18n::installed_packs() {
+        if metadata.language != DEFAULT_LANGUAGE {
+            languages.push((metadata.language, Some(directory)));
+        }
+    }
+    for language in manager.available_languages() {
+        if language != DEFAULT_LANGUAGE
+            && !languages.iter().any(|(tag, _)| *tag == language)
+        {
+            languages.push((language, None));
+        }
+    }
+
+    let mut grouped: Vec<LanguageEntry> = languages
+        .into_iter()
+        .map(|(language, pack_directory)| {
+            let section: SharedString = lang_codes::english_name(&language)
+                .map(SharedString::new_static)
+                .unwrap_or_else(|| language.clone().into());
+            let native_name: SharedString = installed_pack_name(pack_directory.as_deref())
+                .or_else(|| lang_codes::native_name(&language).map(String::from))
+                .unwrap_or_else(|| language.clone())
+                .into();
+            let coverage = language_coverage(&language, pack_directory.as_deref());
+            let flag = lang_codes::LanguageTag::parse(&language)
+                .and_then(|tag| tag.region)
+                .and_then(|region| flag_emoji(&region))
+                .map(SharedString::from);
+            LanguageEntry {
+                english_name: (section.as_ref() != native_name.as_ref())
+                    .then(|| section.clone()),
+                choice: LanguageChoice::Language(language),
+                section: Some(section),
+                native_name,
+                coverage,
+                flag,
+            }
+        })
+        .collect();
+    grouped.sort_by(|a, b| {
+        a.section
+            .cmp(&b.section)
+            // Within a section, best-covered variants first.
+            .then_with(|| {
+                b.coverage
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.coverage.unwrap_or(0.0))
+            })
+            .then_with(|| match (&a.choice, &b.choice) {
+                (LanguageChoice::Language(a), LanguageChoice::Language(b)) => a.cmp(b),
+                _ => std::cmp::Ordering::Equal,
+            })
+    });
+
+    let recent: Vec<LanguageEntry> = recently_used
+        .iter()
+        .filter_map(|language| {
+            grouped.iter().find(
+                |entry| matches!(&entry.choice, LanguageChoice::Language(tag) if tag == language),
+            )
+        })
+        .take(MAX_RECENT_LANGUAGES)
+        .map(|entry| LanguageEntry {
+            section: Some("Recent".into()),
+            ..entry.clone()
+        })
+        .collect();
+
+    entries.extend(recent);
+    entries.extend(grouped);
+    entries
+}
+
+/// The human-readable name from an installed pack's metadata.
+fn installed_pack_name(pack_directory: Option<&str>) -> Option<String> {
+    let directory = pack_directory?;
+    i18n::pack::PackMetadata::load(&paths::language_packs_dir().join(directory))
+        .ok()
+        .map(|metadata| metadata.name)
+}
+
+/// Coverage for a language: from the manager when its source is already
+/// registered, otherwise counted out of the installed pack's file.
+fn language_coverage(language: &str, pack_directory: Option<&str>) -> Option<f32> {
+    let manager = I18nManager::global();
+    if manager.available_languages().contains(&language.to_string()) {
+        return Some(manager.coverage(language));
+    }
+    let directory = paths::language_packs_dir().join(pack_directory?);
+    let translation_path = directory.join("translation.json");
+    let file = if translation_path.exists() {
+        TranslationFile::load(language, &translation_path).log_err()?
+    } else {
+        TranslationFile::load_merged(
+            language,
+            &directory.join(i18n::pack::SPLIT_TRANSLATIONS_DIR_NAME),
+        )
+        .log_err()?
+    };
+    let reference = i18n::default_texts();
+    if reference.is_empty() {
+        return Some(1.0);
+    }
+    let translated = file
+        .entries
+        .keys()
+        .filter(|key| reference.contains_key(i18n::defaults::canonical_key(key)))
+        .count();
+    Some(translated as f32 / reference.len() as f32)
+}
+
+/// The regional-indicator emoji for a two-letter region subtag.
+fn flag_emoji(region: &str) -> Option<String> {
+    if region.len() != 2 {
+        return None;
+    }
+    region
+        .chars()
+        .map(|c| {
+            c.is_ascii_alphabetic()
+                .then(|| char::from_u32(0x1F1E6 + (c.to_ascii_uppercase() as u32 - 'A' as u32)))
+                .flatten()
+        })
+        .collect()
+}
+
+impl PickerDelegate for I18nLangSelectorDelegate {
+    type ListItem = ui::ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select UI Language...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        // Wrap rather than clamp, so stepping past the last entry returns
+        // to the top.
+        self.selected_index = if self.entries.is_empty() {
+            0
+        } else {
+            ix % self.entries.len()
+        };
+        cx.notify();
+    }
+
+    fn can_select(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> bool {
+        match self.entries.get(ix) {
+            Some(PickerEntry::Language(..)) => true,
+            Some(PickerEntry::Header(_)) | None => false,
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .languages
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| StringMatchCandidate::new(id, &entry.match_text()))
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                None
+            } else {
+                Some(
+                    match_strings(
+                        &candidates,
+                        &query,
+                        false,
+                        100,
+                        &Default::default(),
+                        background,
+                    )
+                    .await,
+                )
+            };
+
+            this.update(cx, |this, cx| {
+                this.delegate.matches = matches.clone().unwrap_or_default();
+                this.delegate.rebuild_entries(matches.as_deref());
+                this.delegate.selected_index = this
+                    .delegate
+                    .entries
+                    .iter()
+                    .position(|entry| matches!(entry, PickerEntry::Language(..)))
+                    .unwrap_or(0);
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(PickerEntry::Language(_, entry)) = self.entries.get(self.selected_index) {
+            let choice = entry.choice.clone();
+            // apply_language_settings observes the store, so the switch
+            // itself happens when the settings file lands.
+            update_settings_file::<I18nSettings>(self.fs.clone(), cx, move |settings, _| {
+                match choice {
+                    LanguageChoice::SystemDefault => {
+                        settings.ui_language = None;
+                        settings.auto_detect_system_i18n_lang = true;
+                    }
+                    LanguageChoice::English => {
+                        settings.ui_language = Some(DEFAULT_LANGUAGE.to_string());
+                    }
+                    LanguageChoice::Language(language) => {
+                        settings
+                            .recently_used_languages
+                            .retain(|recent| *recent != language);
+                        settings
+                            .recently_used_languages
+                            .insert(0, language.clone());
+                        settings.recently_used_languages.truncate(MAX_RECENT_LANGUAGES);
+                        settings.ui_language = Some(language);
+                    }
+                }
+            });
+        }
+        self.selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .ok();
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        match self.entries.get(ix)? {
+            PickerEntry::Header(section) => Some(
+                ListItem::new(ix).inset(true).disabled(true).child(
+                    Label::new(section.clone())
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                ),
+            ),
+            PickerEntry::Language(language_index, entry) => {
+                let positions = self
+                    .matches
+                    .iter()
+                    .find(|mat| mat.candidate_id == *language_index)
+                    .map(|mat| mat.positions.clone())
+                    .unwrap_or_default();
+                // The match text starts with the native name, so only the
+                // positions inside it highlight.
+                let positions = positions
+                    .into_iter()
+                    .filter(|position| *position < entry.native_name.len())
+                    .collect();
+                let is_active = entry.choice == self.active_choice;
+                Some(
+                    ListItem::new(ix)
+                        .inset(true)
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(selected)
+                        .when_some(entry.flag.clone(), |this, flag| {
+                            this.start_slot(Label::new(flag))
+                        })
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(HighlightedLabel::new(
+                                    entry.native_name.clone(),
+                                    positions,
+                                ))
+                                .when_some(entry.english_name.clone(), |this, name| {
+                                    this.child(
+                                        Label::new(name)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                }),
+                        )
+                        .end_slot(
+                            h_flex()
+                                .gap_2()
+                                .when_some(entry.coverage, |this, coverage| {
+                                    this.child(
+                                        Label::new(format!(
+                                            "{:.0}%",
+                                            (coverage * 100.0).round()
+                                        ))
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                    )
+                                })
+                                .when(is_active, |this| {
+                                    this.child(
+                                        Icon::new(IconName::Check)
+                                            .color(Color::Accent)
+                                            .size(IconSize::Small),
+                                    )
+                                }),
+                        ),
+                )
+            }
+        }
+    }
+}
@@ -78,13 +78,14 @@ impl JsonLspAdapter {
     fn get_workspace_config(language_names: Vec<String>, cx: &mut App) -> Value {
         let keymap_schema = KeymapFile::generate_json_schema_for_registered_actions(cx);
         let font_names = &cx.text_system().all_font_names();
-        let settings_schema = cx.global::<SettingsStore>().json_schema(
+        let mut settings_schema = cx.global::<SettingsStore>().json_schema(
             &SettingsJsonSchemaParams {
                 language_names: &language_names,
                 font_names,
             },
             cx,
         );
+        i18n::localize_settings_schema(&mut settings_schema);
         let tasks_schema = task::TaskTemplates::generate_json_schema();
         let debug_schema = task::DebugTaskFile::generate_json_schema();
         let snippets_schema = snippet_provider::format::VsSnippetsFile::generate_json_schema();
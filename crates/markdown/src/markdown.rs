@@ -383,6 +383,39 @@ impl Focusable for Markdown {
     }
 }
 
+/// A [`Markdown`] entity bound to a translation key instead of a fixed
+/// source, for longer translated copy (welcome pages, empty states,
+/// onboarding tips; see `i18n::defaults::MARKDOWN_KEYS`). Call
+/// [`Self::entity`] from `render` and hand the result to
+/// [`MarkdownElement::new`]: the key is re-resolved on every frame, so a
+/// language switch re-parses on the next redraw the way plain
+/// [`i18n::TranslatedString`] labels re-resolve.
+pub struct TranslatedMarkdown {
+    key: SharedString,
+    markdown: Entity<Markdown>,
+}
+
+impl TranslatedMarkdown {
+    pub fn new(key: impl Into<SharedString>, cx: &mut App) -> Self {
+        let key = key.into();
+        let source = i18n::I18nManager::global().get_text(&key);
+        Self {
+            markdown: cx.new(|cx| Markdown::new(source, None, None, cx)),
+            key,
+        }
+    }
+
+    /// The underlying entity with its source brought up to date for the
+    /// current language. [`Markdown::reset`] is a no-op when the resolved
+    /// text hasn't changed, so calling this per frame doesn't re-parse.
+    pub fn entity(&self, cx: &mut App) -> Entity<Markdown> {
+        let source = i18n::I18nManager::global().get_text(&self.key);
+        self.markdown
+            .update(cx, |markdown, cx| markdown.reset(source, cx));
+        self.markdown.clone()
+    }
+}
+
 #[derive(Copy, Clone, Default, Debug)]
 struct Selection {
     start: usize,
@@ -1857,6 +1890,29 @@ mod tests {
         rendered.text
     }
 
+    #[gpui::test]
+    fn test_translated_markdown_follows_the_language(cx: &mut TestAppContext) {
+        let translated =
+            cx.update(|cx| TranslatedMarkdown::new("i18n.welcome.tip_command_palette", cx));
+        translated.markdown.read_with(cx, |markdown, _| {
+            assert!(markdown.source().contains("command palette"));
+        });
+
+        i18n::test_utils::with_locale(
+            "zz-markdown-test",
+            &[(
+                "i18n.welcome.tip_command_palette",
+                "用 `cmd-shift-p` 打开命令面板。",
+            )],
+            || {
+                let markdown = cx.update(|cx| translated.entity(cx));
+                markdown.read_with(cx, |markdown, _| {
+                    assert!(markdown.source().contains("命令面板"));
+                });
+            },
+        );
+    }
+
     #[test]
     fn test_escape() {
         assert_eq!(Markdown::escape("hello `world`"), "hello \\`world\\`");
@@ -191,6 +191,19 @@ pub fn settings_file() -> &'static PathBuf {
     SETTINGS_FILE.get_or_init(|| config_dir().join("settings.json"))
 }
 
+/// Returns the path to the user's UI translation overrides file.
+pub fn user_translation_overrides_file() -> &'static PathBuf {
+    static TRANSLATION_OVERRIDES_FILE: OnceLock<PathBuf> = OnceLock::new();
+    TRANSLATION_OVERRIDES_FILE.get_or_init(|| config_dir().join("translations.json"))
+}
+
+/// Returns the path to the directory installed UI language packs live in,
+/// one subdirectory per pack.
+pub fn language_packs_dir() -> &'static PathBuf {
+    static LANGUAGE_PACKS_DIR: OnceLock<PathBuf> = OnceLock::new();
+    LANGUAGE_PACKS_DIR.get_or_init(|| data_dir().join("language_packs"))
+}
+
 /// Returns the path to the global settings file.
 pub fn global_settings_file() -> &'static PathBuf {
     static GLOBAL_SETTINGS_FILE: OnceLock<PathBuf> = OnceLock::new();
@@ -747,16 +747,57 @@ pub fn reset_buffer_font_size(cx: &mut App) {
 // TODO: Make private, change usages to use `get_ui_font_size` instead.
 #[allow(missing_docs)]
 pub fn setup_ui_font(window: &mut Window, cx: &mut App) -> gpui::Font {
-    let (ui_font, ui_font_size) = {
+    let (mut ui_font, ui_font_size) = {
         let theme_settings = ThemeSettings::get_global(cx);
         let font = theme_settings.ui_font.clone();
         (font, theme_settings.ui_font_size(cx))
     };
 
+    if let Some(hints) = i18n::I18nManager::global().font_hints() {
+        ui_font.fallbacks = Some(with_language_fallbacks(&ui_font, &hints));
+    }
+
     window.set_rem_size(ui_font_size);
     ui_font
 }
 
+/// Common CJK-capable system families, appended when the active language
+/// pack sets `requires_cjk_fallback`. Families missing from the system are
+/// skipped at resolution time, so one list can cover every platform.
+static CJK_FALLBACK_FONTS: &[&str] = &[
+    "PingFang SC",      // macOS
+    "Hiragino Sans",    // macOS (Japanese)
+    "Microsoft YaHei",  // Windows
+    "Yu Gothic UI",     // Windows (Japanese)
+    "Malgun Gothic",    // Windows (Korean)
+    "Noto Sans CJK SC", // Linux
+];
+
+/// The UI font's fallback list with the active language pack's hints
+/// appended: the user's configured fallbacks keep priority, then the pack's
+/// preferred families, then the common CJK families when the pack asks for
+/// them.
+fn with_language_fallbacks(font: &Font, hints: &i18n::pack::FontHints) -> FontFallbacks {
+    let mut families: Vec<String> = font
+        .fallbacks
+        .as_ref()
+        .map(|fallbacks| fallbacks.fallback_list().to_vec())
+        .unwrap_or_default();
+    let hinted = hints.preferred_fonts.iter().cloned().chain(
+        hints
+            .requires_cjk_fallback
+            .then(|| CJK_FALLBACK_FONTS.iter().map(|family| family.to_string()))
+            .into_iter()
+            .flatten(),
+    );
+    for family in hinted {
+        if family.as_str() != font.family.as_ref() && !families.contains(&family) {
+            families.push(family);
+        }
+    }
+    FontFallbacks::from_fonts(families)
+}
+
 /// Sets the adjusted UI font size.
 pub fn adjust_ui_font_size(cx: &mut App, mut f: impl FnMut(&mut Pixels)) {
     let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
@@ -1028,6 +1069,33 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn language_font_hints_append_after_configured_fallbacks() {
+        let font = Font {
+            family: "Zed Plex Sans".into(),
+            features: FontFeatures::default(),
+            fallbacks: Some(FontFallbacks::from_fonts(vec![
+                "My Fallback".to_string(),
+                "Noto Sans Thai".to_string(),
+            ])),
+            weight: FontWeight::default(),
+            style: FontStyle::default(),
+        };
+        let hints = i18n::pack::FontHints {
+            preferred_fonts: vec![
+                "Noto Sans Thai".to_string(),
+                "Zed Plex Sans".to_string(),
+                "Sarabun".to_string(),
+            ],
+            requires_cjk_fallback: true,
+        };
+        let merged = with_language_fallbacks(&font, &hints);
+        let merged: Vec<&str> = merged.fallback_list().iter().map(String::as_str).collect();
+        let mut expected = vec!["My Fallback", "Noto Sans Thai", "Sarabun"];
+        expected.extend(CJK_FALLBACK_FONTS);
+        assert_eq!(merged, expected);
+    }
+
     #[test]
     fn test_buffer_line_height_deserialize_valid() {
         assert_eq!(
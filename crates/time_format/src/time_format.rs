@@ -1,5 +1,104 @@
+use std::sync::RwLock;
 use time::{OffsetDateTime, UtcOffset};
 
+pub use time::Weekday;
+
+/// The order of the day, month, and year fields in a short numeric date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `10/04/1990`
+    DayMonthYear,
+    /// `04/10/1990`
+    MonthDayYear,
+    /// `1990-04-10`
+    YearMonthDay,
+}
+
+/// User-configured overrides for the formatting conventions otherwise
+/// derived from the locale, so an English UI can keep local date
+/// conventions (and vice versa). `None` fields keep the locale-derived
+/// behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatPreferences {
+    pub date_order: Option<DateOrder>,
+    pub twelve_hour_clock: Option<bool>,
+    pub first_day_of_week: Option<Weekday>,
+}
+
+static PREFERENCES: RwLock<FormatPreferences> = RwLock::new(FormatPreferences {
+    date_order: None,
+    twelve_hour_clock: None,
+    first_day_of_week: None,
+});
+
+/// Applies the user's formatting overrides process-wide. Pass
+/// `FormatPreferences::default()` to return to locale-derived behavior.
+pub fn set_format_preferences(preferences: FormatPreferences) {
+    if let Ok(mut guard) = PREFERENCES.write() {
+        *guard = preferences;
+    }
+}
+
+fn preferences() -> FormatPreferences {
+    PREFERENCES.read().map(|guard| *guard).unwrap_or_default()
+}
+
+/// The first day of the week for calendar-like UI: the user's override when
+/// set, otherwise derived from the locale's region.
+pub fn first_day_of_week() -> Weekday {
+    if let Some(day) = preferences().first_day_of_week {
+        return day;
+    }
+    let locale = sys_locale::get_locale().unwrap_or_else(|| String::from("en-US"));
+    first_day_of_week_for_locale(&locale)
+}
+
+fn first_day_of_week_for_locale(locale: &str) -> Weekday {
+    let locale = locale.split(['.', '@']).next().unwrap_or("");
+    let region = locale
+        .split(['-', '_'])
+        .skip(1)
+        .find(|segment| segment.len() == 2 && segment.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|segment| segment.to_ascii_uppercase());
+    match region.as_deref() {
+        Some(
+            "US" | "CA" | "JP" | "KR" | "TW" | "HK" | "IL" | "BR" | "MX" | "CO" | "PE" | "PH"
+            | "ZA",
+        ) => Weekday::Sunday,
+        Some("SA" | "EG" | "JO" | "AE" | "IQ" | "SY" | "YE" | "BH" | "KW" | "QA") => {
+            Weekday::Saturday
+        }
+        _ => Weekday::Monday,
+    }
+}
+
+/// Renders a date in the given field order, using the separators each order
+/// is conventionally written with.
+fn format_numeric_date(date: time::Date, order: DateOrder) -> String {
+    match order {
+        DateOrder::MonthDayYear => {
+            format!("{:02}/{:02}/{}", date.month() as u32, date.day(), date.year())
+        }
+        DateOrder::DayMonthYear => {
+            format!("{:02}/{:02}/{}", date.day(), date.month() as u32, date.year())
+        }
+        DateOrder::YearMonthDay => {
+            format!("{}-{:02}-{:02}", date.year(), date.month() as u32, date.day())
+        }
+    }
+}
+
+/// The date order to use: the user's override when set, otherwise inferred
+/// the way the naive formatting always has — month-first for 12-hour
+/// locales, day-first elsewhere.
+fn effective_date_order(is_12_hour_time: bool) -> DateOrder {
+    preferences().date_order.unwrap_or(if is_12_hour_time {
+        DateOrder::MonthDayYear
+    } else {
+        DateOrder::DayMonthYear
+    })
+}
+
 /// The formatting style for a timestamp.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimestampFormat {
@@ -73,7 +172,7 @@ fn format_absolute_date(
     #[cfg(target_os = "macos")]
     {
         if !enhanced_date_formatting {
-            return macos::format_date(&timestamp);
+            return format_date_system(&timestamp);
         }
 
         let timestamp_date = timestamp.date();
@@ -83,7 +182,7 @@ fn format_absolute_date(
         } else if reference_date.previous_day() == Some(timestamp_date) {
             "Yesterday".to_string()
         } else {
-            macos::format_date(&timestamp)
+            format_date_system(&timestamp)
         }
     }
     #[cfg(not(target_os = "macos"))]
@@ -95,7 +194,7 @@ fn format_absolute_date(
         format_timestamp_naive_date(
             timestamp,
             reference,
-            is_12_hour_time_by_locale(current_locale.as_str()),
+            is_12_hour_time(current_locale.as_str()),
         )
     }
 }
@@ -103,7 +202,7 @@ fn format_absolute_date(
 fn format_absolute_time(timestamp: OffsetDateTime) -> String {
     #[cfg(target_os = "macos")]
     {
-        macos::format_time(&timestamp)
+        format_time_system(&timestamp)
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -113,7 +212,7 @@ fn format_absolute_time(timestamp: OffsetDateTime) -> String {
             .get_or_init(|| sys_locale::get_locale().unwrap_or_else(|| String::from("en-US")));
         format_timestamp_naive_time(
             timestamp,
-            is_12_hour_time_by_locale(current_locale.as_str()),
+            is_12_hour_time(current_locale.as_str()),
         )
     }
 }
@@ -163,7 +262,7 @@ fn format_absolute_date_medium(
     #[cfg(target_os = "macos")]
     {
         if !enhanced_formatting {
-            return macos::format_date_medium(&timestamp);
+            return format_date_medium_system(&timestamp);
         }
 
         let timestamp_date = timestamp.date();
@@ -173,7 +272,7 @@ fn format_absolute_date_medium(
         } else if reference_date.previous_day() == Some(timestamp_date) {
             "Yesterday".to_string()
         } else {
-            macos::format_date_medium(&timestamp)
+            format_date_medium_system(&timestamp)
         }
     }
     #[cfg(not(target_os = "macos"))]
@@ -185,7 +284,7 @@ fn format_absolute_date_medium(
         if !enhanced_formatting {
             return format_timestamp_naive_date_medium(
                 timestamp,
-                is_12_hour_time_by_locale(current_locale.as_str()),
+                is_12_hour_time(current_locale.as_str()),
             );
         }
 
@@ -198,7 +297,7 @@ fn format_absolute_date_medium(
         } else {
             format_timestamp_naive_date_medium(
                 timestamp,
-                is_12_hour_time_by_locale(current_locale.as_str()),
+                is_12_hour_time(current_locale.as_str()),
             )
         }
     }
@@ -345,20 +444,7 @@ fn format_timestamp_naive_date(
     } else if reference_local_date.previous_day() == Some(timestamp_local_date) {
         "Yesterday".to_string()
     } else {
-        match is_12_hour_time {
-            true => format!(
-                "{:02}/{:02}/{}",
-                timestamp_local_date.month() as u32,
-                timestamp_local_date.day(),
-                timestamp_local_date.year()
-            ),
-            false => format!(
-                "{:02}/{:02}/{}",
-                timestamp_local_date.day(),
-                timestamp_local_date.month() as u32,
-                timestamp_local_date.year()
-            ),
-        }
+        format_numeric_date(timestamp_local_date, effective_date_order(is_12_hour_time))
     }
 }
 
@@ -369,20 +455,7 @@ fn format_timestamp_naive_date_medium(
 ) -> String {
     let timestamp_local_date = timestamp_local.date();
 
-    match is_12_hour_time {
-        true => format!(
-            "{:02}/{:02}/{}",
-            timestamp_local_date.month() as u32,
-            timestamp_local_date.day(),
-            timestamp_local_date.year()
-        ),
-        false => format!(
-            "{:02}/{:02}/{}",
-            timestamp_local_date.day(),
-            timestamp_local_date.month() as u32,
-            timestamp_local_date.year()
-        ),
-    }
+    format_numeric_date(timestamp_local_date, effective_date_order(is_12_hour_time))
 }
 
 pub fn format_timestamp_naive(
@@ -399,20 +472,8 @@ pub fn format_timestamp_naive(
     } else if reference_local_date.previous_day() == Some(timestamp_local_date) {
         format!("Yesterday at {}", formatted_time)
     } else {
-        let formatted_date = match is_12_hour_time {
-            true => format!(
-                "{:02}/{:02}/{}",
-                timestamp_local_date.month() as u32,
-                timestamp_local_date.day(),
-                timestamp_local_date.year()
-            ),
-            false => format!(
-                "{:02}/{:02}/{}",
-                timestamp_local_date.day(),
-                timestamp_local_date.month() as u32,
-                timestamp_local_date.year()
-            ),
-        };
+        let formatted_date =
+            format_numeric_date(timestamp_local_date, effective_date_order(is_12_hour_time));
         format!("{} {}", formatted_date, formatted_time)
     }
 }
@@ -425,10 +486,19 @@ fn format_timestamp_fallback(timestamp: OffsetDateTime, reference: OffsetDateTim
     let current_locale = CURRENT_LOCALE
         .get_or_init(|| sys_locale::get_locale().unwrap_or_else(|| String::from("en-US")));
 
-    let is_12_hour_time = is_12_hour_time_by_locale(current_locale.as_str());
+    let is_12_hour_time = is_12_hour_time(current_locale.as_str());
     format_timestamp_naive(timestamp, reference, is_12_hour_time)
 }
 
+/// The clock to use: the user's override when set, otherwise derived from
+/// the locale.
+#[cfg(not(target_os = "macos"))]
+fn is_12_hour_time(locale: &str) -> bool {
+    preferences()
+        .twelve_hour_clock
+        .unwrap_or_else(|| is_12_hour_time_by_locale(locale))
+}
+
 /// Returns `true` if the locale is recognized as a 12-hour time locale.
 #[cfg(not(target_os = "macos"))]
 fn is_12_hour_time_by_locale(locale: &str) -> bool {
@@ -447,6 +517,33 @@ fn is_12_hour_time_by_locale(locale: &str) -> bool {
     .contains(&locale)
 }
 
+/// The system's short date format, unless the user overrode the field
+/// order.
+#[cfg(target_os = "macos")]
+fn format_date_system(timestamp: &OffsetDateTime) -> String {
+    match preferences().date_order {
+        Some(order) => format_numeric_date(timestamp.date(), order),
+        None => macos::format_date(timestamp),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn format_date_medium_system(timestamp: &OffsetDateTime) -> String {
+    match preferences().date_order {
+        Some(order) => format_numeric_date(timestamp.date(), order),
+        None => macos::format_date_medium(timestamp),
+    }
+}
+
+/// The system's short time format, unless the user overrode the clock.
+#[cfg(target_os = "macos")]
+fn format_time_system(timestamp: &OffsetDateTime) -> String {
+    match preferences().twelve_hour_clock {
+        Some(twelve_hour) => format_timestamp_naive_time(*timestamp, twelve_hour),
+        None => macos::format_time(timestamp),
+    }
+}
+
 #[cfg(target_os = "macos")]
 mod macos {
     use core_foundation::base::TCFType;
@@ -910,6 +1007,24 @@ mod tests {
         assert_eq!(format_relative_date(next_month(), reference), "1 year ago");
     }
 
+    #[test]
+    fn test_numeric_date_orders() {
+        let date =
+            time::Date::from_calendar_date(1990, time::Month::April, 10).unwrap();
+        assert_eq!(format_numeric_date(date, DateOrder::MonthDayYear), "04/10/1990");
+        assert_eq!(format_numeric_date(date, DateOrder::DayMonthYear), "10/04/1990");
+        assert_eq!(format_numeric_date(date, DateOrder::YearMonthDay), "1990-04-10");
+    }
+
+    #[test]
+    fn test_first_day_of_week_by_locale() {
+        assert_eq!(first_day_of_week_for_locale("en-US"), Weekday::Sunday);
+        assert_eq!(first_day_of_week_for_locale("pt_BR.UTF-8"), Weekday::Sunday);
+        assert_eq!(first_day_of_week_for_locale("de-DE"), Weekday::Monday);
+        assert_eq!(first_day_of_week_for_locale("ar-SA"), Weekday::Saturday);
+        assert_eq!(first_day_of_week_for_locale("fr"), Weekday::Monday);
+    }
+
     #[test]
     fn test_calculate_month_difference() {
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
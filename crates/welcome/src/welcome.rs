@@ -238,7 +238,7 @@ impl Render for WelcomePage {
                                             .icon_position(IconPosition::Start)
                                             .on_click(cx.listener(|_, _, _, cx| {
                                                 telemetry::event!("Welcome Documentation Viewed");
-                                                cx.open_url(DOCS_URL);
+                                                cx.open_url(&i18n::localize_docs_url(DOCS_URL));
                                             })),
                                     )
                                     .child(
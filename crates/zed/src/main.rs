@@ -420,6 +420,8 @@ fn main() {
         let node_runtime = NodeRuntime::new(client.http_client(), Some(shell_env_loaded_rx), rx);
 
         debug_adapter_extension::init(extension_host_proxy.clone(), cx);
+        i18n::init(cx);
+        i18n_extension::init(extension_host_proxy.clone());
         language::init(cx);
         language_extension::init(extension_host_proxy.clone(), languages.clone());
         languages::init(languages.clone(), node_runtime.clone(), cx);
@@ -559,6 +561,7 @@ fn main() {
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
         language_selector::init(cx);
+        i18n_selector::init(cx);
         toolchain_selector::init(cx);
         theme_selector::init(cx);
         language_tools::init(cx);
@@ -864,6 +867,9 @@ async fn installation_id() -> Result<IdType> {
 }
 
 async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp) -> Result<()> {
+    // Don't paint the first window before the startup language preload has
+    // published its strings, or the frame flashes English before switching.
+    i18n::preload_complete().await;
     if let Some(locations) = restorable_workspace_locations(cx, &app_state).await {
         for location in locations {
             match location {
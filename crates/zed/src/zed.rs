@@ -92,6 +92,7 @@ actions!(
         ResetDatabase,
         ShowAll,
         ToggleFullScreen,
+        ToggleI18nKeyOverlay,
         Zoom,
         TestPanic,
     ]
@@ -108,9 +109,160 @@ pub fn init(cx: &mut App) {
 
     cx.on_action(|_: &RestoreBanner, cx| title_bar::restore_banner(cx));
 
+    cx.on_action(|_: &ToggleI18nKeyOverlay, cx| {
+        let manager = i18n::I18nManager::global();
+        manager.set_key_overlay(!manager.key_overlay_enabled());
+        cx.refresh_windows();
+    });
+
     if ReleaseChannel::global(cx) == ReleaseChannel::Dev {
         cx.on_action(test_panic);
     }
+
+    suggest_language_pack(cx);
+    check_language_pack_updates(cx);
+}
+
+/// Checks the installed language packs against the extension store and
+/// offers a one-click update when a newer version is published; new Zed
+/// releases add keys that stale packs won't have.
+fn check_language_pack_updates(cx: &mut App) {
+    let Some(app_state) = AppState::global(cx).upgrade() else {
+        return;
+    };
+    let installed: Vec<(String, String)> = i18n::installed_packs()
+        .into_iter()
+        .map(|(extension_id, metadata)| (extension_id, metadata.version))
+        .collect();
+    if installed.is_empty() {
+        return;
+    }
+    let client = app_state.client.http_client();
+    let trusted_keys = i18n::I18nSettings::get_global(cx)
+        .trusted_language_pack_keys
+        .clone();
+
+    cx.spawn(async move |cx| {
+        let importer = i18n::I18nImporter::new(
+            client.clone(),
+            paths::language_packs_dir().clone(),
+            trusted_keys.clone(),
+        );
+        let updates = match importer.check_for_updates(&client, &installed).await {
+            Ok(updates) => updates,
+            Err(error) => {
+                log::warn!("language pack update check failed: {error:#}");
+                return;
+            }
+        };
+        cx.update(|cx| {
+            for update in updates {
+                notify_language_pack_update(update, client.clone(), trusted_keys.clone(), cx);
+            }
+        })
+        .ok();
+    })
+    .detach();
+}
+
+fn notify_language_pack_update(
+    update: i18n::importer::PackUpdate,
+    client: Arc<http_client::HttpClientWithUrl>,
+    trusted_keys: Vec<String>,
+    cx: &mut App,
+) {
+    struct LanguagePackUpdate;
+    let notification_id =
+        NotificationId::composite::<LanguagePackUpdate>(update.extension_id.clone());
+    show_app_notification(notification_id, cx, move |cx| {
+        let update = update.clone();
+        let client = client.clone();
+        let trusted_keys = trusted_keys.clone();
+        cx.new(|cx| {
+            MessageNotification::new(
+                format!(
+                    "Language pack {} {} is available (you have {}).",
+                    update.extension_id, update.latest_version, update.installed_version
+                ),
+                cx,
+            )
+            .primary_message("Update")
+            .primary_on_click(move |_, cx| {
+                let extension_id = update.extension_id.clone();
+                let client = client.clone();
+                let trusted_keys = trusted_keys.clone();
+                cx.spawn(async move |_, cx| {
+                    let importer = i18n::I18nImporter::new(
+                        client.clone(),
+                        paths::language_packs_dir().clone(),
+                        trusted_keys,
+                    );
+                    importer
+                        .import_from_marketplace(&client, &extension_id)
+                        .await?;
+                    cx.update(i18n::reload_current_language)
+                })
+                .detach_and_log_err(cx);
+                cx.emit(DismissEvent);
+            })
+        })
+    });
+}
+
+/// Offers to install a language pack when the system prefers a language no
+/// installed pack provides. Shown at startup; "Never Ask Again" is persisted
+/// in the i18n settings.
+fn suggest_language_pack(cx: &mut App) {
+    let settings = i18n::I18nSettings::get_global(cx);
+    let Some(language) = i18n::suggested_language(
+        settings,
+        &i18n::lang_codes::system_locales(),
+        &i18n::I18nManager::global().available_languages(),
+    ) else {
+        return;
+    };
+    let extension_id = i18n::lang_codes::extension_id_for_language(&language);
+
+    struct LanguagePackSuggestion;
+    show_app_notification(
+        NotificationId::unique::<LanguagePackSuggestion>(),
+        cx,
+        move |cx| {
+            let language = language.clone();
+            let extension_id = extension_id.clone();
+            cx.new(|cx| {
+                MessageNotification::new(
+                    format!(
+                        "Zed can be translated into your system language ({language}) by \
+                         installing the {extension_id} extension."
+                    ),
+                    cx,
+                )
+                .primary_message("Browse Extensions")
+                .primary_on_click(|window, cx| {
+                    window.dispatch_action(
+                        zed_actions::Extensions {
+                            category_filter: None,
+                        }
+                        .boxed_clone(),
+                        cx,
+                    );
+                    cx.emit(DismissEvent);
+                })
+                .secondary_message("Never Ask Again")
+                .secondary_on_click(|_, cx| {
+                    if let Some(app_state) = AppState::global(cx).upgrade() {
+                        update_settings_file::<i18n::I18nSettings>(
+                            app_state.fs.clone(),
+                            cx,
+                            |settings, _| settings.suggest_language_packs = false,
+                        );
+                    }
+                    cx.emit(DismissEvent);
+                })
+            })
+        },
+    );
 }
 
 fn bind_on_window_closed(cx: &mut App) -> Option<gpui::Subscription> {
@@ -481,7 +633,9 @@ fn register_actions(
 ) {
     workspace
         .register_action(about)
-        .register_action(|_, _: &OpenDocs, _, cx| cx.open_url(DOCS_URL))
+        .register_action(|_, _: &OpenDocs, _, cx| {
+            cx.open_url(&i18n::localize_docs_url(DOCS_URL))
+        })
         .register_action(|_, _: &Minimize, window, _| {
             window.minimize_window();
         })
@@ -494,7 +648,11 @@ fn register_actions(
         .register_action(|_, action: &OpenZedUrl, _, cx| {
             OpenListener::global(cx).open_urls(vec![action.url.clone()])
         })
-        .register_action(|_, action: &OpenBrowser, _window, cx| cx.open_url(&action.url))
+        .register_action(|_, action: &OpenBrowser, _window, cx| {
+            // Routes `Help → Documentation` (and any other docs link opened
+            // through this action) to the active pack's translated docs.
+            cx.open_url(&i18n::localize_docs_url(&action.url))
+        })
         .register_action(|workspace, _: &workspace::Open, window, cx| {
             telemetry::event!("Project Opened");
             let paths = workspace.prompt_for_open_path(
@@ -940,7 +1098,24 @@ fn about(
         ""
     };
     let message = format!("{release_channel} {version} {debug}");
-    let detail = AppCommitSha::try_global(cx).map(|sha| sha.0.clone());
+    let mut detail = AppCommitSha::try_global(cx).map(|sha| sha.0.clone());
+    let language = i18n::I18nManager::global().current_language();
+    if language != i18n::manager::DEFAULT_LANGUAGE {
+        let translators = i18n::I18nManager::global().translators(&language);
+        if !translators.is_empty() {
+            let credits = format!("Translated by {}", translators.join(", "));
+            detail = Some(match detail {
+                Some(detail) => format!("{detail}\n{credits}"),
+                None => credits,
+            });
+        }
+        let percent = (i18n::I18nManager::global().coverage(&language) * 100.0).round();
+        let coverage = format!("UI translated into {language}: {percent:.0}%");
+        detail = Some(match detail {
+            Some(detail) => format!("{detail}\n{coverage}"),
+            None => coverage,
+        });
+    }
 
     let prompt = window.prompt(PromptLevel::Info, &message, detail.as_deref(), &["OK"], cx);
     cx.foreground_executor()